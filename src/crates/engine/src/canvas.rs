@@ -4,6 +4,12 @@ pub trait Canvas {
     fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> Result<()>;
 }
 
+impl<T: Canvas> Canvas for Option<T> {
+    fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> Result<()> {
+        self.as_mut().map_or(Ok(()), |canvas| canvas.set_pixel(x, y, colour))
+    }
+}
+
 pub trait WellKnownColours {
     const BLACK: Colour;
     const TRANSPARENT: Colour;
@@ -0,0 +1,57 @@
+use super::{Canvas, Colour, Error, Result};
+
+/*
+    A canvas that only remembers the colour index of each pixel, so that frames can be rendered without a
+    terminal.  The pixels can be a fixed-size array for targets without an allocator, or anything else that
+    can be borrowed as a slice.
+*/
+pub struct FrameBufferCanvas<TPixels: AsRef<[Colour]>> {
+    width_pixels: u16,
+    height_pixels: u16,
+    pixels: TPixels
+}
+
+impl<TPixels: AsRef<[Colour]>> FrameBufferCanvas<TPixels> {
+    pub fn new(width_pixels: u16, height_pixels: u16, pixels: TPixels) -> Self {
+        assert!(pixels.as_ref().len() >= usize::from(width_pixels) * usize::from(height_pixels), "Pixels must cover the Width and Height");
+        Self { width_pixels, height_pixels, pixels }
+    }
+
+    pub fn width_pixels(&self) -> u16 { self.width_pixels }
+
+    pub fn height_pixels(&self) -> u16 { self.height_pixels }
+
+    pub fn pixel_at(&self, x: u16, y: u16) -> Option<Colour> {
+        self.index_of(x, y).map(|index| self.pixels.as_ref()[index])
+    }
+
+    pub fn pixels(&self) -> &[Colour] {
+        &self.pixels.as_ref()[..usize::from(self.width_pixels) * usize::from(self.height_pixels)]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Colour]> {
+        self.pixels().chunks_exact(usize::from(self.width_pixels))
+    }
+
+    fn index_of(&self, x: u16, y: u16) -> Option<usize> {
+        if x < self.width_pixels && y < self.height_pixels {
+            Some(usize::from(y) * usize::from(self.width_pixels) + usize::from(x))
+        } else {
+            None
+        }
+    }
+}
+
+impl<TPixels: AsRef<[Colour]> + AsMut<[Colour]>> FrameBufferCanvas<TPixels> {
+    pub fn fill(&mut self, colour: Colour) {
+        self.pixels.as_mut().fill(colour);
+    }
+}
+
+impl<TPixels: AsRef<[Colour]> + AsMut<[Colour]>> Canvas for FrameBufferCanvas<TPixels> {
+    fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> Result<()> {
+        let index = self.index_of(x, y).ok_or(Error::CanvasPixelOutOfBounds)?;
+        self.pixels.as_mut()[index] = colour;
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::vec::Vec;
+
+use super::{Colour, FrameBufferCanvas};
+
+pub struct FrameExport<'f, TPixels: AsRef<[Colour]>> {
+    frame: &'f FrameBufferCanvas<TPixels>,
+    palette: fn(Colour) -> (u8, u8, u8)
+}
+
+impl<'f, TPixels: AsRef<[Colour]>> FrameExport<'f, TPixels> {
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    const PNG_BIT_DEPTH: u8 = 8;
+    const PNG_COLOUR_TYPE_INDEXED: u8 = 3;
+    const PNG_FILTER_NONE: u8 = 0;
+    const MAX_STORED_BLOCK_BYTES: usize = u16::MAX as usize;
+
+    pub fn new(frame: &'f FrameBufferCanvas<TPixels>, palette: fn(Colour) -> (u8, u8, u8)) -> Self {
+        Self { frame, palette }
+    }
+
+    // PNG when the extension says so, otherwise PPM
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("png")) {
+            self.write_png(&mut writer)?;
+        } else {
+            self.write_ppm(&mut writer)?;
+        }
+
+        writer.flush()
+    }
+
+    pub fn write_ppm<TWriter: Write>(&self, mut writer: TWriter) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.frame.width_pixels(), self.frame.height_pixels())?;
+        for colour in self.frame.pixels() {
+            let (red, green, blue) = (self.palette)(*colour);
+            writer.write_all(&[red, green, blue])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_png<TWriter: Write>(&self, mut writer: TWriter) -> io::Result<()> {
+        writer.write_all(&Self::PNG_SIGNATURE)?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(u32::from(self.frame.width_pixels()).to_be_bytes());
+        header.extend(u32::from(self.frame.height_pixels()).to_be_bytes());
+        header.extend([Self::PNG_BIT_DEPTH, Self::PNG_COLOUR_TYPE_INDEXED, 0, 0, 0]);
+        Self::write_png_chunk(&mut writer, b"IHDR", &header)?;
+
+        let palette: Vec<u8> = (0..=u8::MAX)
+            .flat_map(|index| {
+                let (red, green, blue) = (self.palette)(Colour::new(index));
+                [red, green, blue]
+            })
+            .collect();
+
+        Self::write_png_chunk(&mut writer, b"PLTE", &palette)?;
+
+        let mut scanlines = Vec::with_capacity(self.frame.pixels().len() + usize::from(self.frame.height_pixels()));
+        for row in self.frame.rows() {
+            scanlines.push(Self::PNG_FILTER_NONE);
+            scanlines.extend(row.iter().map(|colour| colour.as_index()));
+        }
+
+        Self::write_png_chunk(&mut writer, b"IDAT", &Self::zlib_stored(&scanlines))?;
+        Self::write_png_chunk(&mut writer, b"IEND", &[])
+    }
+
+    fn write_png_chunk<TWriter: Write>(writer: &mut TWriter, chunk_type: &[u8; 4], data: &[u8]) -> io::Result<()> {
+        writer.write_all(&(data.len() as u32).to_be_bytes())?;
+        writer.write_all(chunk_type)?;
+        writer.write_all(data)?;
+        writer.write_all(&Self::crc32(chunk_type.iter().chain(data)).to_be_bytes())
+    }
+
+    // Stored (uncompressed) deflate blocks keep the exporter free of dependencies; the files are bigger but valid
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut zlib = Vec::with_capacity(data.len() + data.len() / Self::MAX_STORED_BLOCK_BYTES * 5 + 11);
+        zlib.extend([0x78, 0x01]);
+
+        let mut blocks = data.chunks(Self::MAX_STORED_BLOCK_BYTES).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+        }
+
+        while let Some(block) = blocks.next() {
+            let length = block.len() as u16;
+            zlib.push(u8::from(blocks.peek().is_none()));
+            zlib.extend(length.to_le_bytes());
+            zlib.extend((!length).to_le_bytes());
+            zlib.extend(block);
+        }
+
+        zlib.extend(Self::adler32(data).to_be_bytes());
+        zlib
+    }
+
+    fn crc32<'b>(bytes: impl Iterator<Item = &'b u8>) -> u32 {
+        let mut crc = !0_u32;
+        for byte in bytes {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            }
+        }
+
+        !crc
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        const MODULUS: u32 = 65521;
+        let (a, b) = data.iter().fold((1_u32, 0_u32), |(a, b), byte| {
+            let a = (a + u32::from(*byte)) % MODULUS;
+            (a, (b + a) % MODULUS)
+        });
+
+        (b << 16) | a
+    }
+}
@@ -0,0 +1,51 @@
+use core::cell::{Cell, Ref, RefCell};
+
+use super::{Canvas, Colour, FrameBufferCanvas, Pollable, Result};
+
+/*
+    Stands in for a terminal when a GameLoop is driven without one, such as from tests.  Frames are drawn into
+    a FrameBufferCanvas and polling 'presents' them, which only counts them; the most recently presented frame
+    is left in the buffer once the loop has finished.
+*/
+pub struct HeadlessScreen<TPixels: AsRef<[Colour]> + AsMut<[Colour]>> {
+    frame: RefCell<FrameBufferCanvas<TPixels>>,
+    frames_presented: Cell<u32>
+}
+
+impl<TPixels: AsRef<[Colour]> + AsMut<[Colour]>> HeadlessScreen<TPixels> {
+    pub const fn new(frame: FrameBufferCanvas<TPixels>) -> Self {
+        Self { frame: RefCell::new(frame), frames_presented: Cell::new(0) }
+    }
+
+    pub fn canvas(&self) -> impl Canvas + use<'_, TPixels> {
+        SharedFrame { screen: self }
+    }
+
+    pub fn pollable(&self) -> impl Pollable + use<'_, TPixels> {
+        SharedFrame { screen: self }
+    }
+
+    pub fn frames_presented(&self) -> u32 { self.frames_presented.get() }
+
+    pub fn frame(&self) -> Ref<'_, FrameBufferCanvas<TPixels>> { self.frame.borrow() }
+
+    pub fn into_frame(self) -> FrameBufferCanvas<TPixels> { self.frame.into_inner() }
+}
+
+struct SharedFrame<'s, TPixels: AsRef<[Colour]> + AsMut<[Colour]>> {
+    screen: &'s HeadlessScreen<TPixels>
+}
+
+impl<'s, TPixels: AsRef<[Colour]> + AsMut<[Colour]>> Canvas for SharedFrame<'s, TPixels> {
+    fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> Result<()> {
+        self.screen.frame.borrow_mut().set_pixel(x, y, colour)
+    }
+}
+
+impl<'s, TPixels: AsRef<[Colour]> + AsMut<[Colour]>> Pollable for SharedFrame<'s, TPixels> {
+    fn poll(&mut self) -> Result<()> {
+        let frames_presented = &self.screen.frames_presented;
+        frames_presented.set(frames_presented.get().saturating_add(1));
+        Ok(())
+    }
+}
@@ -1,14 +1,30 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+extern crate self as notray_engine;
+
 mod canvas;
 pub use canvas::*;
 
+mod frame_buffer;
+pub use frame_buffer::*;
+
+#[cfg(feature = "std")]
+mod frame_export;
+#[cfg(feature = "std")]
+pub use frame_export::*;
+
 mod frame_renderer;
 pub use frame_renderer::*;
 
 mod game_loop;
 pub use game_loop::*;
 
+mod headless_screen;
+pub use headless_screen::*;
+
 pub mod raycasting;
 
 mod pollable;
@@ -3,3 +3,18 @@ use super::Result;
 pub trait Pollable {
     fn poll(&mut self) -> Result<()>;
 }
+
+// Optional parts of the loop, such as recorders, can then be left out without changing the types around them
+impl<T: Pollable> Pollable for Option<T> {
+    fn poll(&mut self) -> Result<()> {
+        self.as_mut().map_or(Ok(()), Pollable::poll)
+    }
+}
+
+// Polled in order, so that a GameLoop can drive more than one thing per tick, such as stimuli and a screen
+impl<TFirst: Pollable, TSecond: Pollable> Pollable for (TFirst, TSecond) {
+    fn poll(&mut self) -> Result<()> {
+        self.0.poll()?;
+        self.1.poll()
+    }
+}
@@ -0,0 +1,133 @@
+use std::vec;
+use std::vec::Vec;
+
+use crate::{Canvas, Colour, Result};
+use super::*;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ColumnAccuracy {
+    compared: u32,
+    mismatched: u32,
+    max_wall_height_error: f64,
+    total_wall_height_error: f64,
+    max_cell_offset_error: f64,
+    total_cell_offset_error: f64
+}
+
+impl ColumnAccuracy {
+    pub fn compared(&self) -> u32 { self.compared }
+
+    // Rays where the fixed-point walk hit a different cell or face to the reference, away from any corner
+    pub fn mismatched(&self) -> u32 { self.mismatched }
+
+    pub fn max_wall_height_error(&self) -> f64 { self.max_wall_height_error }
+
+    pub fn mean_wall_height_error(&self) -> f64 { Self::mean(self.total_wall_height_error, self.compared) }
+
+    pub fn max_cell_offset_error(&self) -> f64 { self.max_cell_offset_error }
+
+    pub fn mean_cell_offset_error(&self) -> f64 { Self::mean(self.total_cell_offset_error, self.compared) }
+
+    pub fn merged(self, other: &Self) -> Self {
+        Self {
+            compared: self.compared + other.compared,
+            mismatched: self.mismatched + other.mismatched,
+            max_wall_height_error: self.max_wall_height_error.max(other.max_wall_height_error),
+            total_wall_height_error: self.total_wall_height_error + other.total_wall_height_error,
+            max_cell_offset_error: self.max_cell_offset_error.max(other.max_cell_offset_error),
+            total_cell_offset_error: self.total_cell_offset_error + other.total_cell_offset_error
+        }
+    }
+
+    fn record(&mut self, wall_height_error: f64, cell_offset_error: f64) {
+        self.compared += 1;
+        self.max_wall_height_error = self.max_wall_height_error.max(wall_height_error);
+        self.total_wall_height_error += wall_height_error;
+        self.max_cell_offset_error = self.max_cell_offset_error.max(cell_offset_error);
+        self.total_cell_offset_error += cell_offset_error;
+    }
+
+    fn mean(total: f64, count: u32) -> f64 {
+        if count == 0 { 0.0 } else { total / f64::from(count) }
+    }
+}
+
+struct NullCanvas;
+
+impl Canvas for NullCanvas {
+    fn set_pixel(&mut self, _x: u16, _y: u16, _colour: Colour) -> Result<()> { Ok(()) }
+}
+
+/*
+    Casts every column of a pose through both the RaycastingContext and the ReferenceRaycaster, accumulating
+    the absolute differences in projected wall height (pixels, before rounding to an even height) and in
+    texture coordinate (fractions of a cell).  Rays that graze a corner can legitimately hit either face, so
+    they are left out rather than being counted as mismatches.
+*/
+pub struct AccuracyAnalysis<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> {
+    context: RaycastingContext<TEngineParameters>,
+    reference: ReferenceRaycaster<TEngineParameters>,
+    columns: Vec<ColumnAccuracy>,
+    poses: u32
+}
+
+impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> AccuracyAnalysis<TEngineParameters> {
+    const AMBIGUOUS_CORNER_DISTANCE: f64 = 1.0 / 4096.0;
+
+    pub fn new() -> Self {
+        Self {
+            context: RaycastingContext::default(),
+            reference: ReferenceRaycaster::new(),
+            columns: vec![ColumnAccuracy::default(); usize::from(TEngineParameters::CANVAS_WIDTH_PIXELS)],
+            poses: 0
+        }
+    }
+
+    pub fn analyse_pose<TWorld>(&mut self, world: &TWorld, position: WorldCoordinates<TEngineParameters::Coordinate>, direction: Angle) -> Result<()>
+        where
+            TWorld: World<TEngineParameters::Coordinate> {
+
+        let camera = Camera::<TEngineParameters>::new(Object::new(position, direction));
+        self.context.on_frame_start(&camera, &mut NullCanvas)?;
+        loop {
+            self.context.cast_ray(world)?;
+            let column = self.context.canvas_column_x();
+            let reference = self.reference.cast_column(world, position, direction, column);
+            Self::compare(&mut self.columns[usize::from(column)], self.context.cell_intersection(), reference);
+            if !self.context.next_column()? {
+                break;
+            }
+        }
+
+        self.poses += 1;
+        Ok(())
+    }
+
+    fn compare(accuracy: &mut ColumnAccuracy, intersection: Option<RayCellIntersection>, reference: Option<ReferenceIntersection>) {
+        match (intersection, reference) {
+            (None, None) => { },
+
+            (_, Some(reference)) if reference.distance_to_nearest_corner() < Self::AMBIGUOUS_CORNER_DISTANCE => { },
+
+            (Some(intersection), Some(reference)) if (intersection.cell_x(), intersection.cell_y(), intersection.face()) == (reference.cell_x(), reference.cell_y(), reference.face()) => {
+                let wall_height_error = (intersection.projected_wall_height().to_num::<f64>() - reference.projected_wall_height()).abs();
+                let cell_offset_error = (intersection.cell_offset().to_num::<f64>() - reference.cell_offset()).abs();
+                accuracy.record(wall_height_error, cell_offset_error);
+            },
+
+            _ => accuracy.mismatched += 1
+        }
+    }
+
+    pub fn poses(&self) -> u32 { self.poses }
+
+    pub fn columns(&self) -> &[ColumnAccuracy] { &self.columns }
+
+    pub fn overall(&self) -> ColumnAccuracy {
+        self.columns.iter().fold(ColumnAccuracy::default(), ColumnAccuracy::merged)
+    }
+}
+
+impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> Default for AccuracyAnalysis<TEngineParameters> {
+    fn default() -> Self { Self::new() }
+}
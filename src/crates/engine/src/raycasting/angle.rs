@@ -33,6 +33,11 @@ impl Angle {
     pub const QUADRANT_AXIS_2_3: Angle = Angle::from_raw(0xc000_u16 as i16);
     pub const QUADRANT_AXIS_3_0: Angle = Angle::from_raw(0x0000_u16 as i16);
 
+    pub const FACING_NORTH: Angle = Angle::from_raw(0x4000_u16 as i16);
+    pub const FACING_EAST: Angle = Angle::from_raw(0x0000_u16 as i16);
+    pub const FACING_SOUTH: Angle = Angle::from_raw(0xc000_u16 as i16);
+    pub const FACING_WEST: Angle = Angle::from_raw(0x8000_u16 as i16);
+
     pub const fn default() -> Self {
         Self::lit("0")
     }
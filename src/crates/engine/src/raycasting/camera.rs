@@ -1,7 +1,5 @@
 use core::marker::PhantomData;
 
-use fixed::traits::ToFixed;
-
 use crate::{HasFixedPoint, Vector2d};
 use super::*;
 
@@ -9,7 +7,7 @@ type FixedPoint = <Angle as HasFixedPoint>::FixedPoint;
 
 pub struct Camera<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> {
     _parameters: PhantomData<TEngineParameters>,
-    object: Object,
+    object: Object<TEngineParameters::Coordinate>,
     direction_vector: Vector2d<FixedPoint>,
     projection_plane_vector: Vector2d<FixedPoint>
 }
@@ -17,7 +15,7 @@ pub struct Camera<TEngineParameters: EngineParameters + ProjectionPlaneParameter
 impl<TEngineParameters> Camera<TEngineParameters> where
     TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry {
 
-    pub fn new(object: Object) -> Self {
+    pub fn new(object: Object<TEngineParameters::Coordinate>) -> Self {
         let mut camera = Self {
             _parameters: PhantomData,
             object,
@@ -30,28 +28,22 @@ impl<TEngineParameters> Camera<TEngineParameters> where
         camera
     }
 
-    pub fn position(&self) -> WorldCoordinates { self.object.position() }
+    pub fn position(&self) -> WorldCoordinates<TEngineParameters::Coordinate> { self.object.position() }
 
-    pub fn set_position(&mut self, position: WorldCoordinates) {
+    pub fn set_position(&mut self, position: WorldCoordinates<TEngineParameters::Coordinate>) {
         self.object.set_position(position);
     }
 
-    pub fn move_relative(&mut self, distance: WorldRelativeCoordinate) {
-        let distance: fixed::types::I9F23 = distance.into();
-
-        let delta_x = distance.saturating_mul(self.direction_vector.x().into());
-        let x = delta_x.saturating_add(self.position().x().into())
-            .unsigned_abs()
-            .checked_to_fixed()
-            .unwrap_or(WorldAbsoluteCoordinate::MAX);
-
-        let delta_y = distance.saturating_mul(self.direction_vector.y().into());
-        let y = delta_y.saturating_add(self.position().y().into())
-            .unsigned_abs()
-            .checked_to_fixed()
-            .unwrap_or(WorldAbsoluteCoordinate::MAX);
+    pub fn move_relative(&mut self, distance: <TEngineParameters::Coordinate as WorldCoordinate>::Relative, boundary: WorldBoundary) {
+        // Each axis is constrained on its own, so that walking into an edge that is an error slides along it
+        let mut new_position = self.position();
+        if let Ok(x) = boundary.constrain_x(new_position.x().moved_by(distance, self.direction_vector.x())) {
+            new_position.set_x(x);
+        }
 
-        let new_position = WorldCoordinates::new(x, y);
+        if let Ok(y) = boundary.constrain_y(new_position.y().moved_by(distance, self.direction_vector.y())) {
+            new_position.set_y(y);
+        }
 
         // TODO: collision detection...
         self.set_position(new_position);
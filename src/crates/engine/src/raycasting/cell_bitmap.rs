@@ -0,0 +1,201 @@
+pub struct CellBitmap<TWords: AsRef<[u32]>> {
+    width_cells: u16,
+    height_cells: u16,
+    words: TWords
+}
+
+impl<TWords: AsRef<[u32]>> CellBitmap<TWords> {
+    pub const fn words_for(width_cells: u16, height_cells: u16) -> usize {
+        (width_cells as usize * height_cells as usize).div_ceil(u32::BITS as usize)
+    }
+
+    pub fn new(width_cells: u16, height_cells: u16, words: TWords) -> Self {
+        assert!(words.as_ref().len() >= Self::words_for(width_cells, height_cells), "Words must cover the Width and Height");
+        Self { width_cells, height_cells, words }
+    }
+
+    pub fn width_cells(&self) -> u16 { self.width_cells }
+
+    pub fn height_cells(&self) -> u16 { self.height_cells }
+
+    pub fn is_set(&self, cell_x: u16, cell_y: u16) -> bool {
+        self.bit_of(cell_x, cell_y).is_some_and(|(word, mask)| self.words.as_ref()[word] & mask != 0)
+    }
+
+    pub fn count(&self) -> u32 {
+        self.words.as_ref()[..Self::words_for(self.width_cells, self.height_cells)]
+            .iter()
+            .map(|word| word.count_ones())
+            .sum()
+    }
+
+    pub fn words(&self) -> &[u32] { self.words.as_ref() }
+
+    fn bit_of(&self, cell_x: u16, cell_y: u16) -> Option<(usize, u32)> {
+        if cell_x < self.width_cells && cell_y < self.height_cells {
+            let index = usize::from(cell_y) * usize::from(self.width_cells) + usize::from(cell_x);
+            Some((index / u32::BITS as usize, 1 << (index % u32::BITS as usize)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<TWords: AsRef<[u32]> + AsMut<[u32]>> CellBitmap<TWords> {
+    pub fn set(&mut self, cell_x: u16, cell_y: u16) {
+        if let Some((word, mask)) = self.bit_of(cell_x, cell_y) {
+            self.words.as_mut()[word] |= mask;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.as_mut().fill(0);
+    }
+}
+
+impl<TWords: AsRef<[u32]> + AsMut<[u32]>> CellMarks for CellBitmap<TWords> {
+    fn width_cells(&self) -> u16 { self.width_cells }
+
+    fn height_cells(&self) -> u16 { self.height_cells }
+
+    fn is_marked(&self, cell_x: u16, cell_y: u16) -> bool { self.is_set(cell_x, cell_y) }
+
+    fn mark(&mut self, cell_x: u16, cell_y: u16) { self.set(cell_x, cell_y); }
+}
+
+/*
+    Whatever records the cells that a flood fill has reached; usually a CellBitmap, but the generators mark
+    the world's own cells because they have nowhere else to keep them.
+*/
+pub trait CellMarks {
+    fn width_cells(&self) -> u16;
+
+    fn height_cells(&self) -> u16;
+
+    fn is_marked(&self, cell_x: u16, cell_y: u16) -> bool;
+
+    fn mark(&mut self, cell_x: u16, cell_y: u16);
+
+    fn neighbours_of(&self, cell_x: u16, cell_y: u16, is_wrapped: bool) -> [Option<(u16, u16)>; 4] {
+        let (width, height) = (self.width_cells(), self.height_cells());
+        let stepped = |cell: u16, step: i32, extent: u16| {
+            let stepped = i32::from(cell) + step;
+            if is_wrapped {
+                Some(stepped.rem_euclid(i32::from(extent)) as u16)
+            } else if stepped >= 0 && stepped < i32::from(extent) {
+                Some(stepped as u16)
+            } else {
+                None
+            }
+        };
+
+        [
+            stepped(cell_x, -1, width).map(|x| (x, cell_y)),
+            stepped(cell_x, 1, width).map(|x| (x, cell_y)),
+            stepped(cell_y, -1, height).map(|y| (cell_x, y)),
+            stepped(cell_y, 1, height).map(|y| (cell_x, y))
+        ]
+    }
+
+    /*
+        Marks the starting cell, whether or not it is passable, and every passable cell connected to it; returns
+        how many cells were marked.  Each cell is widened into the run of cells across its row, and the rows above
+        and below are seeded wherever they can be entered from that run.  The seeds are kept on a stack of fixed
+        size, so no allocator is needed; any that do not fit are found again afterwards, by looking for passable
+        cells beside the ones that have been marked.
+    */
+    fn flood_fill_from(&mut self, cell_x: u16, cell_y: u16, is_wrapped: bool, is_passable: impl Fn(&Self, u16, u16) -> bool) -> u32
+        where
+            Self: Sized {
+
+        let mut seeds = FillSeeds::new();
+        let mut marked_cells = fill_row(self, cell_x, cell_y, is_wrapped, &is_passable, &mut seeds);
+        loop {
+            while let Some((x, y)) = seeds.pop() {
+                if !self.is_marked(x, y) {
+                    marked_cells += fill_row(self, x, y, is_wrapped, &is_passable, &mut seeds);
+                }
+            }
+
+            if !seeds.is_overflowed {
+                return marked_cells;
+            }
+
+            seeds.is_overflowed = false;
+            for y in 0..self.height_cells() {
+                for x in 0..self.width_cells() {
+                    if self.is_marked(x, y) {
+                        for (x, y) in self.neighbours_of(x, y, is_wrapped).into_iter().flatten() {
+                            if !self.is_marked(x, y) && is_passable(self, x, y) {
+                                seeds.push(x, y);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Returns how many cells of the row were marked, from the given cell outwards
+fn fill_row<TMarks: CellMarks>(marks: &mut TMarks, cell_x: u16, cell_y: u16, is_wrapped: bool, is_passable: &impl Fn(&TMarks, u16, u16) -> bool, seeds: &mut FillSeeds) -> u32 {
+    let is_fillable = |marks: &TMarks, x: u16, y: u16| !marks.is_marked(x, y) && is_passable(marks, x, y);
+
+    marks.mark(cell_x, cell_y);
+    let (mut left, mut right, mut run_cells) = (cell_x, cell_x, 1);
+    while let [Some((x, _)), ..] = marks.neighbours_of(left, cell_y, is_wrapped) && is_fillable(marks, x, cell_y) {
+        marks.mark(x, cell_y);
+        (left, run_cells) = (x, run_cells + 1);
+    }
+
+    while let [_, Some((x, _)), ..] = marks.neighbours_of(right, cell_y, is_wrapped) && is_fillable(marks, x, cell_y) {
+        marks.mark(x, cell_y);
+        (right, run_cells) = (x, run_cells + 1);
+    }
+
+    // Only the first cell of each stretch that can be entered is seeded, as it fills the rest of its row itself
+    let (mut x, mut was_above_fillable, mut was_below_fillable) = (left, false, false);
+    for _ in 0..run_cells {
+        let [_, next, above, below] = marks.neighbours_of(x, cell_y, is_wrapped);
+        for (neighbour, was_fillable) in [(above, &mut was_above_fillable), (below, &mut was_below_fillable)] {
+            let is_neighbour_fillable = neighbour.is_some_and(|(x, y)| is_fillable(marks, x, y));
+            if let Some((x, y)) = neighbour && is_neighbour_fillable && !*was_fillable {
+                seeds.push(x, y);
+            }
+
+            *was_fillable = is_neighbour_fillable;
+        }
+
+        x = next.map_or(x, |(x, _)| x);
+    }
+
+    run_cells
+}
+
+struct FillSeeds {
+    seeds: [(u16, u16); Self::CAPACITY],
+    count: usize,
+    is_overflowed: bool
+}
+
+impl FillSeeds {
+    const CAPACITY: usize = 64;
+
+    const fn new() -> Self {
+        Self { seeds: [(0, 0); Self::CAPACITY], count: 0, is_overflowed: false }
+    }
+
+    fn push(&mut self, cell_x: u16, cell_y: u16) {
+        if let Some(seed) = self.seeds.get_mut(self.count) {
+            *seed = (cell_x, cell_y);
+            self.count += 1;
+        } else {
+            self.is_overflowed = true;
+        }
+    }
+
+    fn pop(&mut self) -> Option<(u16, u16)> {
+        self.count = self.count.checked_sub(1)?;
+        Some(self.seeds[self.count])
+    }
+}
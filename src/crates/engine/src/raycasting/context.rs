@@ -1,12 +1,15 @@
 use core::marker::PhantomData;
 
 use fixed::FixedU16;
-use fixed::traits::{LossyInto, ToFixed};
-use fixed::types::{I2F14, I8F0, I8F24, U0F16, U11F21, U16F0, U2F14, U8F24};
+use fixed::traits::{Fixed, LossyInto};
+use fixed::types::{I2F14, I8F24, U0F16, U11F21, U16F0, U16F16, U2F14};
 
 use crate::{Canvas, Error, HasFixedPoint, Result, Vector2d};
 use super::*;
 
+type Coordinate<TEngineParameters> = <TEngineParameters as EngineParameters>::Coordinate;
+type Distance<TEngineParameters> = <Coordinate<TEngineParameters> as WorldCoordinate>::Distance;
+
 pub struct RaycastingContext<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> {
     _parameters: PhantomData<TEngineParameters>,
 
@@ -16,18 +19,17 @@ pub struct RaycastingContext<TEngineParameters: EngineParameters + ProjectionPla
     camera_direction_vector: Vector2d<<Angle as HasFixedPoint>::FixedPoint>,
     projection_plane_vector: Vector2d<<Angle as HasFixedPoint>::FixedPoint>,
 
-    ray_origin: WorldCoordinates,
+    ray_origin: WorldCoordinates<Coordinate<TEngineParameters>>,
     ray_direction: Vector2d<I8F24>,
-    ray_delta: Vector2d<U8F24>,
-    ray_cell: WorldCoordinates,
-    ray_abs_distance: Vector2d<U8F24>,
-    ray_abs_distance_last: Vector2d<U8F24>,
-    ray_cell_step: Vector2d<I8F0>,
-
-    is_horizontal_ray_intersection: bool,
-    distance_to_wall: U8F24,
+
+    hit_cell: WorldCoordinates<Coordinate<TEngineParameters>>,
+    hit_face: CellFace,
+    distance_to_wall: Distance<TEngineParameters>,
     projected_wall_height: U11F21,
-    cell_tag: Option<CellTag>
+    cell_tag: Option<CellTag>,
+
+    #[cfg(feature = "diagnostics")]
+    diagnostics: RaycastingDiagnostics<Coordinate<TEngineParameters>>
 }
 
 impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> RaycastingContext<TEngineParameters> {
@@ -38,17 +40,16 @@ impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonome
             canvas_column_angle: Angle::default(),
             camera_direction_vector: Vector2d::default(),
             projection_plane_vector: Vector2d::default(),
-            ray_origin: WorldCoordinates::from_cell_top_left(0, 0),
+            ray_origin: WorldCoordinates::default(),
             ray_direction: Vector2d::default(),
-            ray_delta: Vector2d::default(),
-            ray_cell: WorldCoordinates::from_cell_top_left(0, 0),
-            ray_abs_distance: Vector2d::default(),
-            ray_abs_distance_last: Vector2d::default(),
-            ray_cell_step: Vector2d::default(),
-            is_horizontal_ray_intersection: false,
-            distance_to_wall: U8F24::MAX,
+            hit_cell: WorldCoordinates::default(),
+            hit_face: CellFace::North,
+            distance_to_wall: Distance::<TEngineParameters>::MAX,
             projected_wall_height: U11F21::ZERO,
-            cell_tag: None
+            cell_tag: None,
+
+            #[cfg(feature = "diagnostics")]
+            diagnostics: RaycastingDiagnostics::new()
         }
     }
 
@@ -59,6 +60,9 @@ impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonome
         self.camera_direction_vector = camera.direction_vector();
         self.projection_plane_vector = camera.projection_plane_vector();
 
+        #[cfg(feature = "diagnostics")]
+        self.diagnostics.on_frame_start(camera.position(), camera.direction());
+
         self.canvas_column_x = 0;
         self._next_column()?;
 
@@ -82,35 +86,6 @@ impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonome
                 .ok_or(Error::RaycastingOverflowY)?
                 .lossy_into());
 
-        self.ray_delta = Vector2d::new(
-            self.ray_direction.x().unsigned_abs().saturating_recip(),
-            self.ray_direction.y().unsigned_abs().saturating_recip());
-
-        self.ray_cell = self.ray_origin;
-
-        let (initial_distance_x, cell_step_x) = if self.ray_direction.x() >= 0 {
-            let initial_cell_x = self.ray_origin.cell_x_ceil();
-            let distance_from_cell_edge_x = initial_cell_x - self.ray_origin.x();
-            (distance_from_cell_edge_x.saturating_mul(self.ray_delta.x().lossy_into()), I8F0::ONE)
-        } else {
-            let initial_cell_x = self.ray_origin.cell_x_floor();
-            let distance_from_cell_edge_x = self.ray_origin.x() - initial_cell_x;
-            (distance_from_cell_edge_x.saturating_mul(self.ray_delta.x().lossy_into()), I8F0::NEG_ONE)
-        };
-
-        let (initial_distance_y, cell_step_y) = if self.ray_direction.y() >= 0 {
-            let initial_cell_y = self.ray_origin.cell_y_ceil();
-            let distance_from_cell_edge_y = initial_cell_y - self.ray_origin.y();
-            (distance_from_cell_edge_y.saturating_mul(self.ray_delta.y().lossy_into()), I8F0::ONE)
-        } else {
-            let initial_cell_y = self.ray_origin.cell_y_floor();
-            let distance_from_cell_edge_y = self.ray_origin.y() - initial_cell_y;
-            (distance_from_cell_edge_y.saturating_mul(self.ray_delta.y().lossy_into()), I8F0::NEG_ONE)
-        };
-
-        self.ray_abs_distance = Vector2d::new(initial_distance_x.into(), initial_distance_y.into());
-        self.ray_cell_step = Vector2d::new(cell_step_x, cell_step_y);
-
         Ok(())
     }
 
@@ -124,102 +99,142 @@ impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonome
         }
     }
 
-    pub fn cast_ray<TWorld: World>(&mut self, world: &TWorld) -> Result<()> {
-        self.distance_to_wall = U8F24::MAX;
+    pub fn cast_ray<TWorld: World<Coordinate<TEngineParameters>>>(&mut self, world: &TWorld) -> Result<()> {
+        self.cast_ray_observed(world, &mut ())
+    }
+
+    pub fn cast_ray_observed<TWorld, TRayObserver>(&mut self, world: &TWorld, ray_observer: &mut TRayObserver) -> Result<()>
+        where
+            TWorld: World<Coordinate<TEngineParameters>>,
+            TRayObserver: RayObserver {
+
+        ray_observer.on_cell_seen(self.ray_origin.cell_x_int(), self.ray_origin.cell_y_int());
+        self.distance_to_wall = Distance::<TEngineParameters>::MAX;
         self.projected_wall_height = U11F21::ZERO;
-        for _ in 0..TEngineParameters::MAX_RAY_CELL_PROBES {
-            self.is_horizontal_ray_intersection = self.ray_abs_distance.x() < self.ray_abs_distance.y();
-            if self.is_horizontal_ray_intersection {
-                self.ray_abs_distance_last.set_x(self.ray_abs_distance.x());
-                self.ray_abs_distance.set_x(self.ray_abs_distance.x().saturating_add(self.ray_delta.x()));
-                self.ray_cell.set_x(self.ray_cell.x().saturating_add_signed(self.ray_cell_step.x().into()));
-            } else {
-                self.ray_abs_distance_last.set_y(self.ray_abs_distance.y());
-                self.ray_abs_distance.set_y(self.ray_abs_distance.y().saturating_add(self.ray_delta.y()));
-                self.ray_cell.set_y(self.ray_cell.y().saturating_add_signed(self.ray_cell_step.y().into()));
+        self.cell_tag = None;
+
+        let mut traversal = self.ray_traversal(world);
+        for crossing in traversal.by_ref() {
+            let crossing = crossing?;
+            ray_observer.on_cell_seen(crossing.cell_x(), crossing.cell_y());
+            if let CellProbeResult::Opaque(cell_tag) = crossing.probe_result() {
+                self.cell_tag = Some(*cell_tag);
+                self.distance_to_wall = crossing.distance();
+                self.hit_face = crossing.face();
+                self.hit_cell = crossing.cell();
+                break;
             }
-
-            // TODO: Place the match arms (but not the call to 'probe_cell') into another object that deals with (column) rendering...
-            let probe = CellProbe::new(self.ray_cell);
-            match world.probe_cell(&probe) {
-                CellProbeResult::Opaque(cell_tag) => {
-                    self.cell_tag = Some(cell_tag);
-                    self.distance_to_wall = if self.is_horizontal_ray_intersection {
-                        self.ray_abs_distance_last.x()
-                    } else {
-                        self.ray_abs_distance_last.y()
-                    };
-
-                    break;
-                },
-
-                CellProbeResult::PossiblyTransparent(_cell_tag) => {
-                    /* TODO: Something like an object or a wall with a transparent texture, where
-                       the ray needs to continue so that the background can be overdrawn by the
-                       object with transparency; basically we will need a LIFO queue of
-                       (x.x, y.y, distance) intersection tuples and the 'cell_tag' so we can traverse
-                       it with the Painter's Algorithm during rendering */
-                },
-
-                CellProbeResult::Transparent(_cell_tag) => {
-                    /* TODO: There is something of interest here, but this particular intersection
-                       is empty - maybe a door is ajar, for example */
-                }
-
-                CellProbeResult::Empty => { }
-            };
         }
 
+        #[cfg(feature = "diagnostics")]
+        self.diagnose_column(traversal.saturations());
+
         Ok(())
     }
 
+    #[cfg(feature = "diagnostics")]
+    fn diagnose_column(&mut self, traversal_saturations: &SaturationCounts) {
+        let mut saturations = *traversal_saturations;
+        let is_wall_height_saturated =
+            self.cell_tag.is_some() &&
+            self.distance_to_wall != 0 &&
+            TEngineParameters::ASPECT_RATIO_FOR_WALL_HEIGHT.checked_div(self.distance_to_wall.saturating_to_num()).is_none();
+
+        if is_wall_height_saturated {
+            saturations.record(SaturationSite::WallHeight);
+        }
+
+        self.diagnostics.on_column(self.canvas_column_x, &saturations);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    pub fn diagnostics(&self) -> &RaycastingDiagnostics<Coordinate<TEngineParameters>> { &self.diagnostics }
+
+    pub fn ray_traversal<'w, TWorld: World<Coordinate<TEngineParameters>>>(&self, world: &'w TWorld) -> RayTraversal<'w, Coordinate<TEngineParameters>, TWorld> {
+        RayTraversal::new(world, self.ray_origin, self.ray_direction, TEngineParameters::MAX_RAY_CELL_PROBES)
+    }
+
     pub fn canvas_column_x(&self) -> u16 { self.canvas_column_x }
 
     pub fn canvas_column_angle(&self) -> Angle { self.canvas_column_angle }
 
+    pub fn ray_origin(&self) -> WorldCoordinates<Coordinate<TEngineParameters>> { self.ray_origin }
+
+    pub fn ray_direction(&self) -> Vector2d<I8F24> { self.ray_direction }
+
     pub fn cell_intersection(&self) -> Option<RayCellIntersection> {
         if let Some(cell_tag) = self.cell_tag {
             let projected_wall_height = if self.distance_to_wall != 0 {
-                TEngineParameters::ASPECT_RATIO_FOR_WALL_HEIGHT.saturating_div(self.distance_to_wall.lossy_into())
+                TEngineParameters::ASPECT_RATIO_FOR_WALL_HEIGHT.saturating_div(self.distance_to_wall.saturating_to_num())
             } else {
                 U11F21::ZERO
             };
 
             Some(RayCellIntersection::new(
-                self.ray_origin,
-                self.ray_direction,
+                self.distance_to_wall.saturating_to_num(),
                 projected_wall_height,
-                self.is_horizontal_ray_intersection,
+                self.hit_cell.cell_x_int(),
+                self.hit_cell.cell_y_int(),
+                self.hit_face,
+                self.cell_offset(),
                 cell_tag))
         } else {
             None
         }
     }
 
+    fn cell_offset(&self) -> U0F16 {
+        /*
+            The intersection is at 'origin + distance . direction', where the distance is perpendicular to the
+            projection plane because the direction vector is not normalised.  Only the component running along
+            the wall is of interest.  Faces are seen from the left of the screen to the right when the ray
+            travels in the -x or +y directions, so offsets for the opposite faces are mirrored to stop the
+            texture being flipped.
+        */
+
+        let (cell_offset, is_mirrored) = if matches!(self.hit_face, CellFace::East | CellFace::West) {
+            (self.ray_origin.y().offset_along_ray(self.ray_direction.y(), self.distance_to_wall), self.ray_direction.x() > 0)
+        } else {
+            (self.ray_origin.x().offset_along_ray(self.ray_direction.x(), self.distance_to_wall), self.ray_direction.y() < 0)
+        };
+
+        if is_mirrored {
+            U0F16::from_bits(!cell_offset.to_bits())
+        } else {
+            cell_offset
+        }
+    }
+
     pub fn cell_tag(&self) -> Option<CellTag> { self.cell_tag }
 }
 
 pub struct RayCellIntersection {
-    ray_origin: WorldCoordinates,
-    ray_direction: Vector2d<I8F24>,
+    distance: U16F16,
     projected_wall_height: U11F21,
-    is_horizontal_intersection: bool,
+    cell_x: u16,
+    cell_y: u16,
+    face: CellFace,
+    cell_offset: U0F16,
     cell_tag: CellTag
 }
 
 impl RayCellIntersection {
     pub const fn new(
-        ray_origin: WorldCoordinates,
-        ray_direction: Vector2d<I8F24>,
+        distance: U16F16,
         projected_wall_height: U11F21,
-        is_horizontal_intersection: bool,
+        cell_x: u16,
+        cell_y: u16,
+        face: CellFace,
+        cell_offset: U0F16,
         cell_tag: CellTag) -> Self {
 
         Self {
-            ray_origin,
-            ray_direction,
+            distance,
             projected_wall_height,
-            is_horizontal_intersection,
+            cell_x,
+            cell_y,
+            face,
+            cell_offset,
             cell_tag
         }
     }
@@ -229,21 +244,19 @@ impl RayCellIntersection {
         possibly_odd_wall_height & !1
     }
 
-    pub fn cell_offset(&self) -> U0F16 {
-        let intersection = if self.is_horizontal_intersection {
-            self.projected_wall_height
-                .cast_signed()
-                .saturating_mul_add(self.ray_direction.y(), self.ray_origin.y().into())
-        } else {
-            self.projected_wall_height
-                .cast_signed()
-                .saturating_mul_add(self.ray_direction.x(), self.ray_origin.x().into())
-        };
+    pub fn projected_wall_height(&self) -> U11F21 { self.projected_wall_height }
 
-        intersection.cast_unsigned().frac().saturating_to_fixed()
-    }
+    pub fn distance(&self) -> U16F16 { self.distance }
+
+    pub fn cell_offset(&self) -> U0F16 { self.cell_offset }
+
+    pub fn is_horizontal_intersection(&self) -> bool { matches!(self.face, CellFace::East | CellFace::West) }
+
+    pub fn cell_x(&self) -> u16 { self.cell_x }
+
+    pub fn cell_y(&self) -> u16 { self.cell_y }
 
-    pub fn is_horizontal_intersection(&self) -> bool { self.is_horizontal_intersection }
+    pub fn face(&self) -> CellFace { self.face }
 
     pub fn cell_tag(&self) -> CellTag { self.cell_tag }
 }
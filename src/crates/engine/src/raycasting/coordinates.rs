@@ -1,67 +1,166 @@
-use fixed::types::{I8F8, U8F8};
+use fixed::traits::{FixedSigned, FixedUnsigned, LossyInto, ToFixed};
+use fixed::types::{I16F16, I17F15, I1F15, I32F32, I8F24, I8F8, I9F23, U0F16, U16F16, U8F24, U8F8};
 
 use crate::Vector2d;
-use super::Angle;
 
 pub type WorldAbsoluteCoordinate = U8F8;
 pub type WorldRelativeCoordinate = I8F8;
 
-#[repr(transparent)]
-#[derive(Copy, Clone)]
-pub struct WorldCoordinates(Vector2d<WorldAbsoluteCoordinate>);
+/*
+    The precision of the world is a trade-off between its size and the cost of the arithmetic; U8F8 keeps
+    everything within 32-bit multiplications for small targets, whereas U16F16 allows worlds of up to
+    65536 cells along each axis for those targets that can afford the wider intermediates.
+*/
+pub trait WorldCoordinate: FixedUnsigned {
+    type Relative: FixedSigned;
+    type Distance: FixedUnsigned;
+    type Movement: FixedSigned;
+
+    const ONE: Self;
+    const HALF: Self;
+    const ONE_CELL_DISTANCE: Self::Distance;
+
+    fn from_cell_int(cell: u16) -> Self;
+    fn cell_int(self) -> u16;
+    fn distance_to(self, other: Self) -> Self::Distance;
+    fn distance_along_ray(distance_from_cell_edge: Self::Distance, ray_direction: I8F24) -> Self::Distance;
+    fn offset_along_ray(self, ray_direction: I8F24, distance: Self::Distance) -> U0F16;
+    fn moved_by(self, distance: Self::Relative, direction: I1F15) -> Self::Movement;
+}
 
-impl WorldCoordinates {
-    pub const FACING_NORTH: Angle = Angle::from_raw(0x4000_u16 as i16);
-    pub const FACING_EAST: Angle = Angle::from_raw(0x0000_u16 as i16);
-    pub const FACING_SOUTH: Angle = Angle::from_raw(0xc000_u16 as i16);
-    pub const FACING_WEST: Angle = Angle::from_raw(0x8000_u16 as i16);
+macro_rules! world_coordinate {
+    ($coordinate:ident, $relative:ident, $distance:ident, $signed_distance:ident, $movement:ident) => {
+        impl WorldCoordinate for $coordinate {
+            type Relative = $relative;
+            type Distance = $distance;
+            type Movement = $movement;
+
+            const ONE: Self = $coordinate::ONE;
+            const HALF: Self = $coordinate::lit("0.5");
+            const ONE_CELL_DISTANCE: Self::Distance = $distance::ONE;
+
+            fn from_cell_int(cell: u16) -> Self { Self::saturating_from_num(cell) }
+
+            fn cell_int(self) -> u16 { self.int().saturating_to_num() }
+
+            fn distance_to(self, other: Self) -> Self::Distance { self.dist(other).into() }
+
+            fn distance_along_ray(distance_from_cell_edge: Self::Distance, ray_direction: I8F24) -> Self::Distance {
+                // Division rather than multiplication by the (saturated) reciprocal keeps rays that are almost parallel to an axis accurate
+                distance_from_cell_edge
+                    .checked_div(ray_direction.unsigned_abs().to_fixed())
+                    .unwrap_or(Self::Distance::MAX)
+            }
+
+            fn offset_along_ray(self, ray_direction: I8F24, distance: Self::Distance) -> U0F16 {
+                let distance: $signed_distance = distance.lossy_into();
+                ray_direction
+                    .wide_mul(distance)
+                    .saturating_add(self.into())
+                    .frac()
+                    .saturating_to_fixed()
+            }
+
+            fn moved_by(self, distance: Self::Relative, direction: I1F15) -> Self::Movement {
+                let distance: $movement = distance.into();
+                distance
+                    .saturating_mul(direction.into())
+                    .saturating_add(self.into())
+            }
+        }
+
+        impl WorldCoordinates<$coordinate> {
+            pub const fn from_cell_centre(x: u16, y: u16) -> Self {
+                let cell = Self::from_cell_top_left(x, y);
+                Self::new(
+                    cell.x().const_bitor(<$coordinate as WorldCoordinate>::HALF),
+                    cell.y().const_bitor(<$coordinate as WorldCoordinate>::HALF)
+                )
+            }
+
+            pub const fn from_cell_top_left(x: u16, y: u16) -> Self {
+                Self::new(
+                    Self::saturating_from_cell_int(x),
+                    Self::saturating_from_cell_int(y)
+                )
+            }
+
+            const fn saturating_from_cell_int(cell: u16) -> $coordinate {
+                let max_cell = $coordinate::MAX.to_bits() >> $coordinate::FRAC_NBITS;
+                $coordinate::const_from_int(if (cell as u64) < (max_cell as u64) { cell as _ } else { max_cell })
+            }
+        }
+    };
+}
 
-    const HALF: WorldAbsoluteCoordinate = WorldAbsoluteCoordinate::lit("0.5");
+world_coordinate!(U8F8, I8F8, U8F24, I9F23, I16F16);
+world_coordinate!(U16F16, I16F16, U16F16, I17F15, I32F32);
 
-    pub const fn new(x: WorldAbsoluteCoordinate, y: WorldAbsoluteCoordinate) -> Self {
-        Self(Vector2d::new(x, y))
-    }
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct WorldCoordinates<TCoordinate: WorldCoordinate = WorldAbsoluteCoordinate>(Vector2d<TCoordinate>);
 
-    pub const fn from_cell_centre(x: u8, y: u8) -> Self {
-        let cell = Self::from_cell_top_left(x, y);
-        Self::new(
-            cell.x().const_bitor(Self::HALF),
-            cell.y().const_bitor(Self::HALF)
-        )
+impl<TCoordinate: WorldCoordinate> WorldCoordinates<TCoordinate> {
+    pub const fn default() -> Self {
+        Self(Vector2d::default())
     }
 
-    pub const fn from_cell_top_left(x: u8, y: u8) -> Self {
-        Self::new(
-            WorldAbsoluteCoordinate::const_from_int(x as u16),
-            WorldAbsoluteCoordinate::const_from_int(y as u16)
-        )
+    pub const fn new(x: TCoordinate, y: TCoordinate) -> Self {
+        Self(Vector2d::new(x, y))
     }
 
-    pub const fn x(&self) -> WorldAbsoluteCoordinate { self.0.x() }
+    pub const fn x(&self) -> TCoordinate { self.0.x() }
 
-    pub fn set_x(&mut self, x: WorldAbsoluteCoordinate) { self.0.set_x(x); }
+    pub fn set_x(&mut self, x: TCoordinate) { self.0.set_x(x); }
 
-    pub fn cell_x_floor(&self) -> WorldAbsoluteCoordinate { self.x().floor() }
+    pub fn cell_x_floor(&self) -> TCoordinate { self.x().floor() }
 
-    pub fn cell_x_ceil(&self) -> WorldAbsoluteCoordinate {
-        self.x().checked_ceil().unwrap_or(WorldAbsoluteCoordinate::MAX)
+    pub fn cell_x_ceil(&self) -> TCoordinate {
+        self.x().checked_ceil().unwrap_or(TCoordinate::MAX)
     }
 
-    pub fn cell_x_int(&self) -> u8 { self.x().int().to_num() }
+    pub fn cell_x_int(&self) -> u16 { self.x().cell_int() }
 
-    pub fn cell_x_frac(&self) -> WorldAbsoluteCoordinate { self.x().frac() }
+    pub fn cell_x_frac(&self) -> TCoordinate { self.x().frac() }
 
-    pub const fn y(&self) -> WorldAbsoluteCoordinate { self.0.y() }
+    pub const fn y(&self) -> TCoordinate { self.0.y() }
 
-    pub fn set_y(&mut self, y: WorldAbsoluteCoordinate) { self.0.set_y(y); }
+    pub fn set_y(&mut self, y: TCoordinate) { self.0.set_y(y); }
 
-    pub fn cell_y_floor(&self) -> WorldAbsoluteCoordinate { self.y().floor() }
+    pub fn cell_y_floor(&self) -> TCoordinate { self.y().floor() }
 
-    pub fn cell_y_ceil(&self) -> WorldAbsoluteCoordinate {
-        self.y().checked_ceil().unwrap_or(WorldAbsoluteCoordinate::MAX)
+    pub fn cell_y_ceil(&self) -> TCoordinate {
+        self.y().checked_ceil().unwrap_or(TCoordinate::MAX)
     }
 
-    pub fn cell_y_int(&self) -> u8 { self.y().int().to_num() }
+    pub fn cell_y_int(&self) -> u16 { self.y().cell_int() }
 
-    pub fn cell_y_frac(&self) -> WorldAbsoluteCoordinate { self.y().frac() }
+    pub fn cell_y_frac(&self) -> TCoordinate { self.y().frac() }
+}
+
+/*
+    Trait methods cannot be const, so each coordinate has its own const constructors (above) and these are
+    what code that is generic over the coordinate calls instead; the inherent ones win wherever both apply.
+*/
+pub trait FromCell {
+    fn from_cell_centre(x: u16, y: u16) -> Self;
+
+    fn from_cell_top_left(x: u16, y: u16) -> Self;
+}
+
+impl<TCoordinate: WorldCoordinate> FromCell for WorldCoordinates<TCoordinate> {
+    fn from_cell_centre(x: u16, y: u16) -> Self {
+        let cell = Self::from_cell_top_left(x, y);
+        Self::new(
+            cell.x() | TCoordinate::HALF,
+            cell.y() | TCoordinate::HALF
+        )
+    }
+
+    fn from_cell_top_left(x: u16, y: u16) -> Self {
+        Self::new(
+            TCoordinate::from_cell_int(x),
+            TCoordinate::from_cell_int(y)
+        )
+    }
 }
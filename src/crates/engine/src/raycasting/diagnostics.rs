@@ -0,0 +1,119 @@
+use super::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SaturationSite {
+    InitialCellEdge,
+    RayDistance,
+    SolidBoundary,
+    WallHeight
+}
+
+impl SaturationSite {
+    pub const ALL: [Self; 4] = [Self::InitialCellEdge, Self::RayDistance, Self::SolidBoundary, Self::WallHeight];
+
+    const fn index(self) -> usize {
+        match self {
+            Self::InitialCellEdge => 0,
+            Self::RayDistance => 1,
+            Self::SolidBoundary => 2,
+            Self::WallHeight => 3
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SaturationCounts {
+    counts: [u32; SaturationSite::ALL.len()],
+    first_site: Option<SaturationSite>
+}
+
+impl SaturationCounts {
+    pub const fn new() -> Self {
+        Self { counts: [0; SaturationSite::ALL.len()], first_site: None }
+    }
+
+    pub fn count(&self, site: SaturationSite) -> u32 { self.counts[site.index()] }
+
+    pub fn total(&self) -> u32 { self.counts.iter().sum() }
+
+    pub fn first_site(&self) -> Option<SaturationSite> { self.first_site }
+
+    pub fn is_clean(&self) -> bool { self.first_site.is_none() }
+
+    pub fn record(&mut self, site: SaturationSite) {
+        self.counts[site.index()] = self.counts[site.index()].saturating_add(1);
+        self.first_site.get_or_insert(site);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (count, other_count) in self.counts.iter_mut().zip(other.counts) {
+            *count = count.saturating_add(other_count);
+        }
+
+        if let Some(site) = other.first_site {
+            self.first_site.get_or_insert(site);
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct SaturationEvent<TCoordinate: WorldCoordinate> {
+    site: SaturationSite,
+    column: u16,
+    position: WorldCoordinates<TCoordinate>,
+    direction: Angle
+}
+
+impl<TCoordinate: WorldCoordinate> SaturationEvent<TCoordinate> {
+    pub fn site(&self) -> SaturationSite { self.site }
+
+    pub fn column(&self) -> u16 { self.column }
+
+    pub fn position(&self) -> WorldCoordinates<TCoordinate> { self.position }
+
+    pub fn direction(&self) -> Angle { self.direction }
+}
+
+/*
+    What the raycaster silently clamped while rendering the most recent frame; counts are by site, and only
+    the first event keeps its column and the camera's pose, which is usually enough to reproduce a glitch.
+*/
+pub struct RaycastingDiagnostics<TCoordinate: WorldCoordinate> {
+    counts: SaturationCounts,
+    first: Option<SaturationEvent<TCoordinate>>,
+    position: WorldCoordinates<TCoordinate>,
+    direction: Angle
+}
+
+impl<TCoordinate: WorldCoordinate> RaycastingDiagnostics<TCoordinate> {
+    pub const fn new() -> Self {
+        Self {
+            counts: SaturationCounts::new(),
+            first: None,
+            position: WorldCoordinates::default(),
+            direction: Angle::default()
+        }
+    }
+
+    pub fn counts(&self) -> &SaturationCounts { &self.counts }
+
+    pub fn first(&self) -> Option<&SaturationEvent<TCoordinate>> { self.first.as_ref() }
+
+    pub fn is_clean(&self) -> bool { self.counts.is_clean() }
+
+    pub(crate) fn on_frame_start(&mut self, position: WorldCoordinates<TCoordinate>, direction: Angle) {
+        *self = Self { position, direction, ..Self::new() };
+    }
+
+    pub(crate) fn on_column(&mut self, column: u16, counts: &SaturationCounts) {
+        if let (None, Some(site)) = (self.first, counts.first_site()) {
+            self.first = Some(SaturationEvent { site, column, position: self.position, direction: self.direction });
+        }
+
+        self.counts.merge(counts);
+    }
+}
+
+impl<TCoordinate: WorldCoordinate> Default for RaycastingDiagnostics<TCoordinate> {
+    fn default() -> Self { Self::new() }
+}
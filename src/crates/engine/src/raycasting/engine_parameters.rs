@@ -1,7 +1,9 @@
 use crate::HasFixedPoint;
-use super::{Angle, WorldAbsoluteCoordinate};
+use super::{Angle, WorldCoordinate};
 
 pub trait EngineParameters {
+    type Coordinate: WorldCoordinate;
+
     const MAX_RAY_CELL_PROBES: usize;
 }
 
@@ -17,6 +19,24 @@ macro_rules! raycasting_parameters {
         field_of_view: $fov_degrees:literal degrees;
         sine_lookup_msbs: $sine_lookup_msbs:literal bits;
         sine_lookup_size: $sine_lookup_size_degrees:literal degrees;
+    }) => {
+        $crate::raycasting_parameters! {
+            pub struct $TypeName {
+                canvas: $canvas_width_pixels x $canvas_height_pixels pixels;
+                field_of_view: $fov_degrees degrees;
+                sine_lookup_msbs: $sine_lookup_msbs bits;
+                sine_lookup_size: $sine_lookup_size_degrees degrees;
+                world_coordinates: $crate::raycasting::WorldAbsoluteCoordinate;
+            }
+        }
+    };
+
+    (pub struct $TypeName:ident {
+        canvas: $canvas_width_pixels:literal x $canvas_height_pixels:literal pixels;
+        field_of_view: $fov_degrees:literal degrees;
+        sine_lookup_msbs: $sine_lookup_msbs:literal bits;
+        sine_lookup_size: $sine_lookup_size_degrees:literal degrees;
+        world_coordinates: $world_coordinate:ty;
     }) => {
         ::notray_procmacro::_raycasting_parameters!(
             $TypeName,
@@ -24,6 +44,7 @@ macro_rules! raycasting_parameters {
             $canvas_height_pixels,
             $fov_degrees,
             $sine_lookup_msbs,
-            $sine_lookup_size_degrees);
+            $sine_lookup_size_degrees,
+            $world_coordinate);
     };
 }
@@ -0,0 +1,205 @@
+use crate::{Error, Result};
+use super::*;
+
+// Told about the camera's cell and then every cell that a ray passes through or hits, as the DDA visits them
+pub trait RayObserver {
+    fn on_cell_seen(&mut self, cell_x: u16, cell_y: u16);
+
+    // The camera is standing in the cell, once per tick after it has moved
+    fn on_cell_entered(&mut self, _cell_x: u16, _cell_y: u16) { }
+
+    // The world's cells have moved beneath the camera, so any cells that have been remembered are no longer valid
+    fn on_rebased(&mut self) { }
+}
+
+impl RayObserver for () {
+    fn on_cell_seen(&mut self, _cell_x: u16, _cell_y: u16) { }
+}
+
+pub trait CellVisibility {
+    fn is_cell_visible(&self, cell_x: u16, cell_y: u16) -> bool;
+}
+
+impl CellVisibility for () {
+    fn is_cell_visible(&self, _cell_x: u16, _cell_y: u16) -> bool { true }
+}
+
+/*
+    One bit per cell set once any ray has passed through or hit the cell, and another set once the camera
+    has stood in it.  A secret is only found by walking into it, which is also how a secret door is opened;
+    seeing it from across the room does not count.  Nothing else is kept, so the two bitmaps are all that a
+    save state needs to restore the exploration; statistics are derived on demand.
+*/
+pub struct Exploration<TWords: AsRef<[u32]>> {
+    seen: CellBitmap<TWords>,
+    entered: CellBitmap<TWords>
+}
+
+impl<TWords: AsRef<[u32]>> Exploration<TWords> {
+    const MAGIC: &'static [u8; 8] = b"NTRYEXPL";
+    const VERSION: u8 = 1;
+    const HEADER_BYTES: usize = Self::MAGIC.len() + 1 + 2 + 2;
+
+    pub fn new(width_cells: u16, height_cells: u16, seen_words: TWords, entered_words: TWords) -> Self {
+        Self {
+            seen: CellBitmap::new(width_cells, height_cells, seen_words),
+            entered: CellBitmap::new(width_cells, height_cells, entered_words)
+        }
+    }
+
+    pub fn seen(&self) -> &CellBitmap<TWords> { &self.seen }
+
+    pub fn entered(&self) -> &CellBitmap<TWords> { &self.entered }
+
+    pub const fn bytes_for(width_cells: u16, height_cells: u16) -> usize {
+        Self::HEADER_BYTES + CellBitmap::<TWords>::words_for(width_cells, height_cells) * 2 * size_of::<u32>()
+    }
+
+    // The magic and version, the width and height, then the seen and entered words; all little-endian
+    pub fn write_bytes(&self, bytes: &mut [u8]) -> Result<usize> {
+        let (width_cells, height_cells) = (self.seen.width_cells(), self.seen.height_cells());
+        let length = Self::bytes_for(width_cells, height_cells);
+        let bytes = bytes.get_mut(..length).ok_or(Error::Str("Buffer is too small for the exploration"))?;
+        let (header, words) = bytes.split_at_mut(Self::HEADER_BYTES);
+        header[..Self::MAGIC.len()].copy_from_slice(Self::MAGIC);
+        header[Self::MAGIC.len()] = Self::VERSION;
+        header[Self::MAGIC.len() + 1..][..2].copy_from_slice(&width_cells.to_le_bytes());
+        header[Self::MAGIC.len() + 3..][..2].copy_from_slice(&height_cells.to_le_bytes());
+
+        let words_per_bitmap = CellBitmap::<TWords>::words_for(width_cells, height_cells);
+        let saved_words = self.seen.words()[..words_per_bitmap].iter().chain(&self.entered.words()[..words_per_bitmap]);
+        for (word_bytes, word) in words.chunks_exact_mut(size_of::<u32>()).zip(saved_words) {
+            word_bytes.copy_from_slice(&word.to_le_bytes());
+        }
+
+        Ok(length)
+    }
+
+    // Restores what write_bytes saved into the given words, which must cover the saved width and height
+    pub fn from_bytes(bytes: &[u8], mut seen_words: TWords, mut entered_words: TWords) -> Result<Self>
+        where
+            TWords: AsMut<[u32]> {
+
+        let header = bytes
+            .strip_prefix(Self::MAGIC)
+            .ok_or(Error::Str("Not a saved exploration"))?
+            .split_first()
+            .filter(|&(&version, _)| version == Self::VERSION)
+            .map(|(_, header)| header)
+            .ok_or(Error::Str("Unsupported saved exploration version"))?;
+
+        let [width_lo, width_hi, height_lo, height_hi, ..] = *header else {
+            return Err(Error::Str("Truncated saved exploration"));
+        };
+
+        let (width_cells, height_cells) = (u16::from_le_bytes([width_lo, width_hi]), u16::from_le_bytes([height_lo, height_hi]));
+        if bytes.len() != Self::bytes_for(width_cells, height_cells) {
+            return Err(Error::Str("Saved exploration is not the length of its width and height"));
+        }
+
+        let words_per_bitmap = CellBitmap::<TWords>::words_for(width_cells, height_cells);
+        if seen_words.as_ref().len() < words_per_bitmap || entered_words.as_ref().len() < words_per_bitmap {
+            return Err(Error::Str("Words do not cover the saved exploration"));
+        }
+
+        let mut saved_words = bytes[Self::HEADER_BYTES..]
+            .chunks_exact(size_of::<u32>())
+            .map(|word_bytes| u32::from_le_bytes([word_bytes[0], word_bytes[1], word_bytes[2], word_bytes[3]]));
+
+        for words in [seen_words.as_mut(), entered_words.as_mut()] {
+            words.fill(0);
+            words[..words_per_bitmap].iter_mut().zip(saved_words.by_ref()).for_each(|(word, saved_word)| *word = saved_word);
+        }
+
+        Ok(Self::new(width_cells, height_cells, seen_words, entered_words))
+    }
+
+    pub fn statistics<TCoordinate, TWorld>(&self, world: &TWorld) -> ExplorationStatistics
+        where
+            TCoordinate: WorldCoordinate,
+            TWorld: World<TCoordinate> {
+
+        let mut statistics = ExplorationStatistics::default();
+        for cell_y in 0..self.seen.height_cells() {
+            for cell_x in 0..self.seen.width_cells() {
+                let is_seen = self.seen.is_set(cell_x, cell_y);
+                let (is_open, cell_tag) = match world.probe_cell(&CellProbe::new(WorldCoordinates::<TCoordinate>::from_cell_centre(cell_x, cell_y))) {
+                    CellProbeResult::Empty => (true, None),
+                    CellProbeResult::Opaque(cell_tag) => (false, Some(cell_tag)),
+                    CellProbeResult::Transparent(cell_tag) | CellProbeResult::PossiblyTransparent(cell_tag) => (true, Some(cell_tag))
+                };
+
+                statistics.seen_cells += u32::from(is_seen);
+                statistics.open_cells += u32::from(is_open);
+                statistics.seen_open_cells += u32::from(is_open && is_seen);
+                if cell_tag.is_some_and(|cell_tag| cell_tag.is_secret()) {
+                    statistics.secrets += 1;
+                    statistics.secrets_found += u32::from(self.entered.is_set(cell_x, cell_y));
+                }
+            }
+        }
+
+        statistics
+    }
+}
+
+impl<TWords: AsRef<[u32]> + AsMut<[u32]>> Exploration<TWords> {
+    pub fn forget(&mut self) {
+        self.seen.clear();
+        self.entered.clear();
+    }
+}
+
+impl<TWords: AsRef<[u32]> + AsMut<[u32]>> RayObserver for Exploration<TWords> {
+    fn on_cell_seen(&mut self, cell_x: u16, cell_y: u16) {
+        self.seen.set(cell_x, cell_y);
+    }
+
+    fn on_cell_entered(&mut self, cell_x: u16, cell_y: u16) {
+        self.entered.set(cell_x, cell_y);
+    }
+
+    fn on_rebased(&mut self) {
+        self.forget();
+    }
+}
+
+impl<TWords: AsRef<[u32]>> CellVisibility for Exploration<TWords> {
+    fn is_cell_visible(&self, cell_x: u16, cell_y: u16) -> bool { self.seen.is_set(cell_x, cell_y) }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExplorationStatistics {
+    seen_cells: u32,
+    open_cells: u32,
+    seen_open_cells: u32,
+    secrets: u32,
+    secrets_found: u32
+}
+
+impl ExplorationStatistics {
+    const fn default() -> Self {
+        Self {
+            seen_cells: 0,
+            open_cells: 0,
+            seen_open_cells: 0,
+            secrets: 0,
+            secrets_found: 0
+        }
+    }
+
+    pub fn seen_cells(&self) -> u32 { self.seen_cells }
+
+    pub fn open_cells(&self) -> u32 { self.open_cells }
+
+    // Walls buried in solid rock can never be seen, so only the open cells count towards the total
+    pub fn percent_explored(&self) -> u8 {
+        (u64::from(self.seen_open_cells) * 100)
+            .checked_div(u64::from(self.open_cells))
+            .map_or(100, |percent| percent as u8)
+    }
+
+    pub fn secrets(&self) -> u32 { self.secrets }
+
+    pub fn secrets_found(&self) -> u32 { self.secrets_found }
+}
@@ -0,0 +1,192 @@
+use core::marker::PhantomData;
+
+use fixed::traits::Fixed;
+
+use super::*;
+
+type Coordinate<TEngineParameters> = <TEngineParameters as EngineParameters>::Coordinate;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MapIssue {
+    SpawnInWall { cell_x: u16, cell_y: u16 },
+    SpawnOutsideRegion { cell_x: u16, cell_y: u16 },
+    UnreachableRegion { cell_x: u16, cell_y: u16, region_cells: u32 },
+    OpenBoundary { cell_x: u16, cell_y: u16 },
+    UnboundWall { cell_x: u16, cell_y: u16, wall_id: u16 },
+    CorridorTooLong { cell_x: u16, cell_y: u16, length_cells: u16, max_ray_cell_probes: usize }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapSummary {
+    reachable_cells: u32,
+    unreachable_cells: u32,
+    longest_corridor_cells: u16,
+    issues: u32
+}
+
+impl MapSummary {
+    pub fn reachable_cells(&self) -> u32 { self.reachable_cells }
+
+    pub fn unreachable_cells(&self) -> u32 { self.unreachable_cells }
+
+    pub fn longest_corridor_cells(&self) -> u16 { self.longest_corridor_cells }
+
+    pub fn issues(&self) -> u32 { self.issues }
+
+    pub fn is_ok(&self) -> bool { self.issues == 0 }
+}
+
+/*
+    Worlds do not know their own size, so the checker is told which region to examine; anything outside
+    it is only ever probed to see whether a ray leaving the region would find a wall.  Reachability needs
+    a bit per cell, which the caller provides so that the checker works without an allocator.
+*/
+pub struct MapChecker<TEngineParameters: EngineParameters, TWords: AsRef<[u32]> + AsMut<[u32]>> {
+    _parameters: PhantomData<TEngineParameters>,
+    reachable: CellBitmap<TWords>
+}
+
+impl<TEngineParameters, TWords> MapChecker<TEngineParameters, TWords>
+    where
+        TEngineParameters: EngineParameters,
+        TWords: AsRef<[u32]> + AsMut<[u32]> {
+
+    pub fn new(width_cells: u16, height_cells: u16, words: TWords) -> Self {
+        let max_cells = u32::from(Coordinate::<TEngineParameters>::MAX.cell_int()) + 1;
+        assert!(u32::from(width_cells) <= max_cells && u32::from(height_cells) <= max_cells, "Width and Height must fit within the world coordinates");
+        Self {
+            _parameters: PhantomData,
+            reachable: CellBitmap::new(width_cells, height_cells, words)
+        }
+    }
+
+    pub fn reachable(&self) -> &CellBitmap<TWords> { &self.reachable }
+
+    pub fn check<TWorld>(&mut self, world: &TWorld, mut on_issue: impl FnMut(MapIssue)) -> MapSummary
+        where TWorld: World<Coordinate<TEngineParameters>> + WorldRendering {
+
+        let mut summary = MapSummary::default();
+        let mut report = |issue| {
+            summary.issues += 1;
+            on_issue(issue);
+        };
+
+        let spawn = world.spawn_at();
+        let (spawn_x, spawn_y) = (spawn.cell_x_int(), spawn.cell_y_int());
+        if spawn_x >= self.reachable.width_cells() || spawn_y >= self.reachable.height_cells() {
+            report(MapIssue::SpawnOutsideRegion { cell_x: spawn_x, cell_y: spawn_y });
+            return summary;
+        }
+
+        if !Self::is_passable(world, spawn_x, spawn_y) {
+            report(MapIssue::SpawnInWall { cell_x: spawn_x, cell_y: spawn_y });
+            return summary;
+        }
+
+        let is_wrapped = matches!(world.boundary(), WorldBoundary::Wrap { .. });
+        self.reachable.clear();
+        self.reachable.flood_fill_from(spawn_x, spawn_y, is_wrapped, |_, x, y| Self::is_passable(world, x, y));
+
+        for cell_y in 0..self.reachable.height_cells() {
+            for cell_x in 0..self.reachable.width_cells() {
+                if self.reachable.is_set(cell_x, cell_y) {
+                    summary.reachable_cells += 1;
+                    if !is_wrapped && self.is_open_to_boundary(world, cell_x, cell_y) {
+                        report(MapIssue::OpenBoundary { cell_x, cell_y });
+                    }
+                } else if let CellProbeResult::Opaque(cell_tag) = Self::probe(world, cell_x, cell_y) {
+                    if !world.is_wall_bound(cell_tag) && self.reachable.neighbours_of(cell_x, cell_y, is_wrapped).into_iter().flatten().any(|(x, y)| self.reachable.is_set(x, y)) {
+                        report(MapIssue::UnboundWall { cell_x, cell_y, wall_id: cell_tag.wall_id() });
+                    }
+                } else {
+                    summary.unreachable_cells += 1;
+                }
+            }
+        }
+
+        summary.longest_corridor_cells = self.check_corridors(&mut report);
+        if summary.unreachable_cells > 0 {
+            self.check_unreachable_regions(world, is_wrapped, &mut report);
+            self.reachable.clear();
+            self.reachable.flood_fill_from(spawn_x, spawn_y, is_wrapped, |_, x, y| Self::is_passable(world, x, y));
+        }
+
+        summary
+    }
+
+    fn check_unreachable_regions<TWorld: World<Coordinate<TEngineParameters>>>(&mut self, world: &TWorld, is_wrapped: bool, report: &mut impl FnMut(MapIssue)) {
+        // Each region is filled into the reachable cells once it has been reported, which the caller then puts back
+        for cell_y in 0..self.reachable.height_cells() {
+            for cell_x in 0..self.reachable.width_cells() {
+                if !self.reachable.is_set(cell_x, cell_y) && Self::is_passable(world, cell_x, cell_y) {
+                    let region_cells = self.reachable.flood_fill_from(cell_x, cell_y, is_wrapped, |_, x, y| Self::is_passable(world, x, y));
+                    report(MapIssue::UnreachableRegion { cell_x, cell_y, region_cells });
+                }
+            }
+        }
+    }
+
+    fn is_open_to_boundary<TWorld: World<Coordinate<TEngineParameters>>>(&self, world: &TWorld, cell_x: u16, cell_y: u16) -> bool {
+        // Leaving the region is fine if the world puts a wall there, but leaving the coordinates never is
+        let max_cell = Coordinate::<TEngineParameters>::MAX.cell_int();
+        let is_open_beyond = |x: Option<u16>, y: Option<u16>| match (x, y) {
+            (Some(x), Some(y)) => Self::is_passable(world, x, y),
+            _ => true
+        };
+
+        (cell_x == 0 && is_open_beyond(cell_x.checked_sub(1), Some(cell_y))) ||
+        (cell_y == 0 && is_open_beyond(Some(cell_x), cell_y.checked_sub(1))) ||
+        (cell_x + 1 == self.reachable.width_cells() && is_open_beyond(Some(cell_x + 1).filter(|&x| x <= max_cell), Some(cell_y))) ||
+        (cell_y + 1 == self.reachable.height_cells() && is_open_beyond(Some(cell_x), Some(cell_y + 1).filter(|&y| y <= max_cell)))
+    }
+
+    fn check_corridors(&self, report: &mut impl FnMut(MapIssue)) -> u16 {
+        /*
+            Every cell of a straight run is a step for a ray cast along it, so a run longer than the number of
+            probes leaves rays that end without hitting anything; diagonals can be longer still, but straight
+            runs are what level designers actually build.
+        */
+        let (width, height) = (self.reachable.width_cells(), self.reachable.height_cells());
+        let mut longest = 0;
+        let mut check_run = |cell_x: u16, cell_y: u16, length_cells: u16| {
+            longest = longest.max(length_cells);
+            if usize::from(length_cells) > TEngineParameters::MAX_RAY_CELL_PROBES {
+                report(MapIssue::CorridorTooLong { cell_x, cell_y, length_cells, max_ray_cell_probes: TEngineParameters::MAX_RAY_CELL_PROBES });
+            }
+        };
+
+        for cell_y in 0..height {
+            let mut run = 0;
+            for cell_x in 0..=width {
+                if cell_x < width && self.reachable.is_set(cell_x, cell_y) {
+                    run += 1;
+                } else if run > 0 {
+                    check_run(cell_x - run, cell_y, run);
+                    run = 0;
+                }
+            }
+        }
+
+        for cell_x in 0..width {
+            let mut run = 0;
+            for cell_y in 0..=height {
+                if cell_y < height && self.reachable.is_set(cell_x, cell_y) {
+                    run += 1;
+                } else if run > 0 {
+                    check_run(cell_x, cell_y - run, run);
+                    run = 0;
+                }
+            }
+        }
+
+        longest
+    }
+
+    fn probe<TWorld: World<Coordinate<TEngineParameters>>>(world: &TWorld, cell_x: u16, cell_y: u16) -> CellProbeResult {
+        world.probe_cell(&CellProbe::new(WorldCoordinates::<Coordinate<TEngineParameters>>::from_cell_top_left(cell_x, cell_y)))
+    }
+
+    fn is_passable<TWorld: World<Coordinate<TEngineParameters>>>(world: &TWorld, cell_x: u16, cell_y: u16) -> bool {
+        !matches!(Self::probe(world, cell_x, cell_y), CellProbeResult::Opaque(_))
+    }
+}
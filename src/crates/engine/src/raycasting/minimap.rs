@@ -0,0 +1,309 @@
+use fixed::traits::Fixed;
+use fixed::types::I32F32;
+
+use crate::{Canvas, Colour, FrameRenderer, OnStimuli, Result, Stimuli};
+use super::*;
+
+pub trait MinimapStimuli {
+    fn should_toggle_minimap(&self) -> bool;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MinimapColours {
+    outside: Colour,
+    floor: Colour,
+    wall: Colour,
+    door: Colour,
+    camera: Colour,
+    ray: Colour,
+    hit: Colour
+}
+
+impl MinimapColours {
+    pub const DEFAULT: Self = Self::new(
+        Colour::new(0),
+        Colour::new(24),
+        Colour::new(16),
+        Colour::new(168),
+        Colour::new(64),
+        Colour::new(80),
+        Colour::new(64));
+
+    pub const fn new(outside: Colour, floor: Colour, wall: Colour, door: Colour, camera: Colour, ray: Colour, hit: Colour) -> Self {
+        Self { outside, floor, wall, door, camera, ray, hit }
+    }
+}
+
+/*
+    A top-down view of the cells around the camera, drawn into a rectangle of the canvas with the camera at
+    its centre.  Cells are drawn with x increasing to the right and y increasing downwards, the same way as
+    the editor and the worlds' cell arrays, so the map is a mirror image of a compass because +x is West.
+    Rays are cast with a separate context so that the minimap can show every n'th column without disturbing
+    the scene's own raycasting.  Cells that are not visible are drawn as though they were outside the world,
+    so an 'Exploration' turns the minimap into an automap with a fog of war.
+*/
+pub struct Minimap<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> {
+    left: u16,
+    top: u16,
+    width_pixels: u16,
+    height_pixels: u16,
+    pixels_per_cell: u16,
+    ray_column_stride: Option<u16>,
+    colours: MinimapColours,
+    raycasting_context: RaycastingContext<TEngineParameters>
+}
+
+impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry> Minimap<TEngineParameters> {
+    const CAMERA_RADIUS_PIXELS: i32 = 1;
+    const FACING_LENGTH_CELLS: u16 = 2;
+
+    pub const fn new(left: u16, top: u16, width_pixels: u16, height_pixels: u16) -> Self {
+        Self {
+            left,
+            top,
+            width_pixels,
+            height_pixels,
+            pixels_per_cell: 4,
+            ray_column_stride: None,
+            colours: MinimapColours::DEFAULT,
+            raycasting_context: RaycastingContext::default()
+        }
+    }
+
+    pub const fn with_pixels_per_cell(self, pixels_per_cell: u16) -> Self {
+        assert!(pixels_per_cell > 0, "Cells must be at least one pixel");
+        Self { pixels_per_cell, ..self }
+    }
+
+    pub const fn with_rays(self, column_stride: u16) -> Self {
+        assert!(column_stride > 0, "The column stride must be at least one");
+        Self { ray_column_stride: Some(column_stride), ..self }
+    }
+
+    pub const fn with_colours(self, colours: MinimapColours) -> Self {
+        Self { colours, ..self }
+    }
+
+    pub fn render_onto<TWorld, TCellVisibility, TCanvas>(&mut self, world: &TWorld, camera: &Camera<TEngineParameters>, visibility: &TCellVisibility, canvas: &mut TCanvas) -> Result<()>
+        where
+            TWorld: World<TEngineParameters::Coordinate>,
+            TCellVisibility: CellVisibility,
+            TCanvas: Canvas {
+
+        let centre = Self::position_in_cells(camera.position());
+        self.render_cells_onto(world, centre, visibility, canvas)?;
+
+        if let Some(column_stride) = self.ray_column_stride {
+            self.render_rays_onto(world, camera, centre, column_stride, canvas)?;
+        }
+
+        let (camera_x, camera_y) = self.pixel_for(centre, centre);
+        for y in camera_y - Self::CAMERA_RADIUS_PIXELS..=camera_y + Self::CAMERA_RADIUS_PIXELS {
+            for x in camera_x - Self::CAMERA_RADIUS_PIXELS..=camera_x + Self::CAMERA_RADIUS_PIXELS {
+                self.set_pixel_clipped(canvas, x, y, self.colours.camera)?;
+            }
+        }
+
+        let facing_length = I32F32::from_num(Self::FACING_LENGTH_CELLS);
+        let facing = (
+            centre.0 + camera.direction_vector().x().to_num::<I32F32>() * facing_length,
+            centre.1 + camera.direction_vector().y().to_num::<I32F32>() * facing_length);
+
+        self.render_line_onto(canvas, (camera_x, camera_y), self.pixel_for(facing, centre), self.colours.camera)
+    }
+
+    fn render_cells_onto<TWorld, TCellVisibility, TCanvas>(&self, world: &TWorld, centre: (I32F32, I32F32), visibility: &TCellVisibility, canvas: &mut TCanvas) -> Result<()>
+        where
+            TWorld: World<TEngineParameters::Coordinate>,
+            TCellVisibility: CellVisibility,
+            TCanvas: Canvas {
+
+        let pixels_per_cell = I32F32::from_num(self.pixels_per_cell);
+        let half_width_cells = I32F32::from_num(self.width_pixels / 2) / pixels_per_cell;
+        let half_height_cells = I32F32::from_num(self.height_pixels / 2) / pixels_per_cell;
+        let cells_x = (centre.0 - half_width_cells).floor().to_num::<i32>()..=(centre.0 + half_width_cells).floor().to_num::<i32>();
+        let cells_y = (centre.1 - half_height_cells).floor().to_num::<i32>()..=(centre.1 + half_height_cells).floor().to_num::<i32>();
+
+        for cell_y in cells_y {
+            for cell_x in cells_x.clone() {
+                let colour = self.colour_for(world, visibility, cell_x, cell_y);
+                let (left, top) = self.pixel_for((I32F32::from_num(cell_x), I32F32::from_num(cell_y)), centre);
+                for y in top..top + i32::from(self.pixels_per_cell) {
+                    for x in left..left + i32::from(self.pixels_per_cell) {
+                        self.set_pixel_clipped(canvas, x, y, colour)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_rays_onto<TWorld, TCanvas>(&mut self, world: &TWorld, camera: &Camera<TEngineParameters>, centre: (I32F32, I32F32), column_stride: u16, canvas: &mut TCanvas) -> Result<()>
+        where
+            TWorld: World<TEngineParameters::Coordinate>,
+            TCanvas: Canvas {
+
+        // Rays that hit nothing are drawn to the edge of the minimap, which is never further than this
+        let unobstructed_distance = I32F32::from_num(self.width_pixels + self.height_pixels) / I32F32::from_num(self.pixels_per_cell);
+        let camera_pixel = self.pixel_for(centre, centre);
+
+        self.raycasting_context.on_frame_start(camera, canvas)?;
+        loop {
+            let column = self.raycasting_context.canvas_column_x();
+            if column.is_multiple_of(column_stride) || column == TEngineParameters::CANVAS_WIDTH_PIXELS - 1 {
+                self.raycasting_context.cast_ray(world)?;
+
+                let intersection = self.raycasting_context.cell_intersection();
+                let distance = intersection.as_ref().map_or(unobstructed_distance, |intersection| intersection.distance().to_num());
+                let direction = self.raycasting_context.ray_direction();
+                let end = (
+                    centre.0.saturating_add(direction.x().to_num::<I32F32>().saturating_mul(distance)),
+                    centre.1.saturating_add(direction.y().to_num::<I32F32>().saturating_mul(distance)));
+
+                let end_pixel = self.pixel_for(end, centre);
+                self.render_line_onto(canvas, camera_pixel, end_pixel, self.colours.ray)?;
+                if intersection.is_some() {
+                    self.set_pixel_clipped(canvas, end_pixel.0, end_pixel.1, self.colours.hit)?;
+                }
+            }
+
+            if !self.raycasting_context.next_column()? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_line_onto<TCanvas: Canvas>(&self, canvas: &mut TCanvas, from: (i32, i32), to: (i32, i32), colour: Colour) -> Result<()> {
+        let (delta_x, delta_y) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+        let (step_x, step_y) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+        let mut error = delta_x + delta_y;
+        let (mut x, mut y) = from;
+        loop {
+            // Lines start inside the minimap, so once one leaves it the remainder is clipped anyway
+            if !self.set_pixel_clipped(canvas, x, y, colour)? || (x, y) == to {
+                break;
+            }
+
+            let doubled_error = 2 * error;
+            if doubled_error >= delta_y {
+                error += delta_y;
+                x += step_x;
+            }
+
+            if doubled_error <= delta_x {
+                error += delta_x;
+                y += step_y;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn colour_for<TWorld, TCellVisibility>(&self, world: &TWorld, visibility: &TCellVisibility, cell_x: i32, cell_y: i32) -> Colour
+        where
+            TWorld: World<TEngineParameters::Coordinate>,
+            TCellVisibility: CellVisibility {
+
+        let max_cell = i32::from(TEngineParameters::Coordinate::MAX.cell_int());
+        if cell_x < 0 || cell_y < 0 || cell_x > max_cell || cell_y > max_cell || !visibility.is_cell_visible(cell_x as u16, cell_y as u16) {
+            return self.colours.outside;
+        }
+
+        let probe = CellProbe::new(WorldCoordinates::<TEngineParameters::Coordinate>::from_cell_centre(cell_x as u16, cell_y as u16));
+        match world.probe_cell(&probe) {
+            CellProbeResult::Empty => self.colours.floor,
+            CellProbeResult::Opaque(_) => self.colours.wall,
+            CellProbeResult::Transparent(_) | CellProbeResult::PossiblyTransparent(_) => self.colours.door
+        }
+    }
+
+    fn pixel_for(&self, at: (I32F32, I32F32), centre: (I32F32, I32F32)) -> (i32, i32) {
+        let pixels_per_cell = I32F32::from_num(self.pixels_per_cell);
+        let to_pixels = |coordinate: I32F32, centre: I32F32| coordinate.saturating_sub(centre).saturating_mul(pixels_per_cell).floor().to_num::<i32>();
+        (
+            i32::from(self.left + self.width_pixels / 2) + to_pixels(at.0, centre.0),
+            i32::from(self.top + self.height_pixels / 2) + to_pixels(at.1, centre.1)
+        )
+    }
+
+    fn set_pixel_clipped<TCanvas: Canvas>(&self, canvas: &mut TCanvas, x: i32, y: i32, colour: Colour) -> Result<bool> {
+        let is_x_within = x >= i32::from(self.left) && x < i32::from(self.left + self.width_pixels);
+        let is_y_within = y >= i32::from(self.top) && y < i32::from(self.top + self.height_pixels);
+        if is_x_within && is_y_within {
+            canvas.set_pixel(x as u16, y as u16, colour)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn position_in_cells(position: WorldCoordinates<TEngineParameters::Coordinate>) -> (I32F32, I32F32) {
+        (position.x().to_num(), position.y().to_num())
+    }
+}
+
+pub struct MinimapOverlay<TEngineParameters, TWorld, TRayObserver = ()>
+    where
+        TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver + CellVisibility {
+
+    scene: Scene<TEngineParameters, TWorld, TRayObserver>,
+    minimap: Minimap<TEngineParameters>,
+    is_visible: bool
+}
+
+impl<TEngineParameters, TWorld, TRayObserver> MinimapOverlay<TEngineParameters, TWorld, TRayObserver>
+    where
+        TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver + CellVisibility {
+
+    pub const fn new(scene: Scene<TEngineParameters, TWorld, TRayObserver>, minimap: Minimap<TEngineParameters>) -> Self {
+        Self { scene, minimap, is_visible: false }
+    }
+
+    pub fn with_visibility(self, is_visible: bool) -> Self {
+        Self { is_visible, ..self }
+    }
+
+    pub fn scene(&self) -> &Scene<TEngineParameters, TWorld, TRayObserver> { &self.scene }
+
+    pub fn is_visible(&self) -> bool { self.is_visible }
+}
+
+impl<TEngineParameters, TWorld, TRayObserver, TCanvas> FrameRenderer<TCanvas> for MinimapOverlay<TEngineParameters, TWorld, TRayObserver>
+    where
+        TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
+        TWorld: World<TEngineParameters::Coordinate> + WorldRendering,
+        TRayObserver: RayObserver + CellVisibility,
+        TCanvas: Canvas {
+
+    fn render_frame_onto(&mut self, canvas: &mut TCanvas) -> Result<()> {
+        self.scene.render_frame_onto(canvas)?;
+        if self.is_visible {
+            self.minimap.render_onto(self.scene.world(), self.scene.camera(), self.scene.ray_observer(), canvas)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<TEngineParameters, TWorld, TRayObserver, TStimuli> OnStimuli<TStimuli> for MinimapOverlay<TEngineParameters, TWorld, TRayObserver>
+    where
+        TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver + CellVisibility,
+        TStimuli: Stimuli + CameraStimuli + MinimapStimuli {
+
+    fn on_stimuli(&mut self, stimuli: &TStimuli) -> Result<()> {
+        if stimuli.should_toggle_minimap() {
+            self.is_visible = !self.is_visible;
+        }
+
+        self.scene.on_stimuli(stimuli)
+    }
+}
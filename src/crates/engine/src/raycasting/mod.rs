@@ -1,30 +1,74 @@
+#[cfg(feature = "std")]
+mod accuracy_analysis;
+#[cfg(feature = "std")]
+pub use accuracy_analysis::*;
+
 mod angle;
 pub use angle::*;
 
 mod camera;
 pub use camera::*;
 
+mod cell_bitmap;
+pub use cell_bitmap::*;
+
 mod context;
 pub use context::*;
 
 mod coordinates;
 pub use coordinates::*;
 
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::*;
+
 mod engine_parameters;
 pub use engine_parameters::*;
 
+mod exploration;
+pub use exploration::*;
+
+mod map_checker;
+pub use map_checker::*;
+
+mod minimap;
+pub use minimap::*;
+
 mod object;
 pub use object::*;
 
 mod projection_plane_parameters;
 pub use projection_plane_parameters::*;
 
+mod ray_query;
+pub use ray_query::*;
+
+mod ray_traversal;
+pub use ray_traversal::*;
+
+#[cfg(feature = "std")]
+mod reference_raycaster;
+#[cfg(feature = "std")]
+pub use reference_raycaster::*;
+
 mod rendering;
 pub use rendering::*;
 
 mod scene;
 pub use scene::*;
 
+#[cfg(feature = "std")]
+mod stimuli_recording;
+#[cfg(feature = "std")]
+pub use stimuli_recording::*;
+
+mod stimuli_script;
+pub use stimuli_script::*;
+
+mod stimuli_snapshot;
+pub use stimuli_snapshot::*;
+
 mod world;
 pub use world::*;
 
@@ -1,21 +1,21 @@
-use super::{Angle, WorldCoordinates};
+use super::{Angle, WorldAbsoluteCoordinate, WorldCoordinate, WorldCoordinates};
 
-pub struct Object {
-    position: WorldCoordinates,
+pub struct Object<TCoordinate: WorldCoordinate = WorldAbsoluteCoordinate> {
+    position: WorldCoordinates<TCoordinate>,
     direction: Angle
 }
 
-impl Object {
-    pub const fn new(position: WorldCoordinates, direction: Angle) -> Self {
+impl<TCoordinate: WorldCoordinate> Object<TCoordinate> {
+    pub const fn new(position: WorldCoordinates<TCoordinate>, direction: Angle) -> Self {
         Self {
             position,
             direction
         }
     }
 
-    pub fn position(&self) -> WorldCoordinates { self.position }
+    pub fn position(&self) -> WorldCoordinates<TCoordinate> { self.position }
 
-    pub fn set_position(&mut self, position: WorldCoordinates) {
+    pub fn set_position(&mut self, position: WorldCoordinates<TCoordinate>) {
         self.position = position;
     }
 
@@ -0,0 +1,112 @@
+use core::marker::PhantomData;
+
+use fixed::traits::Fixed;
+use fixed::types::{I32F32, I8F24};
+
+use crate::{Result, Vector2d};
+use super::*;
+
+type Coordinate<TEngineParameters> = <TEngineParameters as EngineParameters>::Coordinate;
+type Distance<TEngineParameters> = <Coordinate<TEngineParameters> as WorldCoordinate>::Distance;
+
+#[derive(Copy, Clone)]
+pub struct RayHit<TCoordinate: WorldCoordinate> {
+    cell_x: u16,
+    cell_y: u16,
+    face: CellFace,
+    point: WorldCoordinates<TCoordinate>,
+    distance: TCoordinate::Distance,
+    cell_tag: CellTag
+}
+
+impl<TCoordinate: WorldCoordinate> RayHit<TCoordinate> {
+    pub const fn new(cell_x: u16, cell_y: u16, face: CellFace, point: WorldCoordinates<TCoordinate>, distance: TCoordinate::Distance, cell_tag: CellTag) -> Self {
+        Self { cell_x, cell_y, face, point, distance, cell_tag }
+    }
+
+    pub fn cell_x(&self) -> u16 { self.cell_x }
+
+    pub fn cell_y(&self) -> u16 { self.cell_y }
+
+    pub fn face(&self) -> CellFace { self.face }
+
+    pub fn point(&self) -> WorldCoordinates<TCoordinate> { self.point }
+
+    pub fn distance(&self) -> TCoordinate::Distance { self.distance }
+
+    pub fn cell_tag(&self) -> CellTag { self.cell_tag }
+}
+
+/*
+    Rays cast for gameplay rather than rendering, so there is no camera or canvas involved.  The directions
+    are normalised before the walk, which makes the distances Euclidean rather than perpendicular to any
+    projection plane; only opaque cells stop a ray, the same as they do when rendering.
+*/
+pub struct RayQuery<'w, TEngineParameters, TWorld>
+    where
+        TEngineParameters: EngineParameters + Trigonometry,
+        TWorld: World<Coordinate<TEngineParameters>> {
+
+    _parameters: PhantomData<TEngineParameters>,
+    world: &'w TWorld
+}
+
+impl<'w, TEngineParameters, TWorld> RayQuery<'w, TEngineParameters, TWorld>
+    where
+        TEngineParameters: EngineParameters + Trigonometry,
+        TWorld: World<Coordinate<TEngineParameters>> {
+
+    pub const fn new(world: &'w TWorld) -> Self {
+        Self { _parameters: PhantomData, world }
+    }
+
+    pub fn first_hit_along(&self, origin: WorldCoordinates<Coordinate<TEngineParameters>>, direction: Angle, max_distance: Distance<TEngineParameters>) -> Result<Option<RayHit<Coordinate<TEngineParameters>>>> {
+        // The same convention as the camera, where an angle of zero looks along -x (East)
+        let unit_direction = (
+            -TEngineParameters::cosine(direction).to_num::<I32F32>(),
+            -TEngineParameters::sine(direction).to_num::<I32F32>());
+
+        self.first_hit(origin, unit_direction, max_distance.to_num())
+    }
+
+    pub fn first_hit_between(&self, from: WorldCoordinates<Coordinate<TEngineParameters>>, to: WorldCoordinates<Coordinate<TEngineParameters>>) -> Result<Option<RayHit<Coordinate<TEngineParameters>>>> {
+        let delta = (
+            to.x().to_num::<I32F32>() - from.x().to_num::<I32F32>(),
+            to.y().to_num::<I32F32>() - from.y().to_num::<I32F32>());
+
+        let length = (delta.0 * delta.0 + delta.1 * delta.1).sqrt();
+        if length == 0 {
+            Ok(None)
+        } else {
+            self.first_hit(from, (delta.0 / length, delta.1 / length), length)
+        }
+    }
+
+    pub fn has_line_of_sight(&self, from: WorldCoordinates<Coordinate<TEngineParameters>>, to: WorldCoordinates<Coordinate<TEngineParameters>>) -> Result<bool> {
+        Ok(self.first_hit_between(from, to)?.is_none())
+    }
+
+    fn first_hit(&self, origin: WorldCoordinates<Coordinate<TEngineParameters>>, unit_direction: (I32F32, I32F32), max_distance: I32F32) -> Result<Option<RayHit<Coordinate<TEngineParameters>>>> {
+        // Each cell travelled can cross at most one vertical and one horizontal boundary
+        let max_probes = (max_distance.ceil().saturating_to_num::<usize>() + 1) * 2;
+        let ray_direction = Vector2d::new(unit_direction.0.saturating_to_num::<I8F24>(), unit_direction.1.saturating_to_num::<I8F24>());
+
+        for crossing in RayTraversal::new(self.world, origin, ray_direction, max_probes) {
+            let crossing = crossing?;
+            let distance = crossing.distance().to_num::<I32F32>();
+            if distance > max_distance {
+                break;
+            }
+
+            if let CellProbeResult::Opaque(cell_tag) = crossing.probe_result() {
+                let point = WorldCoordinates::new(
+                    (origin.x().to_num::<I32F32>() + unit_direction.0 * distance).saturating_to_num(),
+                    (origin.y().to_num::<I32F32>() + unit_direction.1 * distance).saturating_to_num());
+
+                return Ok(Some(RayHit::new(crossing.cell_x(), crossing.cell_y(), crossing.face(), point, crossing.distance(), *cell_tag)));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,182 @@
+use fixed::traits::Fixed;
+use fixed::types::{I8F0, I8F24};
+
+use crate::{Result, Vector2d};
+use super::*;
+
+type Distance<TCoordinate> = <TCoordinate as WorldCoordinate>::Distance;
+
+pub struct RayCrossing<TCoordinate: WorldCoordinate> {
+    distance: Distance<TCoordinate>,
+    cell: WorldCoordinates<TCoordinate>,
+    face: CellFace,
+    probe_result: CellProbeResult
+}
+
+impl<TCoordinate: WorldCoordinate> RayCrossing<TCoordinate> {
+    pub const fn new(distance: Distance<TCoordinate>, cell: WorldCoordinates<TCoordinate>, face: CellFace, probe_result: CellProbeResult) -> Self {
+        Self { distance, cell, face, probe_result }
+    }
+
+    // Perpendicular to the projection plane rather than Euclidean, because the ray's direction is not normalised
+    pub fn distance(&self) -> Distance<TCoordinate> { self.distance }
+
+    pub fn cell(&self) -> WorldCoordinates<TCoordinate> { self.cell }
+
+    pub fn cell_x(&self) -> u16 { self.cell.cell_x_int() }
+
+    pub fn cell_y(&self) -> u16 { self.cell.cell_y_int() }
+
+    pub fn face(&self) -> CellFace { self.face }
+
+    pub fn is_horizontal_intersection(&self) -> bool { matches!(self.face, CellFace::East | CellFace::West) }
+
+    pub fn probe_result(&self) -> &CellProbeResult { &self.probe_result }
+
+    pub fn into_probe_result(self) -> CellProbeResult { self.probe_result }
+}
+
+/*
+    The DDA walk along a single ray, yielding every cell boundary that the ray crosses in order of increasing
+    distance, along with what the world has in the cell on the far side of the boundary.  The walk ends after
+    a fixed number of probes, or after the first error when the world's boundary does not allow the ray to
+    continue; it does not stop at opaque cells, which is left to the caller.
+*/
+pub struct RayTraversal<'w, TCoordinate: WorldCoordinate, TWorld: World<TCoordinate>> {
+    world: &'w TWorld,
+    boundary: WorldBoundary,
+    ray_direction: Vector2d<I8F24>,
+    ray_delta: Vector2d<Distance<TCoordinate>>,
+    ray_cell: WorldCoordinates<TCoordinate>,
+    ray_abs_distance: Vector2d<Distance<TCoordinate>>,
+    ray_cell_step: Vector2d<I8F0>,
+    remaining_probes: usize,
+
+    #[cfg(feature = "diagnostics")]
+    saturations: SaturationCounts
+}
+
+impl<'w, TCoordinate: WorldCoordinate, TWorld: World<TCoordinate>> RayTraversal<'w, TCoordinate, TWorld> {
+    pub fn new(world: &'w TWorld, ray_origin: WorldCoordinates<TCoordinate>, ray_direction: Vector2d<I8F24>, max_probes: usize) -> Self {
+        let ray_delta = Vector2d::new(
+            TCoordinate::distance_along_ray(TCoordinate::ONE_CELL_DISTANCE, ray_direction.x()),
+            TCoordinate::distance_along_ray(TCoordinate::ONE_CELL_DISTANCE, ray_direction.y()));
+
+        let (initial_distance_x, cell_step_x) = Self::initial_distance_and_step(ray_origin.x(), ray_origin.cell_x_floor(), ray_direction.x());
+        let (initial_distance_y, cell_step_y) = Self::initial_distance_and_step(ray_origin.y(), ray_origin.cell_y_floor(), ray_direction.y());
+
+        let traversal = Self {
+            world,
+            boundary: world.boundary(),
+            ray_direction,
+            ray_delta,
+            ray_cell: ray_origin,
+            ray_abs_distance: Vector2d::new(initial_distance_x, initial_distance_y),
+            ray_cell_step: Vector2d::new(cell_step_x, cell_step_y),
+            remaining_probes: max_probes,
+
+            #[cfg(feature = "diagnostics")]
+            saturations: SaturationCounts::new()
+        };
+
+        #[cfg(feature = "diagnostics")]
+        let traversal = traversal.with_initial_cell_edges_diagnosed(ray_origin);
+
+        traversal
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn with_initial_cell_edges_diagnosed(mut self, ray_origin: WorldCoordinates<TCoordinate>) -> Self {
+        for (origin_cell_floor, ray_direction) in [(ray_origin.cell_x_floor(), self.ray_direction.x()), (ray_origin.cell_y_floor(), self.ray_direction.y())] {
+            if ray_direction > 0 && origin_cell_floor.checked_add(TCoordinate::ONE).is_none() {
+                self.saturations.record(SaturationSite::InitialCellEdge);
+            }
+        }
+
+        self
+    }
+
+    #[cfg(feature = "diagnostics")]
+    pub fn saturations(&self) -> &SaturationCounts { &self.saturations }
+
+    #[cfg(feature = "diagnostics")]
+    fn diagnose_step(&mut self, is_horizontal_intersection: bool) {
+        let (distance, ray_delta, coordinate, cell_step) = if is_horizontal_intersection {
+            (self.ray_abs_distance.x(), self.ray_delta.x(), self.ray_cell.x(), self.ray_cell_step.x())
+        } else {
+            (self.ray_abs_distance.y(), self.ray_delta.y(), self.ray_cell.y(), self.ray_cell_step.y())
+        };
+
+        if distance.checked_add(ray_delta).is_none() {
+            self.saturations.record(SaturationSite::RayDistance);
+        }
+
+        // Only a solid boundary clamps the ray's cell; the others either wrap or report an error
+        let stepped = if cell_step > 0 { coordinate.checked_add(TCoordinate::ONE) } else { coordinate.checked_sub(TCoordinate::ONE) };
+        if matches!(self.boundary, WorldBoundary::Solid) && stepped.is_none() {
+            self.saturations.record(SaturationSite::SolidBoundary);
+        }
+    }
+
+    fn initial_distance_and_step(origin: TCoordinate, origin_cell_floor: TCoordinate, ray_direction: I8F24) -> (Distance<TCoordinate>, I8F0) {
+        if ray_direction >= 0 {
+            let initial_cell = origin_cell_floor.saturating_add(TCoordinate::ONE);
+            (TCoordinate::distance_along_ray(origin.distance_to(initial_cell), ray_direction), I8F0::ONE)
+        } else {
+            (TCoordinate::distance_along_ray(origin.distance_to(origin_cell_floor), ray_direction), I8F0::NEG_ONE)
+        }
+    }
+
+    fn face_for(&self, is_horizontal_intersection: bool) -> CellFace {
+        // West is +x and South is +y, so a ray travelling West strikes the East face of the cell, and so on
+        match (is_horizontal_intersection, self.ray_direction.x() > 0, self.ray_direction.y() > 0) {
+            (true, true, _) => CellFace::East,
+            (true, false, _) => CellFace::West,
+            (false, _, true) => CellFace::North,
+            (false, _, false) => CellFace::South
+        }
+    }
+
+    fn step(&mut self) -> Result<(Distance<TCoordinate>, bool)> {
+        let is_horizontal_intersection = self.ray_abs_distance.x() < self.ray_abs_distance.y();
+
+        #[cfg(feature = "diagnostics")]
+        self.diagnose_step(is_horizontal_intersection);
+
+        if is_horizontal_intersection {
+            let distance = self.ray_abs_distance.x();
+            self.ray_abs_distance.set_x(distance.saturating_add(self.ray_delta.x()));
+            self.ray_cell.set_x(self.boundary.step_x(self.ray_cell.x(), self.ray_cell_step.x())?);
+            Ok((distance, true))
+        } else {
+            let distance = self.ray_abs_distance.y();
+            self.ray_abs_distance.set_y(distance.saturating_add(self.ray_delta.y()));
+            self.ray_cell.set_y(self.boundary.step_y(self.ray_cell.y(), self.ray_cell_step.y())?);
+            Ok((distance, false))
+        }
+    }
+}
+
+impl<'w, TCoordinate: WorldCoordinate, TWorld: World<TCoordinate>> Iterator for RayTraversal<'w, TCoordinate, TWorld> {
+    type Item = Result<RayCrossing<TCoordinate>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_probes == 0 {
+            return None;
+        }
+
+        self.remaining_probes -= 1;
+        match self.step() {
+            Ok((distance, is_horizontal_intersection)) => Some(Ok(RayCrossing::new(
+                distance,
+                self.ray_cell,
+                self.face_for(is_horizontal_intersection),
+                self.world.probe_cell(&CellProbe::new(self.ray_cell))))),
+
+            Err(error) => {
+                self.remaining_probes = 0;
+                Some(Err(error))
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use core::f64::consts::PI;
+use core::marker::PhantomData;
+
+use fixed::traits::Fixed;
+use fixed::types::U11F21;
+
+use super::*;
+
+#[derive(Copy, Clone, Debug)]
+pub struct ReferenceIntersection {
+    distance: f64,
+    projected_wall_height: f64,
+    cell_x: u16,
+    cell_y: u16,
+    face: CellFace,
+    cell_offset: f64,
+    cell_tag: CellTag
+}
+
+impl ReferenceIntersection {
+    pub fn distance(&self) -> f64 { self.distance }
+
+    pub fn projected_wall_height(&self) -> f64 { self.projected_wall_height }
+
+    pub fn cell_x(&self) -> u16 { self.cell_x }
+
+    pub fn cell_y(&self) -> u16 { self.cell_y }
+
+    pub fn face(&self) -> CellFace { self.face }
+
+    pub fn cell_offset(&self) -> f64 { self.cell_offset }
+
+    // How close the ray came to a corner, where the fixed-point walk can legitimately pick the neighbouring face
+    pub fn distance_to_nearest_corner(&self) -> f64 { self.cell_offset.min(1.0 - self.cell_offset) }
+
+    pub fn cell_tag(&self) -> CellTag { self.cell_tag }
+}
+
+/*
+    The same DDA walk and projection as the RaycastingContext, but in f64 and from exact trigonometry, so that
+    the error of the fixed-point pipeline can be measured.  The field of view is only known through the
+    projection plane's (I1F15) vector, so its quantisation is the one error that the two have in common.
+*/
+pub struct ReferenceRaycaster<TEngineParameters: EngineParameters + ProjectionPlaneParameters> {
+    _parameters: PhantomData<TEngineParameters>
+}
+
+impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters> ReferenceRaycaster<TEngineParameters> {
+    pub const fn new() -> Self {
+        Self { _parameters: PhantomData }
+    }
+
+    pub fn cast_column<TWorld>(&self, world: &TWorld, position: WorldCoordinates<TEngineParameters::Coordinate>, direction: Angle, column: u16) -> Option<ReferenceIntersection>
+        where
+            TWorld: World<TEngineParameters::Coordinate> {
+
+        let radians = direction.to_fixed_point().to_num::<f64>() * PI;
+        let (sine, cosine) = radians.sin_cos();
+        let projection_plane_y = TEngineParameters::PROJECTION_PLANE_VECTOR_Y.to_num::<f64>();
+        let column_scaling = 2.0 * f64::from(column) / f64::from(TEngineParameters::CANVAS_WIDTH_PIXELS) - 1.0;
+        let ray = (
+            -cosine - column_scaling * projection_plane_y * sine,
+            -sine + column_scaling * projection_plane_y * cosine);
+
+        let origin = (position.x().to_num::<f64>(), position.y().to_num::<f64>());
+        let (distance, cell_x, cell_y, is_horizontal_intersection, cell_tag) = Self::walk(world, origin, ray)?;
+
+        let (along_wall, is_mirrored) = if is_horizontal_intersection {
+            (origin.1 + distance * ray.1, ray.0 > 0.0)
+        } else {
+            (origin.0 + distance * ray.0, ray.1 < 0.0)
+        };
+
+        let cell_offset = along_wall - along_wall.floor();
+        let face = match (is_horizontal_intersection, ray.0 > 0.0, ray.1 > 0.0) {
+            (true, true, _) => CellFace::East,
+            (true, false, _) => CellFace::West,
+            (false, _, true) => CellFace::North,
+            (false, _, false) => CellFace::South
+        };
+
+        Some(ReferenceIntersection {
+            distance,
+            projected_wall_height: Self::projected_wall_height_for(distance),
+            cell_x,
+            cell_y,
+            face,
+            cell_offset: if is_mirrored { 1.0 - cell_offset } else { cell_offset },
+            cell_tag
+        })
+    }
+
+    fn walk<TWorld: World<TEngineParameters::Coordinate>>(world: &TWorld, origin: (f64, f64), ray: (f64, f64)) -> Option<(f64, u16, u16, bool, CellTag)> {
+        let boundary = world.boundary();
+        let max_cell = i32::from(TEngineParameters::Coordinate::MAX.cell_int());
+        let (extent_x, extent_y) = match boundary {
+            WorldBoundary::Solid => (max_cell + 1, max_cell + 1),
+            WorldBoundary::Error { width_cells, height_cells } | WorldBoundary::Wrap { width_cells, height_cells } =>
+                (i32::from(width_cells), i32::from(height_cells))
+        };
+
+        let delta = ((1.0 / ray.0).abs(), (1.0 / ray.1).abs());
+        let (mut cell_x, mut cell_y) = (origin.0.floor() as i32, origin.1.floor() as i32);
+        let (step_x, mut distance_x) = if ray.0 >= 0.0 { (1, (f64::from(cell_x) + 1.0 - origin.0) * delta.0) } else { (-1, (origin.0 - f64::from(cell_x)) * delta.0) };
+        let (step_y, mut distance_y) = if ray.1 >= 0.0 { (1, (f64::from(cell_y) + 1.0 - origin.1) * delta.1) } else { (-1, (origin.1 - f64::from(cell_y)) * delta.1) };
+
+        for _ in 0..TEngineParameters::MAX_RAY_CELL_PROBES {
+            let is_horizontal_intersection = distance_x < distance_y;
+            let distance = if is_horizontal_intersection {
+                cell_x = Self::step(cell_x, step_x, extent_x, boundary)?;
+                distance_x += delta.0;
+                distance_x - delta.0
+            } else {
+                cell_y = Self::step(cell_y, step_y, extent_y, boundary)?;
+                distance_y += delta.1;
+                distance_y - delta.1
+            };
+
+            let cell = WorldCoordinates::<TEngineParameters::Coordinate>::from_cell_centre(cell_x as u16, cell_y as u16);
+            if let CellProbeResult::Opaque(cell_tag) = world.probe_cell(&CellProbe::new(cell)) {
+                return Some((distance, cell_x as u16, cell_y as u16, is_horizontal_intersection, cell_tag));
+            }
+        }
+
+        None
+    }
+
+    fn step(cell: i32, step: i32, extent_cells: i32, boundary: WorldBoundary) -> Option<i32> {
+        let stepped = cell + step;
+        match boundary {
+            WorldBoundary::Solid => Some(stepped.clamp(0, extent_cells - 1)),
+            WorldBoundary::Error { .. } => Some(stepped).filter(|stepped| (0..extent_cells).contains(stepped)),
+            WorldBoundary::Wrap { .. } => Some(stepped.rem_euclid(extent_cells))
+        }
+    }
+
+    fn projected_wall_height_for(distance: f64) -> f64 {
+        // The fixed-point heights saturate (and are zero when touching the wall), so the reference does the same
+        let (width, height) = (f64::from(TEngineParameters::CANVAS_WIDTH_PIXELS), f64::from(TEngineParameters::CANVAS_HEIGHT_PIXELS));
+        let max_height = U11F21::MAX.to_num::<f64>();
+        if distance > 0.0 { (height * height / width / distance).min(max_height) } else { 0.0 }
+    }
+}
+
+impl<TEngineParameters: EngineParameters + ProjectionPlaneParameters> Default for ReferenceRaycaster<TEngineParameters> {
+    fn default() -> Self { Self::new() }
+}
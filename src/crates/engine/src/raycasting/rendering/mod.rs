@@ -1,3 +1,5 @@
+use fixed::types::U0F16;
+
 use crate::{Canvas, Result};
 use crate::raycasting::RayCellIntersection;
 
@@ -18,7 +20,8 @@ pub struct RenderingColumn {
     span_clip_offset: u16,
     unclipped_span_length: u16,
     screen_y_end: u16,
-    raycasting: Option<RayCellIntersection>
+    raycasting: Option<RayCellIntersection>,
+    cell_offset_step: U0F16
 }
 
 impl RenderingColumn {
@@ -30,10 +33,16 @@ impl RenderingColumn {
             span_clip_offset: 0,
             unclipped_span_length: 0,
             screen_y_end: 0,
-            raycasting
+            raycasting,
+            cell_offset_step: U0F16::ZERO
         }
     }
 
+    // How far along the wall the ray has moved since the column to the left, or zero if that saw a different face
+    pub const fn with_cell_offset_step(self, cell_offset_step: U0F16) -> Self {
+        Self { cell_offset_step, ..self }
+    }
+
     pub fn next_span(&mut self, clipped_span_length: u16, span_clip_offset: u16, unclipped_span_length: u16) {
         self.clipped_span_length = clipped_span_length;
         self.span_clip_offset = span_clip_offset;
@@ -0,0 +1,153 @@
+use fixed::traits::ToFixed;
+use fixed::types::U16F16;
+
+use crate::Colour;
+use crate::raycasting::{CellFace, WorldDecals};
+use super::{Texture, TextureCoordinate, TextureCoordinates, TextureDescriptor};
+
+#[derive(Copy, Clone, Debug)]
+pub struct DecalPlacement {
+    left: TextureCoordinate,
+    top: TextureCoordinate,
+    width: TextureCoordinate,
+    height: TextureCoordinate
+}
+
+impl DecalPlacement {
+    pub const fn new(left: TextureCoordinate, top: TextureCoordinate, width: TextureCoordinate, height: TextureCoordinate) -> Self {
+        Self { left, top, width, height }
+    }
+
+    pub const fn left(&self) -> TextureCoordinate { self.left }
+
+    pub const fn top(&self) -> TextureCoordinate { self.top }
+
+    pub const fn width(&self) -> TextureCoordinate { self.width }
+
+    pub const fn height(&self) -> TextureCoordinate { self.height }
+
+    pub fn decal_coordinates_for(&self, face_coordinates: TextureCoordinates) -> Option<TextureCoordinates> {
+        let u = face_coordinates.x().checked_sub(self.left)?;
+        let v = face_coordinates.y().checked_sub(self.top)?;
+        if u >= self.width || v >= self.height {
+            return None;
+        }
+
+        Some(TextureCoordinates::new(
+            U16F16::from(u).saturating_div(self.width.into()).saturating_to_fixed(),
+            U16F16::from(v).saturating_div(self.height.into()).saturating_to_fixed()))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Decal<'t> {
+    texture: TextureDescriptor<'t>,
+    placement: DecalPlacement
+}
+
+impl<'t> Decal<'t> {
+    pub const fn new(texture: TextureDescriptor<'t>, placement: DecalPlacement) -> Self {
+        Self { texture, placement }
+    }
+
+    pub const fn texture(&self) -> &TextureDescriptor<'t> { &self.texture }
+
+    pub const fn placement(&self) -> DecalPlacement { self.placement }
+}
+
+#[derive(Copy, Clone)]
+pub struct PlacedDecal {
+    cell_x: u16,
+    cell_y: u16,
+    face: CellFace,
+    texture_id: u8,
+    placement: DecalPlacement
+}
+
+impl PlacedDecal {
+    pub const fn new(cell_x: u16, cell_y: u16, face: CellFace, texture_id: u8, placement: DecalPlacement) -> Self {
+        Self { cell_x, cell_y, face, texture_id, placement }
+    }
+
+    const fn key(&self) -> u64 { Self::key_of(self.cell_x, self.cell_y, self.face) }
+
+    const fn key_of(cell_x: u16, cell_y: u16, face: CellFace) -> u64 {
+        ((cell_y as u64) << 18) | ((cell_x as u64) << 2) | face as u64
+    }
+}
+
+/*
+    Each texture is listed once and the placements refer to it by its index, so that a texture placed on
+    many walls is only counted once.  The placements are sorted by cell and face, which is checked when the
+    table is built, so that a column's decal is found by a binary search rather than a scan.
+*/
+pub struct DecalTable<'t> {
+    textures: &'t [TextureDescriptor<'t>],
+    placements: &'t [PlacedDecal]
+}
+
+impl<'t> DecalTable<'t> {
+    pub const fn new(textures: &'t [TextureDescriptor<'t>], placements: &'t [PlacedDecal]) -> Self {
+        let mut index = 0;
+        while index < placements.len() {
+            assert!((placements[index].texture_id as usize) < textures.len(), "Decal Texture Id must be in the table");
+            assert!(index == 0 || placements[index - 1].key() < placements[index].key(), "Decal placements must be sorted by Y, X and Face, once each");
+            index += 1;
+        }
+
+        Self { textures, placements }
+    }
+
+    pub const fn memory_bytes(&self) -> usize {
+        let (mut bytes, mut index) = (0, 0);
+        while index < self.textures.len() {
+            bytes += self.textures[index].memory_bytes();
+            index += 1;
+        }
+
+        bytes
+    }
+}
+
+impl WorldDecals for DecalTable<'_> {
+    fn decal_at(&self, cell_x: u16, cell_y: u16, face: CellFace) -> Option<Decal<'_>> {
+        self.placements
+            .binary_search_by_key(&PlacedDecal::key_of(cell_x, cell_y, face), PlacedDecal::key)
+            .ok()
+            .map(|index| {
+                let placed = &self.placements[index];
+                Decal::new(self.textures[usize::from(placed.texture_id)], placed.placement)
+            })
+    }
+}
+
+pub struct DecalOverlay<'t, TTexture: Texture> {
+    base: TTexture,
+    decal: Option<Decal<'t>>
+}
+
+impl<'t, TTexture: Texture> DecalOverlay<'t, TTexture> {
+    pub const fn new(base: TTexture, decal: Option<Decal<'t>>) -> Self {
+        Self { base, decal }
+    }
+}
+
+impl<TTexture: Texture> Texture for DecalOverlay<'_, TTexture> {
+    fn get_texel_at(&self, coordinates: TextureCoordinates) -> Option<Colour> {
+        self.get_texel_at_mip_level(coordinates, 0)
+    }
+
+    fn mip_level_for(&self, projected_height_pixels: u16, cell_offset_step: TextureCoordinate) -> u8 {
+        self.base.mip_level_for(projected_height_pixels, cell_offset_step)
+    }
+
+    fn get_texel_at_mip_level(&self, coordinates: TextureCoordinates, mip_level: u8) -> Option<Colour> {
+        // Compositing at the texel rather than the pixel means anything that shades the wall shades its decals too
+        self.decal
+            .as_ref()
+            .and_then(|decal| decal.placement
+                .decal_coordinates_for(coordinates)
+                .and_then(|decal_coordinates| decal.texture.get_texel_at_mip_level(decal_coordinates, mip_level)))
+            .or_else(|| self.base.get_texel_at_mip_level(coordinates, mip_level))
+    }
+}
@@ -4,6 +4,12 @@ use fixed::types::{U0F16, U16F16};
 use crate::{Canvas, Colour, Error, Result, Vector2d};
 use super::{ColumnRendering, RenderingColumn};
 
+mod decals;
+pub use decals::*;
+
+mod registry;
+pub use registry::*;
+
 mod repeated_static;
 pub use repeated_static::*;
 
@@ -15,15 +21,21 @@ pub type TextureCoordinates = Vector2d<TextureCoordinate>;
 
 pub trait Texture {
     fn get_texel_at(&self, coordinates: TextureCoordinates) -> Option<Colour>;
+
+    fn mip_level_for(&self, _projected_height_pixels: u16, _cell_offset_step: TextureCoordinate) -> u8 { 0 }
+
+    fn get_texel_at_mip_level(&self, coordinates: TextureCoordinates, _mip_level: u8) -> Option<Colour> {
+        self.get_texel_at(coordinates)
+    }
 }
 
 pub struct TextureMappedColumnRenderer<'c, TTexture: Texture> {
-    texture: &'c TTexture,
+    texture: TTexture,
     column: &'c mut RenderingColumn
 }
 
 impl<'c, TTexture: Texture> TextureMappedColumnRenderer<'c, TTexture> {
-    pub const fn new(texture: &'c TTexture, column: &'c mut RenderingColumn) -> Self {
+    pub const fn new(texture: TTexture, column: &'c mut RenderingColumn) -> Self {
         Self { texture, column }
     }
 }
@@ -50,9 +62,10 @@ impl<'c, TTexture: Texture> ColumnRendering for TextureMappedColumnRenderer<'_,
             .checked_to_fixed()
             .ok_or(Error::TextureMappingOverflowDeltaY)?;
 
+        let mip_level = self.texture.mip_level_for(self.column.unclipped_span_length, self.column.cell_offset_step);
         let mut texel_coordinates = TextureCoordinates::new(texel_x, texel_y);
         while self.column.screen_y < self.column.screen_y_end {
-            if let Some(texel) = self.texture.get_texel_at(texel_coordinates) {
+            if let Some(texel) = self.texture.get_texel_at_mip_level(texel_coordinates, mip_level) {
                 canvas.set_pixel(self.column.screen_x, self.column.screen_y, texel)?;
             }
 
@@ -0,0 +1,238 @@
+use crate::{Canvas, Colour, Result};
+use crate::raycasting::CellTag;
+use super::super::{ColumnRendering, RenderingColumn, SolidColourColumnRenderer};
+use super::{repeated_texel_index, stretched_texel_index, Decal, DecalOverlay, Texture, TextureCoordinate, TextureCoordinates, TextureMappedColumnRenderer, REPEATED_TEXELS_PER_CELL};
+
+#[macro_export]
+macro_rules! include_texture {
+    (
+        pixels: $pixels_path:literal;
+        palette: $palette_path:literal;
+        size: $width_pixels:literal x $height_pixels:literal pixels;
+        wrapping: $wrapping:ident;
+        mip_levels: $mip_levels:literal;
+    ) => {
+        ::notray_procmacro::_include_texture!(
+            $pixels_path,
+            $palette_path,
+            $width_pixels,
+            $height_pixels,
+            $wrapping,
+            $mip_levels)
+    };
+
+    // The transparent colour is a palette index here, rather than a Colour, so that the mip levels can leave it out
+    (
+        pixels: $pixels_path:literal;
+        palette: $palette_path:literal;
+        size: $width_pixels:literal x $height_pixels:literal pixels;
+        wrapping: $wrapping:ident;
+        mip_levels: $mip_levels:literal;
+        transparent_colour: $transparent_colour:literal;
+    ) => {
+        ::notray_procmacro::_include_texture!(
+            $pixels_path,
+            $palette_path,
+            $width_pixels,
+            $height_pixels,
+            $wrapping,
+            $mip_levels,
+            $transparent_colour)
+    };
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TextureWrapping {
+    Stretched,
+    Repeated
+}
+
+// The largest power-of-two u8 dimension halves seven times before it is a single pixel
+const MAX_MIP_LEVELS: usize = u8::BITS as usize;
+
+#[derive(Copy, Clone)]
+pub struct TextureDescriptor<'t> {
+    width_pixels: u8,
+    height_pixels: u8,
+    wrapping: TextureWrapping,
+    transparent_colour: Option<Colour>,
+    mip_levels: u8,
+    mip_level_offsets: [u16; MAX_MIP_LEVELS],
+    pixels: &'t [u8]
+}
+
+impl<'t> TextureDescriptor<'t> {
+    pub const fn new(width_pixels: u8, height_pixels: u8, wrapping: TextureWrapping, pixels: &'t [u8]) -> Self {
+        assert!(width_pixels != 0 && width_pixels.is_power_of_two(), "Texture Width must be a power of two");
+        assert!(height_pixels != 0 && height_pixels.is_power_of_two(), "Texture Height must be a power of two");
+        assert!(pixels.len() >= width_pixels as usize * height_pixels as usize, "Texture pixels must cover the Width and Height");
+
+        Self {
+            width_pixels,
+            height_pixels,
+            wrapping,
+            transparent_colour: None,
+            mip_levels: 1,
+            mip_level_offsets: [0; MAX_MIP_LEVELS],
+            pixels
+        }
+    }
+
+    pub const fn with_mip_levels(self, mip_levels: u8) -> Self {
+        let smallest_dimension = if self.width_pixels < self.height_pixels { self.width_pixels } else { self.height_pixels };
+        assert!(
+            mip_levels >= 1 && (mip_levels as u32) <= smallest_dimension.ilog2() + 1,
+            "Number of Mip Levels must be at least one and cannot shrink the texture beyond a single pixel");
+
+        // The offsets are worked out once here rather than for every texel fetched
+        let mut mip_level_offsets = [0; MAX_MIP_LEVELS];
+        let mut mip_level = 1;
+        while mip_level < mip_levels {
+            let (width, height) = self.mip_level_dimensions(mip_level - 1);
+            mip_level_offsets[mip_level as usize] = mip_level_offsets[mip_level as usize - 1] + (width * height) as u16;
+            mip_level += 1;
+        }
+
+        let mipmapped = Self { mip_levels, mip_level_offsets, ..self };
+        assert!(self.pixels.len() >= mipmapped.memory_bytes(), "Texture pixels must cover every Mip Level");
+        mipmapped
+    }
+
+    pub const fn with_transparent_colour(self, colour: Colour) -> Self {
+        Self { transparent_colour: Some(colour), ..self }
+    }
+
+    pub const fn width_pixels(&self) -> u8 { self.width_pixels }
+
+    pub const fn height_pixels(&self) -> u8 { self.height_pixels }
+
+    pub const fn wrapping(&self) -> TextureWrapping { self.wrapping }
+
+    pub const fn transparent_colour(&self) -> Option<Colour> { self.transparent_colour }
+
+    pub const fn mip_levels(&self) -> u8 { self.mip_levels }
+
+    pub const fn pixels(&self) -> &'t [u8] { self.pixels }
+
+    pub const fn memory_bytes(&self) -> usize {
+        let (mut bytes, mut mip_level) = (0, 0);
+        while mip_level < self.mip_levels {
+            let (width, height) = self.mip_level_dimensions(mip_level);
+            bytes += width * height;
+            mip_level += 1;
+        }
+
+        bytes
+    }
+
+    const fn mip_level_dimensions(&self, mip_level: u8) -> (usize, usize) {
+        (
+            (self.width_pixels >> mip_level) as usize,
+            (self.height_pixels >> mip_level) as usize
+        )
+    }
+}
+
+impl Texture for TextureDescriptor<'_> {
+    fn get_texel_at(&self, coordinates: TextureCoordinates) -> Option<Colour> {
+        self.get_texel_at_mip_level(coordinates, 0)
+    }
+
+    fn mip_level_for(&self, projected_height_pixels: u16, cell_offset_step: TextureCoordinate) -> u8 {
+        // Walls seen at a glance are squeezed across far more than down, so whichever is squeezed the most decides
+        let (texels_per_cell_across, texels_per_projected_height) = match self.wrapping {
+            TextureWrapping::Stretched => (u32::from(self.width_pixels), u32::from(self.height_pixels)),
+            TextureWrapping::Repeated => (u32::from(REPEATED_TEXELS_PER_CELL), u32::from(REPEATED_TEXELS_PER_CELL))
+        };
+
+        let texels_per_pixel_down = texels_per_projected_height / u32::from(projected_height_pixels.max(1));
+        let texels_per_pixel_across = (u32::from(cell_offset_step.to_bits()) * texels_per_cell_across) >> TextureCoordinate::FRAC_NBITS;
+        let texels_per_pixel = texels_per_pixel_down.max(texels_per_pixel_across);
+        if texels_per_pixel < 2 {
+            0
+        } else {
+            (texels_per_pixel.ilog2() as u8).min(self.mip_levels - 1)
+        }
+    }
+
+    fn get_texel_at_mip_level(&self, coordinates: TextureCoordinates, mip_level: u8) -> Option<Colour> {
+        let mip_level = mip_level.min(self.mip_levels - 1);
+        let (width, height) = self.mip_level_dimensions(mip_level);
+        let index = usize::from(self.mip_level_offsets[mip_level as usize]) + match self.wrapping {
+            TextureWrapping::Stretched => stretched_texel_index(coordinates, width, height),
+            TextureWrapping::Repeated => repeated_texel_index(coordinates, width, height, mip_level)
+        };
+
+        let texel = self.pixels[index];
+        match self.transparent_colour {
+            Some(transparent) if transparent.as_index() == texel => None,
+            _ => Some(Colour::new(texel))
+        }
+    }
+}
+
+pub trait TextureRegistry {
+    fn texture_for(&self, cell_tag: CellTag) -> Option<&TextureDescriptor<'_>>;
+
+    fn wall_renderer_for<'c>(
+        &'c self,
+        cell_tag: Option<CellTag>,
+        decal: Option<Decal<'c>>,
+        unbound_colour: Colour,
+        column: &'c mut RenderingColumn) -> RegisteredTextureColumnRenderer<'c> {
+
+        match cell_tag.and_then(|cell_tag| self.texture_for(cell_tag)) {
+            Some(texture) => RegisteredTextureColumnRenderer::Bound(TextureMappedColumnRenderer::new(DecalOverlay::new(*texture, decal), column)),
+            None => RegisteredTextureColumnRenderer::Unbound(SolidColourColumnRenderer::new(unbound_colour, column))
+        }
+    }
+}
+
+pub struct TextureTable<'t> {
+    textures: &'t [Option<TextureDescriptor<'t>>]
+}
+
+impl<'t> TextureTable<'t> {
+    pub const fn new(textures: &'t [Option<TextureDescriptor<'t>>]) -> Self {
+        Self { textures }
+    }
+
+    pub const fn len(&self) -> usize { self.textures.len() }
+
+    pub const fn is_empty(&self) -> bool { self.textures.is_empty() }
+
+    pub const fn memory_bytes(&self) -> usize {
+        let (mut bytes, mut index) = (0, 0);
+        while index < self.textures.len() {
+            if let Some(texture) = &self.textures[index] {
+                bytes += texture.memory_bytes();
+            }
+
+            index += 1;
+        }
+
+        bytes
+    }
+}
+
+impl TextureRegistry for TextureTable<'_> {
+    fn texture_for(&self, cell_tag: CellTag) -> Option<&TextureDescriptor<'_>> {
+        self.textures
+            .get(cell_tag.wall_id() as usize)
+            .and_then(|texture| texture.as_ref())
+    }
+}
+
+pub enum RegisteredTextureColumnRenderer<'c> {
+    Unbound(SolidColourColumnRenderer<'c>),
+    Bound(TextureMappedColumnRenderer<'c, DecalOverlay<'c, TextureDescriptor<'c>>>)
+}
+
+impl ColumnRendering for RegisteredTextureColumnRenderer<'_> {
+    fn render_column_onto<TCanvas: Canvas>(&mut self, canvas: &mut TCanvas) -> Result<()> {
+        match self {
+            RegisteredTextureColumnRenderer::Unbound(renderer) => renderer.render_column_onto(canvas),
+            RegisteredTextureColumnRenderer::Bound(renderer) => renderer.render_column_onto(canvas)
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::{raycasting::WorldAbsoluteCoordinate, Colour};
-use super::{Texture, TextureCoordinates};
+use crate::Colour;
+use super::{Texture, TextureCoordinate, TextureCoordinates};
 
 pub struct RepeatedStaticTexture<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLOUR: u16> {
     pixels: &'t [u8]
@@ -19,11 +19,7 @@ impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLO
 
 impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLOUR: u16> Texture for RepeatedStaticTexture<'t, WIDTH_PIXELS, HEIGHT_PIXELS, TRANSPARENT_COLOUR> {
     fn get_texel_at(&self, coordinates: TextureCoordinates) -> Option<Colour> {
-        const MSB_SHIFT: u32 = 16 - WorldAbsoluteCoordinate::INT_NBITS;
-        let u: usize = ((coordinates.x().to_bits() >> MSB_SHIFT) as usize) & (Self::WIDTH_PIXELS - 1);
-        let v: usize = ((coordinates.y().to_bits() >> MSB_SHIFT) as usize) & (Self::HEIGHT_PIXELS - 1);
-
-        let texel = self.pixels[v * Self::WIDTH_PIXELS + u];
+        let texel = self.pixels[repeated_texel_index(coordinates, Self::WIDTH_PIXELS, Self::HEIGHT_PIXELS, 0)];
         if texel as u16 != TRANSPARENT_COLOUR {
             Some(Colour::new(texel))
         } else {
@@ -31,3 +27,17 @@ impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLO
         }
     }
 }
+
+// Repeated textures are addressed as though each face were 256 texels across, whatever the world's precision
+const REPEATED_TEXEL_INDEX_BITS: u32 = 8;
+const MSB_SHIFT: u32 = TextureCoordinate::FRAC_NBITS - REPEATED_TEXEL_INDEX_BITS;
+
+pub(super) const REPEATED_TEXELS_PER_CELL: u16 = 1 << REPEATED_TEXEL_INDEX_BITS;
+
+pub(super) fn repeated_texel_index(coordinates: TextureCoordinates, width_pixels: usize, height_pixels: usize, mip_level: u8) -> usize {
+    let msb_shift = MSB_SHIFT + mip_level as u32;
+    let u: usize = ((coordinates.x().to_bits() >> msb_shift) as usize) & (width_pixels - 1);
+    let v: usize = ((coordinates.y().to_bits() >> msb_shift) as usize) & (height_pixels - 1);
+
+    v * width_pixels + u
+}
@@ -9,9 +9,7 @@ pub struct StretchedStaticTexture<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXEL
 
 impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLOUR: u16> StretchedStaticTexture<'t, WIDTH_PIXELS, HEIGHT_PIXELS, TRANSPARENT_COLOUR> {
     const WIDTH_PIXELS: usize = WIDTH_PIXELS as usize;
-    const WIDTH_PIXELS_FIXED: U16F0 = U16F0::const_from_int(WIDTH_PIXELS as u16);
     const HEIGHT_PIXELS: usize = HEIGHT_PIXELS as usize;
-    const HEIGHT_PIXELS_FIXED: U16F0 = U16F0::const_from_int(HEIGHT_PIXELS as u16);
 
     const _ENSURE_WIDTH_IS_POWER_2: () = assert!(WIDTH_PIXELS != 0 && WIDTH_PIXELS.is_power_of_two(), "Texture Width must be a power of two");
     const _ENSURE_HEIGHT_IS_POWER_2: () = assert!(HEIGHT_PIXELS != 0 && HEIGHT_PIXELS.is_power_of_two(), "Texture Height must be a power of two");
@@ -23,13 +21,7 @@ impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLO
 
 impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLOUR: u16> Texture for StretchedStaticTexture<'t, WIDTH_PIXELS, HEIGHT_PIXELS, TRANSPARENT_COLOUR> {
     fn get_texel_at(&self, coordinates: TextureCoordinates) -> Option<Colour> {
-        let u: usize = (coordinates.x().wide_mul(Self::WIDTH_PIXELS_FIXED)).round().to_num();
-        let u = u.min(Self::WIDTH_PIXELS - 1);
-
-        let v: usize = (coordinates.y().wide_mul(Self::HEIGHT_PIXELS_FIXED)).round().to_num();
-        let v = v.min(Self::HEIGHT_PIXELS - 1);
-
-        let texel = self.pixels[v * Self::WIDTH_PIXELS + u];
+        let texel = self.pixels[stretched_texel_index(coordinates, Self::WIDTH_PIXELS, Self::HEIGHT_PIXELS)];
         if texel as u16 != TRANSPARENT_COLOUR {
             Some(Colour::new(texel))
         } else {
@@ -37,3 +29,13 @@ impl<'t, const WIDTH_PIXELS: u8, const HEIGHT_PIXELS: u8, const TRANSPARENT_COLO
         }
     }
 }
+
+pub(super) fn stretched_texel_index(coordinates: TextureCoordinates, width_pixels: usize, height_pixels: usize) -> usize {
+    let u: usize = (coordinates.x().wide_mul(U16F0::from_num(width_pixels))).round().to_num();
+    let u = u.min(width_pixels - 1);
+
+    let v: usize = (coordinates.y().wide_mul(U16F0::from_num(height_pixels))).round().to_num();
+    let v = v.min(height_pixels - 1);
+
+    v * width_pixels + u
+}
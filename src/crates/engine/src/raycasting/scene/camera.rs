@@ -20,10 +20,11 @@ pub trait CameraStimuli {
     fn is_fast(&self) -> bool;
 }
 
-impl<TEngineParameters, TWorld, TStimuli> OnStimuli<TStimuli> for Scene<TEngineParameters, TWorld>
+impl<TEngineParameters, TWorld, TRayObserver, TStimuli> OnStimuli<TStimuli> for Scene<TEngineParameters, TWorld, TRayObserver>
     where
         TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
-        TWorld: World,
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver,
         TStimuli: Stimuli + CameraStimuli {
 
     fn on_stimuli(&mut self, stimuli: &TStimuli) -> Result<()> {
@@ -34,13 +35,22 @@ impl<TEngineParameters, TWorld, TStimuli> OnStimuli<TStimuli> for Scene<TEngineP
             self.camera_mut().turn(-left); // TODO: angle needs to be determined based on frame rate, and whether 'is_fast()'
         }
 
-        let forward = WorldRelativeCoordinate::lit("0.125");
+        let forward: <TEngineParameters::Coordinate as WorldCoordinate>::Relative = WorldRelativeCoordinate::lit("0.125").to_num();
+        let boundary = self.world().boundary();
         if stimuli.should_move_forward() {
-            self.camera_mut().move_relative(forward); // TODO: distance needs to be determined based on frame rate, and whether 'is_fast()'
+            self.camera_mut().move_relative(forward, boundary); // TODO: distance needs to be determined based on frame rate, and whether 'is_fast()'
         } else if stimuli.should_move_backward() {
-            self.camera_mut().move_relative(-forward); // TODO: distance needs to be determined based on frame rate, and whether 'is_fast()'
+            self.camera_mut().move_relative(-forward, boundary); // TODO: distance needs to be determined based on frame rate, and whether 'is_fast()'
         }
 
+        let position = self.camera_mut().position();
+        if let Some(rebased_position) = self.world_mut().rebase(position) {
+            self.camera_mut().set_position(rebased_position);
+            self.ray_observer_mut().on_rebased();
+        }
+
+        let position = self.camera_mut().position();
+        self.ray_observer_mut().on_cell_entered(position.cell_x_int(), position.cell_y_int());
         Ok(())
     }
 }
@@ -1,60 +1,106 @@
+use fixed::types::U0F16;
+
 use crate::{Canvas, Colour, FrameRenderer, Result};
 use crate::raycasting::*;
 
-pub struct Scene<TEngineParameters, TWorld>
+pub struct Scene<TEngineParameters, TWorld, TRayObserver = ()>
     where
         TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
-        TWorld: World {
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver {
 
     world: TWorld,
     camera: Camera<TEngineParameters>,
-    raycasting_context: RaycastingContext<TEngineParameters>
+    raycasting_context: RaycastingContext<TEngineParameters>,
+    ray_observer: TRayObserver
 }
 
 impl<TEngineParameters, TWorld> Scene<TEngineParameters, TWorld>
     where
         TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
-        TWorld: World {
+        TWorld: World<TEngineParameters::Coordinate> {
 
     pub fn new(world: TWorld) -> Self {
         Self {
             camera: Camera::new(Object::new(world.spawn_at(), world.spawn_angle())),
             world,
-            raycasting_context: RaycastingContext::default()
+            raycasting_context: RaycastingContext::default(),
+            ray_observer: ()
+        }
+    }
+
+    pub fn with_ray_observer<TRayObserver: RayObserver>(self, ray_observer: TRayObserver) -> Scene<TEngineParameters, TWorld, TRayObserver> {
+        Scene {
+            world: self.world,
+            camera: self.camera,
+            raycasting_context: self.raycasting_context,
+            ray_observer
         }
     }
 }
 
-impl<TEngineParameters, TWorld> HasCameraMut for Scene<TEngineParameters, TWorld>
+impl<TEngineParameters, TWorld, TRayObserver> Scene<TEngineParameters, TWorld, TRayObserver>
+    where
+        TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver {
+
+    pub fn world(&self) -> &TWorld { &self.world }
+
+    pub fn camera(&self) -> &Camera<TEngineParameters> { &self.camera }
+
+    pub fn world_mut(&mut self) -> &mut TWorld { &mut self.world }
+
+    pub fn ray_observer(&self) -> &TRayObserver { &self.ray_observer }
+
+    pub fn ray_observer_mut(&mut self) -> &mut TRayObserver { &mut self.ray_observer }
+
+    #[cfg(feature = "diagnostics")]
+    pub fn diagnostics(&self) -> &RaycastingDiagnostics<TEngineParameters::Coordinate> { self.raycasting_context.diagnostics() }
+}
+
+impl<TEngineParameters, TWorld, TRayObserver> HasCameraMut for Scene<TEngineParameters, TWorld, TRayObserver>
     where
         TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
-        TWorld: World {
+        TWorld: World<TEngineParameters::Coordinate>,
+        TRayObserver: RayObserver {
 
     type EngineParameters = TEngineParameters;
 
     fn camera_mut(&mut self) -> &mut Camera<Self::EngineParameters> { &mut self.camera }
 }
 
-impl<TEngineParameters, TWorld, TCanvas> FrameRenderer<TCanvas> for Scene<TEngineParameters, TWorld>
+impl<TEngineParameters, TWorld, TRayObserver, TCanvas> FrameRenderer<TCanvas> for Scene<TEngineParameters, TWorld, TRayObserver>
     where
         TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry,
-        TWorld: World + WorldRendering,
+        TWorld: World<TEngineParameters::Coordinate> + WorldRendering,
+        TRayObserver: RayObserver,
         TCanvas: Canvas {
 
     fn render_frame_onto(&mut self, canvas: &mut TCanvas) -> Result<()> {
         // TODO: Frame usage
         let mut frame = self.raycasting_context.on_frame_start(&self.camera, canvas)?;
 
+        let mut previous_column_hit: Option<((u16, u16, CellFace), U0F16)> = None;
         for x in 0..TEngineParameters::CANVAS_WIDTH_PIXELS {
-            self.raycasting_context.cast_ray(&self.world)?;
+            self.raycasting_context.cast_ray_observed(&self.world, &mut self.ray_observer)?;
 
             let cell_intersection = self.raycasting_context.cell_intersection();
+            let column_hit = cell_intersection.as_ref().map(|wall| ((wall.cell_x(), wall.cell_y(), wall.face()), wall.cell_offset()));
+            let cell_offset_step = match (previous_column_hit, column_hit) {
+                (Some((previous_face, previous_offset)), Some((face, offset))) if previous_face == face =>
+                    U0F16::from_bits(offset.to_bits().abs_diff(previous_offset.to_bits())),
+
+                _ => U0F16::ZERO
+            };
+
+            previous_column_hit = column_hit;
             let projected_wall_height = if let Some(ref wall) = cell_intersection { wall.projected_wall_height_int() } else { 0 };
             let projected_wall_height_clipped = projected_wall_height.min(TEngineParameters::CANVAS_HEIGHT_PIXELS);
             let top_of_wall = (TEngineParameters::CANVAS_HEIGHT_PIXELS - projected_wall_height_clipped) / 2;
             let bottom_of_wall = TEngineParameters::CANVAS_HEIGHT_PIXELS - top_of_wall;
 
-            let mut column = RenderingColumn::new(x, 0, cell_intersection);
+            let mut column = RenderingColumn::new(x, 0, cell_intersection).with_cell_offset_step(cell_offset_step);
             column.next_span(
                 top_of_wall,
                 0,
@@ -0,0 +1,249 @@
+use core::cell::{Cell, RefCell};
+use std::iter;
+use std::vec::Vec;
+
+use crate::{Error, Pollable, QuitStimuli, Result, Stimuli};
+use super::*;
+
+/*
+    The stimuli for every tick of a GameLoop, run-length encoded because most ticks repeat the one before.
+    The movement is a fixed step per tick, so replaying the ticks into the same world and spawn gives the
+    same camera poses, whatever the frame rate was when they were recorded.
+*/
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StimuliRecording {
+    runs: Vec<(StimuliSnapshot, u32)>
+}
+
+impl StimuliRecording {
+    const MAGIC: &'static [u8; 8] = b"NTRYSTIM";
+    const VERSION: u8 = 1;
+
+    pub const fn new() -> Self {
+        Self { runs: Vec::new() }
+    }
+
+    // Such as a script of ScriptSteps; runs of the same snapshot are merged, as though pushed a tick at a time
+    pub fn from_runs<TRun: Into<(StimuliSnapshot, u32)>>(runs: impl IntoIterator<Item = TRun>) -> Self {
+        let mut recording = Self::new();
+        for (snapshot, ticks) in runs.into_iter().map(Into::into) {
+            recording.push_run(snapshot, ticks);
+        }
+
+        recording
+    }
+
+    pub fn push(&mut self, snapshot: StimuliSnapshot) {
+        self.push_run(snapshot, 1);
+    }
+
+    fn push_run(&mut self, snapshot: StimuliSnapshot, mut ticks: u32) {
+        if let Some((last, last_ticks)) = self.runs.last_mut() && *last == snapshot {
+            let merged = ticks.min(u32::MAX - *last_ticks);
+            *last_ticks += merged;
+            ticks -= merged;
+        }
+
+        if ticks > 0 {
+            self.runs.push((snapshot, ticks));
+        }
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.runs.iter().map(|&(_, ticks)| u64::from(ticks)).sum()
+    }
+
+    pub fn snapshots(&self) -> impl Iterator<Item = StimuliSnapshot> + '_ {
+        self.runs.iter().flat_map(|&(snapshot, ticks)| iter::repeat_n(snapshot, ticks as usize))
+    }
+
+    // The magic and version, then each run as its snapshot's bits and a LEB128 count of ticks
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::MAGIC.len() + 1 + self.runs.len() * 2);
+        bytes.extend(Self::MAGIC);
+        bytes.push(Self::VERSION);
+        for &(snapshot, mut ticks) in &self.runs {
+            bytes.push(snapshot.to_bits());
+            while ticks >= 0x80 {
+                bytes.push((ticks as u8) | 0x80);
+                ticks >>= 7;
+            }
+
+            bytes.push(ticks as u8);
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let runs = bytes
+            .strip_prefix(Self::MAGIC)
+            .ok_or(Error::Str("Not a stimuli recording"))?
+            .split_first()
+            .filter(|&(&version, _)| version == Self::VERSION)
+            .map(|(_, runs)| runs)
+            .ok_or(Error::Str("Unsupported stimuli recording version"))?;
+
+        let mut recording = Self::new();
+        let mut bytes = runs.iter().copied();
+        while let Some(bits) = bytes.next() {
+            let snapshot = StimuliSnapshot::from_bits(bits).ok_or(Error::Str("Unknown stimuli in recording"))?;
+            let ticks = Self::read_ticks(&mut bytes)?;
+            if ticks > 0 {
+                recording.runs.push((snapshot, ticks));
+            }
+        }
+
+        Ok(recording)
+    }
+
+    fn read_ticks(bytes: &mut impl Iterator<Item = u8>) -> Result<u32> {
+        let mut ticks = 0u64;
+        for shift in (0..35).step_by(7) {
+            let byte = bytes.next().ok_or(Error::Str("Truncated stimuli recording"))?;
+            ticks |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return u32::try_from(ticks).map_err(|_| Error::Str("Stimuli recording run is too long"));
+            }
+        }
+
+        Err(Error::Str("Stimuli recording run is too long"))
+    }
+}
+
+// Polled after the stimuli have been updated for the tick, so that what is recorded is what the scene will see
+pub struct StimuliRecorder {
+    recording: RefCell<StimuliRecording>
+}
+
+impl StimuliRecorder {
+    pub const fn new() -> Self {
+        Self { recording: RefCell::new(StimuliRecording::new()) }
+    }
+
+    pub fn pollable<TStimuli: QuitStimuli + CameraStimuli + MinimapStimuli>(&self, stimuli: TStimuli) -> impl Pollable + use<'_, TStimuli> {
+        RecordingPollable { recording: &self.recording, stimuli }
+    }
+
+    pub fn into_recording(self) -> StimuliRecording {
+        self.recording.into_inner()
+    }
+}
+
+impl Default for StimuliRecorder {
+    fn default() -> Self { Self::new() }
+}
+
+struct RecordingPollable<'r, TStimuli: QuitStimuli + CameraStimuli + MinimapStimuli> {
+    recording: &'r RefCell<StimuliRecording>,
+    stimuli: TStimuli
+}
+
+impl<'r, TStimuli: QuitStimuli + CameraStimuli + MinimapStimuli> Pollable for RecordingPollable<'r, TStimuli> {
+    fn poll(&mut self) -> Result<()> {
+        self.recording.borrow_mut().push(StimuliSnapshot::of(&self.stimuli));
+        Ok(())
+    }
+}
+
+/*
+    Plays a recording back in place of live stimuli; each poll moves on to the next tick's snapshot, in the
+    same place in the loop as the StimuliRecorder was polled.  Once the recording runs out the replay quits.
+*/
+pub struct StimuliReplay {
+    recording: StimuliRecording,
+    ticks: u64,
+    ticks_replayed: Cell<u64>,
+    cursor: Cell<ReplayCursor>
+}
+
+// The run that the current tick is in, and how many ticks there are up to the end of that run
+#[derive(Copy, Clone)]
+struct ReplayCursor {
+    run: usize,
+    end_tick: u64
+}
+
+impl StimuliReplay {
+    pub fn new(recording: &StimuliRecording) -> Self {
+        let first_run_ticks = recording.runs.first().map_or(0, |&(_, ticks)| u64::from(ticks));
+        Self {
+            recording: recording.clone(),
+            ticks: recording.ticks(),
+            ticks_replayed: Cell::new(0),
+            cursor: Cell::new(ReplayCursor { run: 0, end_tick: first_run_ticks })
+        }
+    }
+
+    pub fn stimuli(&self) -> ReplayStimuli<'_> {
+        ReplayStimuli { replay: self }
+    }
+
+    pub fn pollable(&self) -> impl Pollable + '_ {
+        ReplayPollable { replay: self }
+    }
+
+    pub fn ticks_replayed(&self) -> u64 { self.ticks_replayed.get().min(self.ticks) }
+
+    pub fn is_finished(&self) -> bool { self.ticks_replayed.get() > self.ticks }
+
+    fn current(&self) -> StimuliSnapshot {
+        if self.ticks_replayed.get() == 0 {
+            return StimuliSnapshot::new();
+        }
+
+        self.recording.runs
+            .get(self.cursor.get().run)
+            .map_or(StimuliSnapshot::new().with_quit(true), |&(snapshot, _)| snapshot)
+    }
+
+    fn advance(&self) {
+        let ticks_replayed = self.ticks_replayed.get().saturating_add(1);
+        let mut cursor = self.cursor.get();
+        while ticks_replayed > cursor.end_tick && cursor.run < self.recording.runs.len() {
+            cursor.run += 1;
+            cursor.end_tick += self.recording.runs.get(cursor.run).map_or(0, |&(_, ticks)| u64::from(ticks));
+        }
+
+        self.ticks_replayed.set(ticks_replayed);
+        self.cursor.set(cursor);
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ReplayStimuli<'r> {
+    replay: &'r StimuliReplay
+}
+
+impl<'r> Stimuli for ReplayStimuli<'r> { }
+
+impl<'r> QuitStimuli for ReplayStimuli<'r> {
+    fn should_quit(&self) -> bool { self.replay.current().should_quit() }
+}
+
+impl<'r> CameraStimuli for ReplayStimuli<'r> {
+    fn should_move_forward(&self) -> bool { self.replay.current().should_move_forward() }
+
+    fn should_move_backward(&self) -> bool { self.replay.current().should_move_backward() }
+
+    fn should_turn_left(&self) -> bool { self.replay.current().should_turn_left() }
+
+    fn should_turn_right(&self) -> bool { self.replay.current().should_turn_right() }
+
+    fn is_fast(&self) -> bool { self.replay.current().is_fast() }
+}
+
+impl<'r> MinimapStimuli for ReplayStimuli<'r> {
+    fn should_toggle_minimap(&self) -> bool { self.replay.current().should_toggle_minimap() }
+}
+
+struct ReplayPollable<'r> {
+    replay: &'r StimuliReplay
+}
+
+impl<'r> Pollable for ReplayPollable<'r> {
+    fn poll(&mut self) -> Result<()> {
+        self.replay.advance();
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+/*
+    One run of a scripted timeline for driving a GameLoop without a keyboard, such as "forward for 10 ticks,
+    turn left for 4 ticks, quit"; the steps become the runs of a StimuliRecording, which a StimuliReplay then
+    plays back and quits once it runs out.
+*/
+#[derive(Copy, Clone, Debug)]
+pub struct ScriptStep {
+    snapshot: StimuliSnapshot,
+    ticks: u32
+}
+
+impl ScriptStep {
+    pub const fn new(snapshot: StimuliSnapshot, ticks: u32) -> Self {
+        Self { snapshot, ticks }
+    }
+
+    pub const fn idle(ticks: u32) -> Self { Self::new(StimuliSnapshot::new(), ticks) }
+
+    pub const fn forward(ticks: u32) -> Self { Self::new(StimuliSnapshot::new().with_move_forward(true), ticks) }
+
+    pub const fn backward(ticks: u32) -> Self { Self::new(StimuliSnapshot::new().with_move_backward(true), ticks) }
+
+    pub const fn turn_left(ticks: u32) -> Self { Self::new(StimuliSnapshot::new().with_turn_left(true), ticks) }
+
+    pub const fn turn_right(ticks: u32) -> Self { Self::new(StimuliSnapshot::new().with_turn_right(true), ticks) }
+
+    pub const fn toggle_minimap() -> Self { Self::new(StimuliSnapshot::new().with_toggle_minimap(true), 1) }
+
+    pub const fn quit() -> Self { Self::new(StimuliSnapshot::new().with_quit(true), 1) }
+
+    pub const fn with_fast(self) -> Self {
+        Self { snapshot: self.snapshot.with_fast(true), ..self }
+    }
+
+    pub const fn snapshot(&self) -> StimuliSnapshot { self.snapshot }
+
+    pub const fn ticks(&self) -> u32 { self.ticks }
+}
+
+impl From<ScriptStep> for (StimuliSnapshot, u32) {
+    fn from(step: ScriptStep) -> Self { (step.snapshot, step.ticks) }
+}
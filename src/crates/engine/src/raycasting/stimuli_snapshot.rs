@@ -0,0 +1,81 @@
+use crate::{QuitStimuli, Stimuli};
+use super::*;
+
+/*
+    Everything that the scene reacts to during a single tick, packed into a byte so that sequences of them
+    are cheap to keep and to compare.  The unused bits are reserved for future actions.
+*/
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StimuliSnapshot(u8);
+
+impl StimuliSnapshot {
+    const MOVE_FORWARD: u8 = 1 << 0;
+    const MOVE_BACKWARD: u8 = 1 << 1;
+    const TURN_LEFT: u8 = 1 << 2;
+    const TURN_RIGHT: u8 = 1 << 3;
+    const FAST: u8 = 1 << 4;
+    const TOGGLE_MINIMAP: u8 = 1 << 5;
+    const QUIT: u8 = 1 << 6;
+    const ALL: u8 = (Self::QUIT << 1) - 1;
+
+    pub const fn new() -> Self { Self(0) }
+
+    pub fn of<TStimuli: QuitStimuli + CameraStimuli + MinimapStimuli>(stimuli: &TStimuli) -> Self {
+        Self::new()
+            .with_move_forward(stimuli.should_move_forward())
+            .with_move_backward(stimuli.should_move_backward())
+            .with_turn_left(stimuli.should_turn_left())
+            .with_turn_right(stimuli.should_turn_right())
+            .with_fast(stimuli.is_fast())
+            .with_toggle_minimap(stimuli.should_toggle_minimap())
+            .with_quit(stimuli.should_quit())
+    }
+
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        if bits & !Self::ALL == 0 { Some(Self(bits)) } else { None }
+    }
+
+    pub const fn to_bits(self) -> u8 { self.0 }
+
+    pub const fn with_move_forward(self, is_set: bool) -> Self { self.with_flag(Self::MOVE_FORWARD, is_set) }
+
+    pub const fn with_move_backward(self, is_set: bool) -> Self { self.with_flag(Self::MOVE_BACKWARD, is_set) }
+
+    pub const fn with_turn_left(self, is_set: bool) -> Self { self.with_flag(Self::TURN_LEFT, is_set) }
+
+    pub const fn with_turn_right(self, is_set: bool) -> Self { self.with_flag(Self::TURN_RIGHT, is_set) }
+
+    pub const fn with_fast(self, is_set: bool) -> Self { self.with_flag(Self::FAST, is_set) }
+
+    pub const fn with_toggle_minimap(self, is_set: bool) -> Self { self.with_flag(Self::TOGGLE_MINIMAP, is_set) }
+
+    pub const fn with_quit(self, is_set: bool) -> Self { self.with_flag(Self::QUIT, is_set) }
+
+    const fn with_flag(self, flag: u8, is_set: bool) -> Self {
+        if is_set { Self(self.0 | flag) } else { Self(self.0 & !flag) }
+    }
+
+    const fn has_flag(self, flag: u8) -> bool { self.0 & flag != 0 }
+}
+
+impl Stimuli for StimuliSnapshot { }
+
+impl QuitStimuli for StimuliSnapshot {
+    fn should_quit(&self) -> bool { self.has_flag(Self::QUIT) }
+}
+
+impl CameraStimuli for StimuliSnapshot {
+    fn should_move_forward(&self) -> bool { self.has_flag(Self::MOVE_FORWARD) }
+
+    fn should_move_backward(&self) -> bool { self.has_flag(Self::MOVE_BACKWARD) }
+
+    fn should_turn_left(&self) -> bool { self.has_flag(Self::TURN_LEFT) }
+
+    fn should_turn_right(&self) -> bool { self.has_flag(Self::TURN_RIGHT) }
+
+    fn is_fast(&self) -> bool { self.has_flag(Self::FAST) }
+}
+
+impl MinimapStimuli for StimuliSnapshot {
+    fn should_toggle_minimap(&self) -> bool { self.has_flag(Self::TOGGLE_MINIMAP) }
+}
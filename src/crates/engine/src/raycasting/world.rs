@@ -1,9 +1,88 @@
-use super::{Angle, ColumnRendering, RenderingColumn, WorldCoordinates};
+use fixed::traits::{Fixed, ToFixed};
+use fixed::types::I8F0;
 
-pub trait World {
-    fn spawn_at(&self) -> WorldCoordinates;
+use crate::{Error, Result};
+use super::{Angle, ColumnRendering, Decal, RenderingColumn, WorldAbsoluteCoordinate, WorldCoordinate, WorldCoordinates};
+
+pub trait World<TCoordinate: WorldCoordinate = WorldAbsoluteCoordinate> {
+    fn spawn_at(&self) -> WorldCoordinates<TCoordinate>;
     fn spawn_angle(&self) -> Angle;
-    fn probe_cell(&self, probe: &CellProbe) -> CellProbeResult;
+    fn probe_cell(&self, probe: &CellProbe<TCoordinate>) -> CellProbeResult;
+
+    fn boundary(&self) -> WorldBoundary { WorldBoundary::Solid }
+
+    fn rebase(&mut self, _position: WorldCoordinates<TCoordinate>) -> Option<WorldCoordinates<TCoordinate>> { None }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WorldBoundary {
+    Solid,
+    Error { width_cells: u16, height_cells: u16 },
+    Wrap { width_cells: u16, height_cells: u16 }
+}
+
+impl WorldBoundary {
+    pub fn step_x<TCoordinate: WorldCoordinate>(&self, x: TCoordinate, step: I8F0) -> Result<TCoordinate> {
+        self.step(x, step, self.extent_cells().0)
+    }
+
+    pub fn step_y<TCoordinate: WorldCoordinate>(&self, y: TCoordinate, step: I8F0) -> Result<TCoordinate> {
+        self.step(y, step, self.extent_cells().1)
+    }
+
+    pub fn constrain_x<TCoordinate: WorldCoordinate>(&self, x: TCoordinate::Movement) -> Result<TCoordinate> {
+        self.constrain(x, self.extent_cells().0)
+    }
+
+    pub fn constrain_y<TCoordinate: WorldCoordinate>(&self, y: TCoordinate::Movement) -> Result<TCoordinate> {
+        self.constrain(y, self.extent_cells().1)
+    }
+
+    fn extent_cells(&self) -> (u16, u16) {
+        match *self {
+            WorldBoundary::Solid => (u16::MAX, u16::MAX),
+            WorldBoundary::Error { width_cells, height_cells } => (width_cells, height_cells),
+            WorldBoundary::Wrap { width_cells, height_cells } => (width_cells, height_cells)
+        }
+    }
+
+    fn step<TCoordinate: WorldCoordinate>(&self, coordinate: TCoordinate, step: I8F0, extent_cells: u16) -> Result<TCoordinate> {
+        let is_forwards = step > 0;
+        let stepped = if is_forwards {
+            coordinate.checked_add(TCoordinate::ONE)
+        } else {
+            coordinate.checked_sub(TCoordinate::ONE)
+        };
+
+        match self {
+            WorldBoundary::Solid => Ok(stepped.unwrap_or(coordinate)),
+
+            WorldBoundary::Error { .. } => stepped
+                .filter(|stepped| stepped.cell_int() < extent_cells)
+                .ok_or(Error::RaycastingFellOffTheWorld),
+
+            WorldBoundary::Wrap { .. } => Ok(match stepped {
+                Some(stepped) if stepped.cell_int() < extent_cells => stepped,
+                _ if is_forwards => coordinate.frac(),
+                _ => coordinate.frac() | TCoordinate::from_cell_int(extent_cells.saturating_sub(1))
+            })
+        }
+    }
+
+    fn constrain<TCoordinate: WorldCoordinate>(&self, coordinate: TCoordinate::Movement, extent_cells: u16) -> Result<TCoordinate> {
+        match self {
+            WorldBoundary::Solid => Ok(coordinate.saturating_to_fixed()),
+
+            WorldBoundary::Error { .. } => coordinate
+                .checked_to_fixed()
+                .filter(|constrained: &TCoordinate| constrained.cell_int() < extent_cells)
+                .ok_or(Error::RaycastingFellOffTheWorld),
+
+            WorldBoundary::Wrap { .. } => Ok(coordinate
+                .rem_euclid(TCoordinate::Movement::saturating_from_num(extent_cells))
+                .saturating_to_fixed())
+        }
+    }
 }
 
 pub trait WorldRendering {
@@ -14,30 +93,115 @@ pub trait WorldRendering {
     fn sky_for_column<'c>(&'c self, cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::SkyRenderer<'c>;
     fn wall_for_column<'c>(&'c self, cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::WallRenderer<'c>;
     fn ground_for_column<'c>(&'c self, cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::GroundRenderer<'c>;
+
+    fn is_wall_bound(&self, _cell: CellTag) -> bool { true }
 }
 
-pub struct CellProbe {
-    at: WorldCoordinates
+pub trait WorldDecals {
+    fn decal_at(&self, cell_x: u16, cell_y: u16, face: CellFace) -> Option<Decal<'_>>;
 }
 
-impl CellProbe {
-    pub const fn new(at: WorldCoordinates) -> Self {
+pub struct CellProbe<TCoordinate: WorldCoordinate = WorldAbsoluteCoordinate> {
+    at: WorldCoordinates<TCoordinate>
+}
+
+impl<TCoordinate: WorldCoordinate> CellProbe<TCoordinate> {
+    pub const fn new(at: WorldCoordinates<TCoordinate>) -> Self {
         Self { at }
     }
 
-    pub fn at(&self) -> WorldCoordinates { self.at }
+    pub fn at(&self) -> WorldCoordinates<TCoordinate> { self.at }
+}
+
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CellFlags(u8);
+
+impl CellFlags {
+    pub const NONE: Self = Self(0);
+    pub const SECRET: Self = Self(1 << 0);
+    pub const DAMAGING: Self = Self(1 << 1);
+    const DOOR: Self = Self(1 << 7);
+
+    pub const fn from_bits(bits: u8) -> Self { Self(bits & !Self::DOOR.0) }
+
+    pub const fn bits(&self) -> u8 { self.0 }
+
+    pub const fn union(self, other: Self) -> Self { Self(self.0 | other.0) }
+
+    pub const fn contains(&self, other: Self) -> bool { self.0 & other.0 == other.0 }
 }
 
+/*
+    Packed as (from the least significant bits): wall id (16), floor texture id (8), ceiling texture id (8),
+    light level (8), door id (8) and flags (8), so that the tag is still cheap to copy through the raycaster.
+*/
 #[repr(transparent)]
-#[derive(Copy, Clone, Debug)]
-pub struct CellTag(u8);
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CellTag(u64);
 
 impl CellTag {
-    pub const fn from_world_cell_id(cell_id: u8) -> Self {
-        Self(cell_id)
+    const WALL_ID_SHIFT: u32 = 0;
+    const FLOOR_TEXTURE_ID_SHIFT: u32 = 16;
+    const CEILING_TEXTURE_ID_SHIFT: u32 = 24;
+    const LIGHT_LEVEL_SHIFT: u32 = 32;
+    const DOOR_ID_SHIFT: u32 = 40;
+    const FLAGS_SHIFT: u32 = 48;
+
+    pub const fn from_wall_id(wall_id: u16) -> Self {
+        Self((wall_id as u64) << Self::WALL_ID_SHIFT)
+    }
+
+    pub const fn with_wall_id(self, wall_id: u16) -> Self {
+        Self((self.0 & !(0xffff << Self::WALL_ID_SHIFT)) | ((wall_id as u64) << Self::WALL_ID_SHIFT))
+    }
+
+    pub const fn with_floor_texture_id(self, texture_id: u8) -> Self { self.with_byte(Self::FLOOR_TEXTURE_ID_SHIFT, texture_id) }
+
+    pub const fn with_ceiling_texture_id(self, texture_id: u8) -> Self { self.with_byte(Self::CEILING_TEXTURE_ID_SHIFT, texture_id) }
+
+    pub const fn with_light_level(self, light_level: u8) -> Self { self.with_byte(Self::LIGHT_LEVEL_SHIFT, light_level) }
+
+    pub const fn with_door_id(self, door_id: u8) -> Self {
+        self
+            .with_byte(Self::DOOR_ID_SHIFT, door_id)
+            .with_byte(Self::FLAGS_SHIFT, self.flags().union(CellFlags::DOOR).bits())
+    }
+
+    pub const fn with_flags(self, flags: CellFlags) -> Self {
+        let door = self.byte(Self::FLAGS_SHIFT) & CellFlags::DOOR.bits();
+        self.with_byte(Self::FLAGS_SHIFT, flags.bits() | door)
+    }
+
+    pub const fn wall_id(&self) -> u16 { (self.0 >> Self::WALL_ID_SHIFT) as u16 }
+
+    pub const fn floor_texture_id(&self) -> u8 { self.byte(Self::FLOOR_TEXTURE_ID_SHIFT) }
+
+    pub const fn ceiling_texture_id(&self) -> u8 { self.byte(Self::CEILING_TEXTURE_ID_SHIFT) }
+
+    pub const fn light_level(&self) -> u8 { self.byte(Self::LIGHT_LEVEL_SHIFT) }
+
+    pub const fn door_id(&self) -> Option<u8> {
+        if self.flags().contains(CellFlags::DOOR) {
+            Some(self.byte(Self::DOOR_ID_SHIFT))
+        } else {
+            None
+        }
     }
 
-    pub const fn world_cell_id(&self) -> u8 { self.0 }
+    pub const fn flags(&self) -> CellFlags { CellFlags(self.byte(Self::FLAGS_SHIFT)) }
+
+    pub const fn is_secret(&self) -> bool { self.flags().contains(CellFlags::SECRET) }
+
+    pub const fn is_damaging(&self) -> bool { self.flags().contains(CellFlags::DAMAGING) }
+
+    pub const fn to_bits(&self) -> u64 { self.0 }
+
+    const fn byte(&self, shift: u32) -> u8 { (self.0 >> shift) as u8 }
+
+    const fn with_byte(self, shift: u32, value: u8) -> Self {
+        Self((self.0 & !(0xff << shift)) | ((value as u64) << shift))
+    }
 }
 
 pub enum CellProbeResult {
@@ -46,3 +210,11 @@ pub enum CellProbeResult {
     Transparent(CellTag),
     PossiblyTransparent(CellTag)
 }
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CellFace {
+    North,
+    East,
+    South,
+    West
+}
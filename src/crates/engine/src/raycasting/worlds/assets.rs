@@ -1,4 +1,4 @@
-use crate::{Colour, WellKnownColours};
+use crate::{include_texture, Colour, WellKnownColours};
 use crate::raycasting::*;
 
 pub struct Palette;
@@ -22,41 +22,69 @@ impl WellKnownColours for Palette {
     const TRANSPARENT: Colour = Palette::TRANSPARENT;
 }
 
-const NO_TRANSPARENCY: u16 = 0xff00;
+static TEXTURE_TABLE: [Option<TextureDescriptor<'static>>; 3] = [
+    None,
+    Some(include_texture! {
+        pixels: "src/raycasting/worlds/brick1-64x64.raw";
+        palette: "src/raycasting/worlds/palette.rgb";
+        size: 64 x 64 pixels;
+        wrapping: Stretched;
+        mip_levels: 4;
+    }),
+    Some(include_texture! {
+        pixels: "src/raycasting/worlds/stone1-64x32.raw";
+        palette: "src/raycasting/worlds/palette.rgb";
+        size: 64 x 32 pixels;
+        wrapping: Repeated;
+        mip_levels: 4;
+    })
+];
 
-type OpaqueStretchedStaticTexture<'t, const W: u8, const H: u8> = StretchedStaticTexture<'t, W, H, NO_TRANSPARENCY>;
-type OpaqueRepeatedStaticTexture<'t, const W: u8, const H: u8> = RepeatedStaticTexture<'t, W, H, NO_TRANSPARENCY>;
+pub const TEXTURES: TextureTable<'static> = TextureTable::new(&TEXTURE_TABLE);
 
-type Brick1Texture<'t> = OpaqueStretchedStaticTexture<'t, 64, 64>;
-type Brick1TextureColumnRenderer<'c> = TextureMappedColumnRenderer<'c, Brick1Texture<'c>>;
+pub const TEXTURES_MEMORY_BYTES: usize = TEXTURES.memory_bytes();
 
-type Stone1Texture<'t> = OpaqueRepeatedStaticTexture<'t, 64, 32>;
-type Stone1TextureColumnRenderer<'c> = TextureMappedColumnRenderer<'c, Stone1Texture<'c>>;
+const PLAQUE: TextureDescriptor<'static> = include_texture! {
+    pixels: "src/raycasting/worlds/plaque1-32x16.raw";
+    palette: "src/raycasting/worlds/palette.rgb";
+    size: 32 x 16 pixels;
+    wrapping: Stretched;
+    mip_levels: 1;
+    transparent_colour: 0;
+};
 
-pub struct Textures<'c> {
-    brick1: Brick1Texture<'c>,
-    stone1: Stone1Texture<'c>
-}
+const SPLAT: TextureDescriptor<'static> = include_texture! {
+    pixels: "src/raycasting/worlds/splat1-16x16.raw";
+    palette: "src/raycasting/worlds/palette.rgb";
+    size: 16 x 16 pixels;
+    wrapping: Stretched;
+    mip_levels: 1;
+    transparent_colour: 0;
+};
 
-pub enum TextureRenderer<'c> {
-    Unknown(SolidColourColumnRenderer<'c>),
-    Brick1(Brick1TextureColumnRenderer<'c>),
-    Stone1(Stone1TextureColumnRenderer<'c>)
-}
+const PLAQUE_PLACEMENT: DecalPlacement = DecalPlacement::new(
+    TextureCoordinate::lit("0.25"),
+    TextureCoordinate::lit("0.3125"),
+    TextureCoordinate::lit("0.5"),
+    TextureCoordinate::lit("0.25"));
 
-impl<'c> Textures<'c> {
-    pub const fn new() -> Self {
-        Self {
-            brick1: Brick1Texture::new(include_bytes!("brick1-64x64.raw")),
-            stone1: Stone1Texture::new(include_bytes!("stone1-64x32.raw"))
-        }
-    }
+const SPLAT_PLACEMENT: DecalPlacement = DecalPlacement::new(
+    TextureCoordinate::lit("0.375"),
+    TextureCoordinate::lit("0.5"),
+    TextureCoordinate::lit("0.375"),
+    TextureCoordinate::lit("0.375"));
 
-    pub fn new_renderer_for(&'c self, cell_tag: Option<CellTag>, column: &'c mut RenderingColumn) -> TextureRenderer<'c> {
-        match cell_tag.map(|x| x.world_cell_id()).unwrap_or(255) {
-            1 => TextureRenderer::Brick1(Brick1TextureColumnRenderer::new(&self.brick1, column)),
-            2 => TextureRenderer::Stone1(Stone1TextureColumnRenderer::new(&self.stone1, column)),
-            _ => TextureRenderer::Unknown(SolidColourColumnRenderer::new(Palette::BLACK, column))
-        }
-    }
-}
+const PLAQUE_ID: u8 = 0;
+const SPLAT_ID: u8 = 1;
+
+static DECAL_TEXTURES: [TextureDescriptor<'static>; 2] = [PLAQUE, SPLAT];
+
+static DECAL_TABLE: [PlacedDecal; 3] = [
+    PlacedDecal::new(3, 2, CellFace::East, SPLAT_ID, SPLAT_PLACEMENT),
+    PlacedDecal::new(2, 6, CellFace::North, PLAQUE_ID, PLAQUE_PLACEMENT),
+    PlacedDecal::new(10, 9, CellFace::North, PLAQUE_ID, PLAQUE_PLACEMENT)
+];
+
+pub const DECALS: DecalTable<'static> = DecalTable::new(&DECAL_TEXTURES, &DECAL_TABLE);
+
+pub const DECALS_MEMORY_BYTES: usize = DECALS.memory_bytes();
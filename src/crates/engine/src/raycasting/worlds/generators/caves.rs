@@ -0,0 +1,86 @@
+use crate::raycasting::*;
+use crate::raycasting::worlds::{GridWorld, Prng};
+use super::{is_open, retain_reachable_from, spawn_facing_open, wall_for, GridGenerator, OPEN};
+
+/*
+    Cellular automaton caves: random noise smoothed by repeatedly turning each cell into a wall when most of
+    its neighbours are walls.  The result is organic but not necessarily connected, so anything that cannot be
+    reached from the spawn is filled in afterwards.
+*/
+pub struct Caves {
+    wall_percent: u8,
+    iterations: u8
+}
+
+impl Caves {
+    const WALL_NEIGHBOUR_THRESHOLD: u8 = 5;
+
+    pub const fn new() -> Self {
+        Self {
+            wall_percent: 45,
+            iterations: 4
+        }
+    }
+
+    pub const fn with_wall_percent(self, wall_percent: u8) -> Self {
+        Self { wall_percent, ..self }
+    }
+
+    pub const fn with_iterations(self, iterations: u8) -> Self {
+        Self { iterations, ..self }
+    }
+
+    fn is_border<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>, cell_x: u16, cell_y: u16) -> bool {
+        cell_x == 0 || cell_y == 0 || cell_x + 1 >= world.width_cells() || cell_y + 1 >= world.height_cells()
+    }
+
+    fn walls_around<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>, cell_x: u16, cell_y: u16) -> u8 {
+        let mut walls = 0;
+        for y in cell_y.saturating_sub(1)..=cell_y + 1 {
+            for x in cell_x.saturating_sub(1)..=cell_x + 1 {
+                if !is_open(world, x, y) {
+                    walls += 1;
+                }
+            }
+        }
+
+        walls
+    }
+}
+
+impl Default for Caves {
+    fn default() -> Self { Self::new() }
+}
+
+impl GridGenerator for Caves {
+    fn generate<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(&self, world: &mut GridWorld<TCells>, seed: u64) {
+        let mut prng = Prng::new(seed);
+        for cell_y in 0..world.height_cells() {
+            for cell_x in 0..world.width_cells() {
+                let is_wall = Self::is_border(world, cell_x, cell_y) || prng.chance(u32::from(self.wall_percent), 100);
+                world.set_cell(cell_x, cell_y, if is_wall { wall_for(seed, cell_x, cell_y) } else { OPEN });
+            }
+        }
+
+        // Smoothing in place rather than double-buffering; the bias this introduces is invisible in caves
+        for _ in 0..self.iterations {
+            for cell_y in 1..world.height_cells().saturating_sub(1) {
+                for cell_x in 1..world.width_cells().saturating_sub(1) {
+                    let is_wall = Self::walls_around(world, cell_x, cell_y) >= Self::WALL_NEIGHBOUR_THRESHOLD;
+                    world.set_cell(cell_x, cell_y, if is_wall { wall_for(seed, cell_x, cell_y) } else { OPEN });
+                }
+            }
+        }
+
+        let (centre_x, centre_y) = (world.width_cells() / 2, world.height_cells() / 2);
+        let nearest_open = (0..world.height_cells())
+            .flat_map(|cell_y| (0..world.width_cells()).map(move |cell_x| (cell_x, cell_y)))
+            .filter(|&(cell_x, cell_y)| is_open(world, cell_x, cell_y))
+            .min_by_key(|&(cell_x, cell_y)| cell_x.abs_diff(centre_x) + cell_y.abs_diff(centre_y));
+
+        let (spawn_x, spawn_y) = nearest_open.unwrap_or((centre_x, centre_y));
+        world.set_cell(spawn_x, spawn_y, OPEN);
+        retain_reachable_from(world, seed, spawn_x, spawn_y);
+        spawn_facing_open(world, spawn_x, spawn_y);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::raycasting::*;
+use crate::raycasting::worlds::{GridWorld, Prng};
+use super::{fill_with_walls, is_open, spawn_facing_open, GridGenerator, OPEN};
+
+#[derive(Copy, Clone)]
+struct Room {
+    left: u16,
+    top: u16,
+    width: u16,
+    height: u16
+}
+
+impl Room {
+    const NONE: Self = Self { left: 0, top: 0, width: 0, height: 0 };
+
+    const fn centre(&self) -> (u16, u16) { (self.left + self.width / 2, self.top + self.height / 2) }
+
+    const fn contains(&self, cell_x: u16, cell_y: u16) -> bool {
+        cell_x >= self.left && cell_x < self.left + self.width && cell_y >= self.top && cell_y < self.top + self.height
+    }
+
+    // Rooms keep at least one wall between them so that they remain distinct spaces joined only by corridors
+    const fn is_clear_of(&self, other: &Room) -> bool {
+        self.left > other.left + other.width ||
+        other.left > self.left + self.width ||
+        self.top > other.top + other.height ||
+        other.top > self.top + self.height
+    }
+}
+
+/*
+    Rectangular rooms scattered without overlap, each joined to the one placed before it by an L-shaped
+    corridor; every room is therefore reachable from the first, which is where the camera spawns.
+*/
+pub struct Dungeon {
+    max_rooms: u8,
+    min_room_cells: u16,
+    max_room_cells: u16,
+    has_doors: bool
+}
+
+impl Dungeon {
+    const MAX_ROOMS: usize = 32;
+    const PLACEMENT_ATTEMPTS_PER_ROOM: u8 = 4;
+
+    pub const fn new() -> Self {
+        Self {
+            max_rooms: 12,
+            min_room_cells: 3,
+            max_room_cells: 7,
+            has_doors: false
+        }
+    }
+
+    pub const fn with_max_rooms(self, max_rooms: u8) -> Self {
+        Self { max_rooms, ..self }
+    }
+
+    pub const fn with_room_size(self, min_room_cells: u16, max_room_cells: u16) -> Self {
+        Self { min_room_cells, max_room_cells, ..self }
+    }
+
+    pub const fn with_doors(self, has_doors: bool) -> Self {
+        Self { has_doors, ..self }
+    }
+
+    fn place_room<TCells: AsRef<[CellTag]>>(&self, world: &GridWorld<TCells>, prng: &mut Prng) -> Option<Room> {
+        let max_width = self.max_room_cells.min(world.width_cells().checked_sub(2)?);
+        let max_height = self.max_room_cells.min(world.height_cells().checked_sub(2)?);
+        if max_width < self.min_room_cells || max_height < self.min_room_cells {
+            return None;
+        }
+
+        let width = prng.next_in_range(self.min_room_cells, max_width);
+        let height = prng.next_in_range(self.min_room_cells, max_height);
+        Some(Room {
+            left: prng.next_in_range(1, world.width_cells() - width - 1),
+            top: prng.next_in_range(1, world.height_cells() - height - 1),
+            width,
+            height
+        })
+    }
+
+    fn carve_corridor<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(world: &mut GridWorld<TCells>, from: (u16, u16), to: (u16, u16), is_horizontal_first: bool) {
+        let corner = if is_horizontal_first { (to.0, from.1) } else { (from.0, to.1) };
+        for (start, end) in [(from, corner), (corner, to)] {
+            for cell_x in start.0.min(end.0)..=start.0.max(end.0) {
+                for cell_y in start.1.min(end.1)..=start.1.max(end.1) {
+                    world.set_cell(cell_x, cell_y, OPEN);
+                }
+            }
+        }
+    }
+
+    fn place_doors<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(world: &mut GridWorld<TCells>, rooms: &[Room]) {
+        let is_room = |cell_x: u16, cell_y: u16| rooms.iter().any(|room| room.contains(cell_x, cell_y));
+        let mut door_id = 0_u8;
+        for cell_y in 1..world.height_cells().saturating_sub(1) {
+            for cell_x in 1..world.width_cells().saturating_sub(1) {
+                if !is_open(world, cell_x, cell_y) || is_room(cell_x, cell_y) {
+                    continue;
+                }
+
+                // A corridor that runs alongside a room would otherwise get a door in every cell
+                let is_wall = |cell_x, cell_y| !is_open(world, cell_x, cell_y);
+                let is_door = |cell_x, cell_y| world.cell_at(cell_x, cell_y).is_some_and(|cell| cell.door_id().is_some());
+                let is_doorway_x = is_wall(cell_x, cell_y - 1) && is_wall(cell_x, cell_y + 1) && !is_door(cell_x - 1, cell_y) && (is_room(cell_x - 1, cell_y) || is_room(cell_x + 1, cell_y));
+                let is_doorway_y = is_wall(cell_x - 1, cell_y) && is_wall(cell_x + 1, cell_y) && !is_door(cell_x, cell_y - 1) && (is_room(cell_x, cell_y - 1) || is_room(cell_x, cell_y + 1));
+                if is_doorway_x || is_doorway_y {
+                    world.set_cell(cell_x, cell_y, OPEN.with_door_id(door_id));
+                    door_id = door_id.wrapping_add(1);
+                }
+            }
+        }
+    }
+}
+
+impl Default for Dungeon {
+    fn default() -> Self { Self::new() }
+}
+
+impl GridGenerator for Dungeon {
+    fn generate<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(&self, world: &mut GridWorld<TCells>, seed: u64) {
+        fill_with_walls(world, seed);
+
+        let mut prng = Prng::new(seed);
+        let mut rooms = [Room::NONE; Self::MAX_ROOMS];
+        let mut room_count = 0;
+        let max_rooms = usize::from(self.max_rooms).min(Self::MAX_ROOMS);
+        for _ in 0..u16::from(self.max_rooms) * u16::from(Self::PLACEMENT_ATTEMPTS_PER_ROOM) {
+            if room_count >= max_rooms {
+                break;
+            }
+
+            let Some(room) = self.place_room(world, &mut prng) else { break; };
+            if rooms[..room_count].iter().any(|other| !room.is_clear_of(other)) {
+                continue;
+            }
+
+            for cell_y in room.top..room.top + room.height {
+                for cell_x in room.left..room.left + room.width {
+                    world.set_cell(cell_x, cell_y, OPEN);
+                }
+            }
+
+            if let Some(previous) = room_count.checked_sub(1).map(|previous| rooms[previous]) {
+                Self::carve_corridor(world, previous.centre(), room.centre(), prng.chance(1, 2));
+            }
+
+            rooms[room_count] = room;
+            room_count += 1;
+        }
+
+        let (spawn_x, spawn_y) = match room_count {
+            0 => {
+                // Too small for even a single room, so there is just somewhere to stand
+                let centre = (world.width_cells() / 2, world.height_cells() / 2);
+                world.set_cell(centre.0, centre.1, OPEN);
+                centre
+            },
+            _ => rooms[0].centre()
+        };
+
+        if self.has_doors {
+            Self::place_doors(world, &rooms[..room_count]);
+        }
+
+        spawn_facing_open(world, spawn_x, spawn_y);
+    }
+}
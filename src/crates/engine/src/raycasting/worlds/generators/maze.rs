@@ -0,0 +1,69 @@
+use crate::raycasting::*;
+use crate::raycasting::worlds::{GridWorld, Prng};
+use super::{fill_with_walls, spawn_facing_open, GridGenerator, OPEN};
+
+/*
+    A perfect maze by recursive backtracking.  Rather than needing a stack, each visited cell remembers the
+    direction back to the cell it was carved from in its (temporary) wall id, and is opened up when the
+    backtracker leaves it for the last time.  Cells are at odd coordinates with the walls between them.
+*/
+pub struct Maze;
+
+impl Maze {
+    const STEPS: [(i32, i32); 4] = [(0, -2), (2, 0), (0, 2), (-2, 0)];
+    const CARVED_FROM_BASE: u16 = 0xfff0;
+    const CARVED_ROOT: u16 = Self::CARVED_FROM_BASE + Self::STEPS.len() as u16;
+
+    fn neighbour<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>, cell_x: u16, cell_y: u16, step: usize) -> Option<(u16, u16)> {
+        let (dx, dy) = Self::STEPS[step];
+        let (x, y) = (i32::from(cell_x) + dx, i32::from(cell_y) + dy);
+        if x >= 1 && y >= 1 && x < i32::from(world.width_cells()) - 1 && y < i32::from(world.height_cells()) - 1 {
+            Some((x as u16, y as u16))
+        } else {
+            None
+        }
+    }
+
+    fn is_unvisited<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>, cell: Option<(u16, u16)>) -> bool {
+        cell
+            .and_then(|(x, y)| world.cell_at(x, y))
+            .is_some_and(|cell| cell.wall_id() != 0 && cell.wall_id() < Self::CARVED_FROM_BASE)
+    }
+}
+
+impl GridGenerator for Maze {
+    fn generate<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(&self, world: &mut GridWorld<TCells>, seed: u64) {
+        fill_with_walls(world, seed);
+        if world.width_cells() < 3 || world.height_cells() < 3 {
+            return;
+        }
+
+        let mut prng = Prng::new(seed);
+        let (mut cell_x, mut cell_y) = (1, 1);
+        world.set_cell(cell_x, cell_y, CellTag::from_wall_id(Self::CARVED_ROOT));
+        loop {
+            let unvisited = (0..Self::STEPS.len()).filter(|&step| Self::is_unvisited(world, Self::neighbour(world, cell_x, cell_y, step)));
+            let count = unvisited.clone().count() as u32;
+            if let Some(step) = unvisited.clone().nth(prng.next_below(count.max(1)) as usize).filter(|_| count > 0) {
+                let (next_x, next_y) = Self::neighbour(world, cell_x, cell_y, step).unwrap_or((cell_x, cell_y));
+                let back = (step + Self::STEPS.len() / 2) % Self::STEPS.len();
+                world.set_cell((cell_x + next_x) / 2, (cell_y + next_y) / 2, OPEN);
+                world.set_cell(next_x, next_y, CellTag::from_wall_id(Self::CARVED_FROM_BASE + back as u16));
+                (cell_x, cell_y) = (next_x, next_y);
+                continue;
+            }
+
+            let carved_from = world.cell_at(cell_x, cell_y).map_or(Self::CARVED_ROOT, |cell| cell.wall_id());
+            world.set_cell(cell_x, cell_y, OPEN);
+            if carved_from == Self::CARVED_ROOT {
+                break;
+            }
+
+            let (dx, dy) = Self::STEPS[usize::from(carved_from - Self::CARVED_FROM_BASE)];
+            cell_x = (i32::from(cell_x) + dx) as u16;
+            cell_y = (i32::from(cell_y) + dy) as u16;
+        }
+
+        spawn_facing_open(world, 1, 1);
+    }
+}
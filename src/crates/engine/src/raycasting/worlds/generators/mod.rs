@@ -0,0 +1,99 @@
+use crate::raycasting::*;
+use crate::raycasting::worlds::{hash, GridWorld};
+
+mod caves;
+pub use caves::*;
+
+mod dungeon;
+pub use dungeon::*;
+
+mod maze;
+pub use maze::*;
+
+pub trait GridGenerator {
+    fn generate<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(&self, world: &mut GridWorld<TCells>, seed: u64);
+}
+
+const OPEN: CellTag = CellTag::from_wall_id(0);
+const BRICK_WALL_ID: u16 = 1;
+const STONE_WALL_ID: u16 = 2;
+
+fn is_open(world: &GridWorld<impl AsRef<[CellTag]>>, cell_x: u16, cell_y: u16) -> bool {
+    world.cell_at(cell_x, cell_y).is_some_and(|cell| cell.wall_id() == 0)
+}
+
+fn wall_for(seed: u64, cell_x: u16, cell_y: u16) -> CellTag {
+    // Walls are grouped into 8x8 blocks of the same texture so that variety reads as distinct areas rather than noise
+    if hash(seed, i32::from(cell_x / 8), i32::from(cell_y / 8)) & 0x3 == 0 {
+        CellTag::from_wall_id(STONE_WALL_ID)
+    } else {
+        CellTag::from_wall_id(BRICK_WALL_ID)
+    }
+}
+
+fn fill_with_walls<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(world: &mut GridWorld<TCells>, seed: u64) {
+    for cell_y in 0..world.height_cells() {
+        for cell_x in 0..world.width_cells() {
+            world.set_cell(cell_x, cell_y, wall_for(seed, cell_x, cell_y));
+        }
+    }
+}
+
+fn spawn_facing_open<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(world: &mut GridWorld<TCells>, cell_x: u16, cell_y: u16) {
+    let angle = if is_open(world, cell_x, cell_y + 1) {
+        Angle::FACING_SOUTH
+    } else if is_open(world, cell_x + 1, cell_y) {
+        Angle::FACING_WEST
+    } else if cell_y > 0 && is_open(world, cell_x, cell_y - 1) {
+        Angle::FACING_NORTH
+    } else {
+        Angle::FACING_EAST
+    };
+
+    world.set_spawn(cell_x, cell_y, angle);
+}
+
+/*
+    The reached cells are marked in the world itself, as there is no allocator for a CellBitmap.  Only open
+    cells are reached, so the mark is a wall id that no generator uses; swapping it back to zero afterwards
+    leaves the rest of each cell's tag as it was.
+*/
+struct ReachedCells<'w, TCells: AsRef<[CellTag]> + AsMut<[CellTag]>> {
+    world: &'w mut GridWorld<TCells>
+}
+
+impl<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>> ReachedCells<'_, TCells> {
+    const REACHED_WALL_ID: u16 = u16::MAX;
+}
+
+impl<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>> CellMarks for ReachedCells<'_, TCells> {
+    fn width_cells(&self) -> u16 { self.world.width_cells() }
+
+    fn height_cells(&self) -> u16 { self.world.height_cells() }
+
+    fn is_marked(&self, cell_x: u16, cell_y: u16) -> bool {
+        self.world.cell_at(cell_x, cell_y).is_some_and(|cell| cell.wall_id() == Self::REACHED_WALL_ID)
+    }
+
+    fn mark(&mut self, cell_x: u16, cell_y: u16) {
+        if let Some(cell) = self.world.cell_at(cell_x, cell_y) {
+            self.world.set_cell(cell_x, cell_y, cell.with_wall_id(Self::REACHED_WALL_ID));
+        }
+    }
+}
+
+fn retain_reachable_from<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>>(world: &mut GridWorld<TCells>, seed: u64, cell_x: u16, cell_y: u16) {
+    let mut reached = ReachedCells { world };
+    reached.flood_fill_from(cell_x, cell_y, false, |reached, x, y| is_open(reached.world, x, y));
+
+    let world = reached.world;
+    for y in 0..world.height_cells() {
+        for x in 0..world.width_cells() {
+            match world.cell_at(x, y) {
+                Some(cell) if cell.wall_id() == ReachedCells::<TCells>::REACHED_WALL_ID => world.set_cell(x, y, cell.with_wall_id(0)),
+                Some(cell) if cell.wall_id() == 0 => world.set_cell(x, y, wall_for(seed, x, y)),
+                _ => ()
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::raycasting::*;
+use crate::raycasting::worlds::assets;
+
+pub struct GridWorld<TCells: AsRef<[CellTag]>> {
+    width_cells: u16,
+    height_cells: u16,
+    cells: TCells,
+    spawn_x: u16,
+    spawn_y: u16,
+    spawn_angle: Angle,
+    boundary: WorldBoundary,
+    textures: TextureTable<'static>
+}
+
+impl<TCells: AsRef<[CellTag]>> GridWorld<TCells> {
+    pub fn new(width_cells: u16, height_cells: u16, cells: TCells) -> Self {
+        assert!(cells.as_ref().len() >= usize::from(width_cells) * usize::from(height_cells), "Cells must cover the Width and Height");
+        Self {
+            width_cells,
+            height_cells,
+            cells,
+            spawn_x: 0,
+            spawn_y: 0,
+            spawn_angle: Angle::FACING_SOUTH,
+            boundary: WorldBoundary::Solid,
+            textures: assets::TEXTURES
+        }
+    }
+
+    pub fn with_boundary(self, boundary: WorldBoundary) -> Self {
+        Self { boundary, ..self }
+    }
+
+    pub fn width_cells(&self) -> u16 { self.width_cells }
+
+    pub fn height_cells(&self) -> u16 { self.height_cells }
+
+    pub fn cell_at(&self, cell_x: u16, cell_y: u16) -> Option<CellTag> {
+        self.index_of(cell_x, cell_y).map(|index| self.cells.as_ref()[index])
+    }
+
+    pub fn cells(&self) -> &[CellTag] { self.cells.as_ref() }
+
+    pub fn spawn_cell(&self) -> (u16, u16) { (self.spawn_x, self.spawn_y) }
+
+    pub fn set_spawn(&mut self, cell_x: u16, cell_y: u16, angle: Angle) {
+        self.spawn_x = cell_x;
+        self.spawn_y = cell_y;
+        self.spawn_angle = angle;
+    }
+
+    fn index_of(&self, cell_x: u16, cell_y: u16) -> Option<usize> {
+        if cell_x < self.width_cells && cell_y < self.height_cells {
+            Some(usize::from(cell_y) * usize::from(self.width_cells) + usize::from(cell_x))
+        } else {
+            None
+        }
+    }
+}
+
+impl<TCells: AsRef<[CellTag]> + AsMut<[CellTag]>> GridWorld<TCells> {
+    pub fn set_cell(&mut self, cell_x: u16, cell_y: u16, cell_tag: CellTag) {
+        if let Some(index) = self.index_of(cell_x, cell_y) {
+            self.cells.as_mut()[index] = cell_tag;
+        }
+    }
+
+    pub fn fill(&mut self, cell_tag: CellTag) {
+        self.cells.as_mut().fill(cell_tag);
+    }
+}
+
+impl<TCoordinate: WorldCoordinate, TCells: AsRef<[CellTag]>> World<TCoordinate> for GridWorld<TCells> {
+    fn spawn_at(&self) -> WorldCoordinates<TCoordinate> { WorldCoordinates::<TCoordinate>::from_cell_centre(self.spawn_x, self.spawn_y) }
+
+    fn spawn_angle(&self) -> Angle { self.spawn_angle }
+
+    fn probe_cell(&self, probe: &CellProbe<TCoordinate>) -> CellProbeResult {
+        match self.cell_at(probe.at().cell_x_int(), probe.at().cell_y_int()) {
+            Some(cell_tag) if cell_tag.wall_id() != 0 => CellProbeResult::Opaque(cell_tag),
+            Some(cell_tag) if cell_tag.door_id().is_some() => CellProbeResult::Transparent(cell_tag),
+            Some(_) => CellProbeResult::Empty,
+            None => CellProbeResult::Opaque(CellTag::from_wall_id(0))
+        }
+    }
+
+    fn boundary(&self) -> WorldBoundary { self.boundary }
+}
+
+impl<TCells: AsRef<[CellTag]>> WorldRendering for GridWorld<TCells> {
+    type SkyRenderer<'c> = SolidColourColumnRenderer<'c> where Self: 'c;
+
+    type WallRenderer<'c> = RegisteredTextureColumnRenderer<'c> where Self: 'c;
+
+    type GroundRenderer<'c> = SolidColourColumnRenderer<'c> where Self: 'c;
+
+    fn sky_for_column<'c>(&'c self, _cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::SkyRenderer<'c> {
+        Self::SkyRenderer::new(assets::Palette::SKY_LIGHTEST, column)
+    }
+
+    fn wall_for_column<'c>(&'c self, cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::WallRenderer<'c> {
+        self.textures.wall_renderer_for(cell, None, assets::Palette::BLACK, column)
+    }
+
+    fn ground_for_column<'c>(&'c self, _cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::GroundRenderer<'c> {
+        Self::GroundRenderer::new(assets::Palette::GRASS_LIGHTEST, column)
+    }
+
+    fn is_wall_bound(&self, cell: CellTag) -> bool { self.textures.texture_for(cell).is_some() }
+}
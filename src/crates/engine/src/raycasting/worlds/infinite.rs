@@ -0,0 +1,233 @@
+use core::cell::RefCell;
+
+use crate::raycasting::*;
+use crate::raycasting::worlds::{assets, hash};
+
+pub const CHUNK_SIZE_CELLS: u16 = 16;
+
+pub type ChunkCells = [CellTag; CHUNK_SIZE_CELLS as usize * CHUNK_SIZE_CELLS as usize];
+
+/*
+    How many chunks a frame can need when rays go as far as they can in any direction, wherever the camera is
+    within its chunk; a cache of fewer chunks than this regenerates chunks over and over within each frame.
+*/
+pub const fn cached_chunks_for_reach(max_ray_cell_probes: usize) -> usize {
+    let span_chunks = 2 * max_ray_cell_probes.div_ceil(CHUNK_SIZE_CELLS as usize) + 1;
+    span_chunks * span_chunks
+}
+
+pub trait ChunkGenerator {
+    fn generate_chunk(&self, seed: u64, chunk_x: i32, chunk_y: i32, cells: &mut ChunkCells);
+}
+
+/*
+    Every 4x4 block of cells is a 3x3 room with a pillar in the corner; the walls between rooms have a
+    doorway in the middle that is open or closed depending on a hash of the wall's global position, so
+    neighbouring chunks always agree on their shared walls without either needing to know about the other.
+*/
+pub struct Corridors;
+
+impl Corridors {
+    const BLOCK_SIZE_CELLS: i32 = 4;
+    const DOORWAY_OFFSET_CELLS: i32 = 2;
+    const DOORWAY_OPEN_IN_8: u64 = 5;
+
+    fn cell_at(seed: u64, cell_x: i32, cell_y: i32) -> CellTag {
+        let (block_x, offset_x) = (cell_x.div_euclid(Self::BLOCK_SIZE_CELLS), cell_x.rem_euclid(Self::BLOCK_SIZE_CELLS));
+        let (block_y, offset_y) = (cell_y.div_euclid(Self::BLOCK_SIZE_CELLS), cell_y.rem_euclid(Self::BLOCK_SIZE_CELLS));
+        let wall = CellTag::from_wall_id(if hash(seed, block_x, block_y) & 0x7 == 0 { 2 } else { 1 });
+
+        match (offset_x, offset_y) {
+            (0, 0) => wall,
+            (0, offset_y) | (offset_y, 0) if offset_y != Self::DOORWAY_OFFSET_CELLS => wall,
+            (0, _) | (_, 0) => {
+                if hash(seed ^ 0x5eed, cell_x, cell_y) & 0x7 < Self::DOORWAY_OPEN_IN_8 {
+                    CellTag::from_wall_id(0)
+                } else {
+                    wall
+                }
+            },
+            _ => CellTag::from_wall_id(0)
+        }
+    }
+}
+
+impl ChunkGenerator for Corridors {
+    fn generate_chunk(&self, seed: u64, chunk_x: i32, chunk_y: i32, cells: &mut ChunkCells) {
+        let size = CHUNK_SIZE_CELLS as i32;
+        for (index, cell) in cells.iter_mut().enumerate() {
+            let (offset_x, offset_y) = (index as i32 % size, index as i32 / size);
+            *cell = Self::cell_at(seed, chunk_x * size + offset_x, chunk_y * size + offset_y);
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Chunk {
+    chunk_x: i32,
+    chunk_y: i32,
+    cells: ChunkCells
+}
+
+struct ChunkCache<const CACHED_CHUNKS: usize> {
+    chunks: [Option<Chunk>; CACHED_CHUNKS],
+    next: usize,
+    last_hit: usize
+}
+
+impl<const CACHED_CHUNKS: usize> ChunkCache<CACHED_CHUNKS> {
+    const _ENSURE_AT_LEAST_ONE_CHUNK: () = assert!(CACHED_CHUNKS > 0, "At least one chunk must be cached");
+
+    const fn new() -> Self {
+        let () = Self::_ENSURE_AT_LEAST_ONE_CHUNK;
+        Self {
+            chunks: [const { None }; CACHED_CHUNKS],
+            next: 0,
+            last_hit: 0
+        }
+    }
+
+    fn cell_at<TGenerator: ChunkGenerator>(&mut self, generator: &TGenerator, seed: u64, chunk_x: i32, chunk_y: i32, index: usize) -> CellTag {
+        let is_chunk = |chunk: &Option<Chunk>| matches!(chunk, Some(chunk) if chunk.chunk_x == chunk_x && chunk.chunk_y == chunk_y);
+        if !is_chunk(&self.chunks[self.last_hit]) {
+            self.last_hit = match self.chunks.iter().position(is_chunk) {
+                Some(hit) => hit,
+                None => self.generate(generator, seed, chunk_x, chunk_y)
+            };
+        }
+
+        self.chunks[self.last_hit].as_ref().map_or(CellTag::from_wall_id(0), |chunk| chunk.cells[index])
+    }
+
+    fn generate<TGenerator: ChunkGenerator>(&mut self, generator: &TGenerator, seed: u64, chunk_x: i32, chunk_y: i32) -> usize {
+        let slot = self.next;
+        self.next = (self.next + 1) % CACHED_CHUNKS;
+
+        let chunk = self.chunks[slot].insert(Chunk {
+            chunk_x,
+            chunk_y,
+            cells: [CellTag::from_wall_id(0); CHUNK_SIZE_CELLS as usize * CHUNK_SIZE_CELLS as usize]
+        });
+
+        generator.generate_chunk(seed, chunk_x, chunk_y, &mut chunk.cells);
+        slot
+    }
+}
+
+pub struct InfiniteWorld<TGenerator: ChunkGenerator, const CACHED_CHUNKS: usize> {
+    generator: TGenerator,
+    seed: u64,
+    origin_chunk_x: i32,
+    origin_chunk_y: i32,
+    cache: RefCell<ChunkCache<CACHED_CHUNKS>>,
+    textures: TextureTable<'static>
+}
+
+impl<TGenerator: ChunkGenerator, const CACHED_CHUNKS: usize> InfiniteWorld<TGenerator, CACHED_CHUNKS> {
+    // Rays can travel 64 cells, so the camera is kept far enough from the edge of the local coordinates that they never run out
+    const REBASE_MARGIN_CHUNKS: u32 = 4;
+
+    pub const fn new(generator: TGenerator, seed: u64) -> Self {
+        Self {
+            generator,
+            seed,
+            origin_chunk_x: 0,
+            origin_chunk_y: 0,
+            cache: RefCell::new(ChunkCache::new()),
+            textures: assets::TEXTURES
+        }
+    }
+
+    pub fn seed(&self) -> u64 { self.seed }
+
+    pub fn origin_chunk(&self) -> (i32, i32) { (self.origin_chunk_x, self.origin_chunk_y) }
+
+    pub const fn memory_bytes() -> usize { core::mem::size_of::<ChunkCache<CACHED_CHUNKS>>() }
+
+    fn local_extent_chunks<TCoordinate: WorldCoordinate>() -> u32 {
+        (u32::from(TCoordinate::MAX.cell_int()) + 1) / u32::from(CHUNK_SIZE_CELLS)
+    }
+
+    fn rebased_chunk(local_chunk: u32, extent_chunks: u32) -> Option<i32> {
+        let margin = Self::REBASE_MARGIN_CHUNKS.min(extent_chunks / 2);
+        if local_chunk < margin || local_chunk >= extent_chunks - margin {
+            Some(extent_chunks as i32 / 2 - local_chunk as i32)
+        } else {
+            None
+        }
+    }
+}
+
+impl<TCoordinate, TGenerator, const CACHED_CHUNKS: usize> World<TCoordinate> for InfiniteWorld<TGenerator, CACHED_CHUNKS>
+    where
+        TCoordinate: WorldCoordinate,
+        TGenerator: ChunkGenerator {
+
+    fn spawn_at(&self) -> WorldCoordinates<TCoordinate> {
+        let centre_chunk = (Self::local_extent_chunks::<TCoordinate>() / 2) as u16;
+        let spawn_cell = centre_chunk * CHUNK_SIZE_CELLS + 2;
+        WorldCoordinates::<TCoordinate>::from_cell_centre(spawn_cell, spawn_cell)
+    }
+
+    fn spawn_angle(&self) -> Angle { Angle::FACING_SOUTH }
+
+    fn probe_cell(&self, probe: &CellProbe<TCoordinate>) -> CellProbeResult {
+        let (cell_x, cell_y) = (probe.at().cell_x_int(), probe.at().cell_y_int());
+        let chunk_x = self.origin_chunk_x + i32::from(cell_x / CHUNK_SIZE_CELLS);
+        let chunk_y = self.origin_chunk_y + i32::from(cell_y / CHUNK_SIZE_CELLS);
+        let index = usize::from(cell_y % CHUNK_SIZE_CELLS) * usize::from(CHUNK_SIZE_CELLS) + usize::from(cell_x % CHUNK_SIZE_CELLS);
+
+        let cell_tag = self.cache.borrow_mut().cell_at(&self.generator, self.seed, chunk_x, chunk_y, index);
+        if cell_tag.wall_id() == 0 {
+            CellProbeResult::Empty
+        } else {
+            CellProbeResult::Opaque(cell_tag)
+        }
+    }
+
+    fn rebase(&mut self, position: WorldCoordinates<TCoordinate>) -> Option<WorldCoordinates<TCoordinate>> {
+        let extent_chunks = Self::local_extent_chunks::<TCoordinate>();
+        let local_chunk_x = u32::from(position.cell_x_int() / CHUNK_SIZE_CELLS);
+        let local_chunk_y = u32::from(position.cell_y_int() / CHUNK_SIZE_CELLS);
+
+        let shift_x = Self::rebased_chunk(local_chunk_x, extent_chunks).unwrap_or(0);
+        let shift_y = Self::rebased_chunk(local_chunk_y, extent_chunks).unwrap_or(0);
+        if shift_x == 0 && shift_y == 0 {
+            return None;
+        }
+
+        self.origin_chunk_x -= shift_x;
+        self.origin_chunk_y -= shift_y;
+
+        let shifted = |coordinate: TCoordinate, shift_chunks: i32| {
+            let shift = TCoordinate::from_cell_int(shift_chunks.unsigned_abs() as u16 * CHUNK_SIZE_CELLS);
+            if shift_chunks < 0 { coordinate - shift } else { coordinate + shift }
+        };
+
+        Some(WorldCoordinates::new(
+            shifted(position.x(), shift_x),
+            shifted(position.y(), shift_y)))
+    }
+}
+
+impl<TGenerator: ChunkGenerator, const CACHED_CHUNKS: usize> WorldRendering for InfiniteWorld<TGenerator, CACHED_CHUNKS> {
+    type SkyRenderer<'c> = SolidColourColumnRenderer<'c> where Self: 'c;
+
+    type WallRenderer<'c> = RegisteredTextureColumnRenderer<'c> where Self: 'c;
+
+    type GroundRenderer<'c> = SolidColourColumnRenderer<'c> where Self: 'c;
+
+    fn sky_for_column<'c>(&'c self, _cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::SkyRenderer<'c> {
+        Self::SkyRenderer::new(assets::Palette::SKY_LIGHTEST, column)
+    }
+
+    fn wall_for_column<'c>(&'c self, cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::WallRenderer<'c> {
+        self.textures.wall_renderer_for(cell, None, assets::Palette::BLACK, column)
+    }
+
+    fn ground_for_column<'c>(&'c self, _cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::GroundRenderer<'c> {
+        Self::GroundRenderer::new(assets::Palette::GRASS_LIGHTEST, column)
+    }
+
+    fn is_wall_bound(&self, cell: CellTag) -> bool { self.textures.texture_for(cell).is_some() }
+}
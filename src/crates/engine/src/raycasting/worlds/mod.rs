@@ -1,4 +1,16 @@
 pub mod assets;
 
+mod generators;
+pub use generators::*;
+
+mod grid;
+pub use grid::*;
+
+mod infinite;
+pub use infinite::*;
+
+mod random;
+pub use random::*;
+
 mod world1;
 pub use world1::*;
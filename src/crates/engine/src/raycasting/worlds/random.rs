@@ -0,0 +1,45 @@
+/*
+    SplitMix64 throughout; it is tiny, has no state beyond a single word and is more than good enough for
+    laying out walls.  Everything derived from a seed must be deterministic so that worlds can be shared.
+*/
+pub struct Prng {
+    state: u64
+}
+
+impl Prng {
+    const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(Self::GOLDEN_GAMMA);
+        mix(self.state)
+    }
+
+    pub fn next_below(&mut self, bound: u32) -> u32 {
+        (((self.next_u64() >> 32) * u64::from(bound)) >> 32) as u32
+    }
+
+    pub fn next_in_range(&mut self, min: u16, max_inclusive: u16) -> u16 {
+        min + self.next_below(u32::from(max_inclusive - min) + 1) as u16
+    }
+
+    pub fn chance(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.next_below(denominator) < numerator
+    }
+}
+
+pub fn hash(seed: u64, x: i32, y: i32) -> u64 {
+    mix(seed
+        .wrapping_add(((x as u32 as u64) << 32) | y as u32 as u64)
+        .wrapping_mul(Prng::GOLDEN_GAMMA))
+}
+
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
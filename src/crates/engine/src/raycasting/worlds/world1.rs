@@ -2,33 +2,38 @@ use crate::raycasting::*;
 use crate::raycasting::worlds::assets;
 
 pub struct World1 {
-    textures: assets::Textures<'static>
+    textures: TextureTable<'static>,
+    decals: DecalTable<'static>
 }
 
 impl World1 {
+    pub const WIDTH_CELLS: u16 = CELLS[0].len() as u16;
+    pub const HEIGHT_CELLS: u16 = CELLS.len() as u16;
+
     pub const fn new() -> Self {
         Self {
-            textures: assets::Textures::new()
+            textures: assets::TEXTURES,
+            decals: assets::DECALS
         }
     }
 }
 
-impl World for World1 {
-    fn spawn_at(&self) -> WorldCoordinates { WorldCoordinates::from_cell_centre(2, 2) }
+impl<TCoordinate: WorldCoordinate> World<TCoordinate> for World1 {
+    fn spawn_at(&self) -> WorldCoordinates<TCoordinate> { WorldCoordinates::<TCoordinate>::from_cell_centre(2, 2) }
 
-    fn spawn_angle(&self) -> Angle { WorldCoordinates::FACING_SOUTH }
+    fn spawn_angle(&self) -> Angle { Angle::FACING_SOUTH }
 
-    fn probe_cell(&self, probe: &CellProbe) -> CellProbeResult {
+    fn probe_cell(&self, probe: &CellProbe<TCoordinate>) -> CellProbeResult {
         let cell_x = probe.at().cell_x_int() as usize;
         let cell_y = probe.at().cell_y_int() as usize;
         if let Some(cell_type) = CELLS.get(cell_y).and_then(|row| row.get(cell_x)) {
             if *cell_type == 0 {
                 CellProbeResult::Empty
             } else {
-                CellProbeResult::Opaque(CellTag::from_world_cell_id(*cell_type))
+                CellProbeResult::Opaque(CellTag::from_wall_id(u16::from(*cell_type)))
             }
         } else {
-            CellProbeResult::Opaque(CellTag::from_world_cell_id(0))
+            CellProbeResult::Opaque(CellTag::from_wall_id(0))
         }
     }
 }
@@ -36,7 +41,7 @@ impl World for World1 {
 impl WorldRendering for World1 {
     type SkyRenderer<'c> = SolidColourColumnRenderer<'c>;
 
-    type WallRenderer<'c> = assets::TextureRenderer<'c>;
+    type WallRenderer<'c> = RegisteredTextureColumnRenderer<'c>;
 
     type GroundRenderer<'c> = SolidColourColumnRenderer<'c>;
 
@@ -45,22 +50,19 @@ impl WorldRendering for World1 {
     }
 
     fn wall_for_column<'c>(&'c self, cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::WallRenderer<'c> {
-        self.textures.new_renderer_for(cell, column)
+        let decal = column
+            .raycasting()
+            .as_ref()
+            .and_then(|intersection| self.decals.decal_at(intersection.cell_x(), intersection.cell_y(), intersection.face()));
+
+        self.textures.wall_renderer_for(cell, decal, assets::Palette::BLACK, column)
     }
 
     fn ground_for_column<'c>(&self, _cell: Option<CellTag>, column: &'c mut RenderingColumn) -> Self::GroundRenderer<'c> {
         Self::GroundRenderer::new(assets::Palette::GRASS_LIGHTEST, column)
     }
-}
 
-impl<'c> ColumnRendering for assets::TextureRenderer<'c> {
-    fn render_column_onto<TCanvas: crate::Canvas>(&mut self, canvas: &mut TCanvas) -> crate::Result<()> {
-        match self {
-            assets::TextureRenderer::Unknown(renderer) => renderer.render_column_onto(canvas),
-            assets::TextureRenderer::Brick1(renderer) => renderer.render_column_onto(canvas),
-            assets::TextureRenderer::Stone1(renderer) => renderer.render_column_onto(canvas)
-        }
-    }
+    fn is_wall_bound(&self, cell: CellTag) -> bool { self.textures.texture_for(cell).is_some() }
 }
 
 static CELLS: [[u8; 16]; 16] = [
@@ -1,6 +1,7 @@
 #[derive(Debug)]
 pub enum Error {
     Str(&'static str),
+    CanvasPixelOutOfBounds,
     RaycastingOverflowX,
     RaycastingOverflowY,
     RaycastingFellOffTheWorld,
@@ -0,0 +1,69 @@
+use notray_engine::raycasting::*;
+
+fn fully_populated() -> CellTag {
+    CellTag::from_wall_id(0xa55a)
+        .with_floor_texture_id(0x11)
+        .with_ceiling_texture_id(0x22)
+        .with_light_level(0x33)
+        .with_door_id(0x44)
+        .with_flags(CellFlags::SECRET.union(CellFlags::DAMAGING))
+}
+
+#[test]
+fn every_field_reads_back_what_was_packed() {
+    let cell_tag = fully_populated();
+
+    assert_eq!(cell_tag.wall_id(), 0xa55a);
+    assert_eq!(cell_tag.floor_texture_id(), 0x11);
+    assert_eq!(cell_tag.ceiling_texture_id(), 0x22);
+    assert_eq!(cell_tag.light_level(), 0x33);
+    assert_eq!(cell_tag.door_id(), Some(0x44));
+    assert!(cell_tag.is_secret());
+    assert!(cell_tag.is_damaging());
+}
+
+#[test]
+fn door_ids_round_trip_and_cells_without_one_are_not_doors() {
+    assert_eq!(CellTag::from_wall_id(1).door_id(), None);
+    assert_eq!(CellTag::from_wall_id(1).with_light_level(0xff).door_id(), None);
+
+    for door_id in [0x00, 0x01, 0x80, 0xff] {
+        assert_eq!(CellTag::from_wall_id(1).with_door_id(door_id).door_id(), Some(door_id));
+    }
+}
+
+#[test]
+fn setting_flags_preserves_the_door() {
+    let door = CellTag::from_wall_id(3).with_door_id(0);
+
+    assert_eq!(door.with_flags(CellFlags::SECRET).door_id(), Some(0));
+    assert_eq!(door.with_flags(CellFlags::NONE).door_id(), Some(0));
+    assert!(door.with_flags(CellFlags::SECRET).is_secret());
+}
+
+#[test]
+fn public_flags_cannot_forge_a_door() {
+    let forged = CellTag::from_wall_id(3).with_flags(CellFlags::from_bits(0xff));
+
+    assert_eq!(forged.door_id(), None);
+    assert!(forged.is_secret());
+    assert!(forged.is_damaging());
+}
+
+#[test]
+fn fields_do_not_bleed_into_each_other() {
+    let all_ones = CellTag::from_wall_id(u16::MAX)
+        .with_floor_texture_id(u8::MAX)
+        .with_ceiling_texture_id(u8::MAX)
+        .with_light_level(u8::MAX)
+        .with_door_id(u8::MAX)
+        .with_flags(CellFlags::from_bits(u8::MAX));
+
+    assert_eq!(all_ones.to_bits(), 0x00ff_ffff_ffff_ffff);
+    assert_eq!(all_ones.with_floor_texture_id(0).to_bits(), 0x00ff_ffff_ff00_ffff);
+    assert_eq!(all_ones.with_ceiling_texture_id(0).to_bits(), 0x00ff_ffff_00ff_ffff);
+    assert_eq!(all_ones.with_light_level(0).to_bits(), 0x00ff_ff00_ffff_ffff);
+    assert_eq!(all_ones.with_door_id(0).to_bits(), 0x00ff_00ff_ffff_ffff);
+    assert_eq!(all_ones.with_flags(CellFlags::NONE).to_bits(), 0x0080_ffff_ffff_ffff);
+    assert_eq!(CellTag::from_wall_id(0).with_light_level(u8::MAX).to_bits(), 0x0000_00ff_0000_0000);
+}
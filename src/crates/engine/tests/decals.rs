@@ -0,0 +1,100 @@
+use notray_engine::Colour;
+use notray_engine::raycasting::*;
+
+const TRANSPARENT_INDEX: u8 = 0;
+
+// A 2x2 decal with a hole in its top-left texel, over a 4x4 wall numbered from 16 upwards
+static DECAL_PIXELS: [u8; 4] = [TRANSPARENT_INDEX, 1, 2, 3];
+static WALL_PIXELS: [u8; 16] = [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31];
+
+const DECAL: TextureDescriptor<'static> = TextureDescriptor::new(2, 2, TextureWrapping::Stretched, &DECAL_PIXELS)
+    .with_transparent_colour(Colour::new(TRANSPARENT_INDEX));
+
+const WALL: TextureDescriptor<'static> = TextureDescriptor::new(4, 4, TextureWrapping::Stretched, &WALL_PIXELS);
+
+// The middle of the face, half of it across and half of it down
+const MIDDLE: DecalPlacement = DecalPlacement::new(
+    TextureCoordinate::lit("0.25"),
+    TextureCoordinate::lit("0.25"),
+    TextureCoordinate::lit("0.5"),
+    TextureCoordinate::lit("0.5"));
+
+fn coordinates(x: &str, y: &str) -> TextureCoordinates {
+    TextureCoordinates::new(TextureCoordinate::lit(x), TextureCoordinate::lit(y))
+}
+
+fn texel_index(texture: &impl Texture, x: &str, y: &str) -> Option<u8> {
+    texture.get_texel_at(coordinates(x, y)).map(Colour::as_index)
+}
+
+#[test]
+fn placements_map_the_face_onto_the_whole_decal_and_nothing_outside_it() {
+    let decal_coordinates = |x, y| MIDDLE.decal_coordinates_for(coordinates(x, y)).map(|at| (at.x(), at.y()));
+
+    assert_eq!(decal_coordinates("0.25", "0.25"), Some((TextureCoordinate::ZERO, TextureCoordinate::ZERO)));
+    assert_eq!(decal_coordinates("0.5", "0.625"), Some((TextureCoordinate::lit("0.5"), TextureCoordinate::lit("0.75"))));
+    assert_eq!(decal_coordinates("0.2421875", "0.5"), None);
+    assert_eq!(decal_coordinates("0.5", "0.75"), None);
+    assert_eq!(decal_coordinates("0.75", "0.5"), None);
+}
+
+#[test]
+fn decals_are_found_only_on_the_cell_and_face_they_are_placed_on() {
+    let textures = [WALL, DECAL];
+    let placements = [
+        PlacedDecal::new(7, 1, CellFace::West, 1, MIDDLE),
+        PlacedDecal::new(2, 3, CellFace::North, 0, MIDDLE),
+        PlacedDecal::new(2, 3, CellFace::South, 1, MIDDLE),
+        PlacedDecal::new(300, 3, CellFace::East, 1, MIDDLE)
+    ];
+
+    let table = DecalTable::new(&textures, &placements);
+    let width_at = |cell_x, cell_y, face| table.decal_at(cell_x, cell_y, face).map(|decal| decal.texture().width_pixels());
+
+    assert_eq!(width_at(7, 1, CellFace::West), Some(2));
+    assert_eq!(width_at(2, 3, CellFace::North), Some(4));
+    assert_eq!(width_at(2, 3, CellFace::South), Some(2));
+    assert_eq!(width_at(300, 3, CellFace::East), Some(2));
+    assert_eq!(width_at(2, 3, CellFace::East), None);
+    assert_eq!(width_at(3, 2, CellFace::North), None);
+    assert_eq!(width_at(7, 0, CellFace::West), None);
+}
+
+#[test]
+#[should_panic(expected = "sorted")]
+fn decal_tables_must_be_sorted_by_cell_and_face() {
+    let textures = [DECAL];
+    DecalTable::new(&textures, &[PlacedDecal::new(2, 3, CellFace::South, 0, MIDDLE), PlacedDecal::new(2, 3, CellFace::North, 0, MIDDLE)]);
+}
+
+#[test]
+#[should_panic(expected = "Texture Id")]
+fn decal_tables_must_have_a_texture_for_every_placement() {
+    let textures = [DECAL];
+    DecalTable::new(&textures, &[PlacedDecal::new(2, 3, CellFace::North, 1, MIDDLE)]);
+}
+
+#[test]
+fn each_texture_counts_once_however_many_walls_it_is_placed_on() {
+    let once = [DECAL];
+    let placements: Vec<PlacedDecal> = (0..8).map(|cell_x| PlacedDecal::new(cell_x, 0, CellFace::North, 0, MIDDLE)).collect();
+    assert_eq!(DecalTable::new(&once, &placements).memory_bytes(), DECAL_PIXELS.len());
+
+    // Different textures are different memory, even when their pixels happen to match
+    let copied_pixels = DECAL_PIXELS;
+    let twice = [DECAL, TextureDescriptor::new(2, 2, TextureWrapping::Stretched, &copied_pixels)];
+    assert_eq!(DecalTable::new(&twice, &placements).memory_bytes(), DECAL_PIXELS.len() * 2);
+}
+
+#[test]
+fn decals_cover_the_wall_except_where_they_are_transparent() {
+    let overlay = DecalOverlay::new(WALL, Some(Decal::new(DECAL, MIDDLE)));
+
+    assert_eq!(texel_index(&overlay, "0.25", "0.25"), Some(16 + 5));
+    assert_eq!(texel_index(&overlay, "0.5", "0.25"), Some(1));
+    assert_eq!(texel_index(&overlay, "0.25", "0.5"), Some(2));
+    assert_eq!(texel_index(&overlay, "0.5", "0.5"), Some(3));
+    assert_eq!(texel_index(&overlay, "0", "0"), Some(16));
+    assert_eq!(texel_index(&overlay, "0.75", "0.75"), Some(16 + 15));
+    assert_eq!(texel_index(&DecalOverlay::new(WALL, None), "0.5", "0.5"), Some(16 + 10));
+}
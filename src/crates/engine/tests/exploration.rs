@@ -0,0 +1,151 @@
+use fixed::types::U16F16;
+
+use notray_engine::{raycasting_parameters, Error, OnStimuli, Stimuli};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::GridWorld;
+
+raycasting_parameters! {
+    pub struct ExplorationParameters {
+        canvas: 160 x 96 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+const OPEN: CellTag = CellTag::from_wall_id(0);
+const WALL: CellTag = CellTag::from_wall_id(1);
+const SECRET_DOOR: CellTag = OPEN.with_door_id(1).with_flags(CellFlags::SECRET);
+
+type Room = GridWorld<Vec<CellTag>>;
+
+// A corridor from (1, 1) to (6, 1), walled all around, with a secret door at (4, 1)
+fn corridor_with_secret() -> Room {
+    let (width_cells, height_cells) = (8, 3);
+    let mut room = GridWorld::new(width_cells, height_cells, vec![WALL; usize::from(width_cells) * usize::from(height_cells)]);
+    for cell_x in 1..width_cells - 1 {
+        room.set_cell(cell_x, 1, OPEN);
+    }
+
+    room.set_cell(4, 1, SECRET_DOOR);
+    room.set_spawn(1, 1, Angle::FACING_WEST);
+    room
+}
+
+fn exploration_of(width_cells: u16, height_cells: u16) -> Exploration<Vec<u32>> {
+    let words = CellBitmap::<&[u32]>::words_for(width_cells, height_cells);
+    Exploration::new(width_cells, height_cells, vec![0; words], vec![0; words])
+}
+
+fn statistics_of(exploration: &Exploration<Vec<u32>>, world: &Room) -> ExplorationStatistics {
+    exploration.statistics::<WorldAbsoluteCoordinate, _>(world)
+}
+
+struct Forward;
+
+impl Stimuli for Forward { }
+
+impl CameraStimuli for Forward {
+    fn should_move_forward(&self) -> bool { true }
+
+    fn should_move_backward(&self) -> bool { false }
+
+    fn should_turn_left(&self) -> bool { false }
+
+    fn should_turn_right(&self) -> bool { false }
+
+    fn is_fast(&self) -> bool { false }
+}
+
+// Open everywhere, so that every cell of even the largest exploration counts towards its total
+struct OpenEverywhere;
+
+impl World<U16F16> for OpenEverywhere {
+    fn spawn_at(&self) -> WorldCoordinates<U16F16> { WorldCoordinates::<U16F16>::from_cell_centre(0, 0) }
+
+    fn spawn_angle(&self) -> Angle { Angle::FACING_EAST }
+
+    fn probe_cell(&self, _probe: &CellProbe<U16F16>) -> CellProbeResult { CellProbeResult::Empty }
+}
+
+#[test]
+fn secrets_are_found_by_walking_into_them_and_not_by_seeing_them() {
+    let world = corridor_with_secret();
+    let mut exploration = exploration_of(world.width_cells(), world.height_cells());
+    (1..7).for_each(|cell_x| exploration.on_cell_seen(cell_x, 1));
+
+    let statistics = statistics_of(&exploration, &world);
+    assert_eq!((statistics.secrets(), statistics.secrets_found()), (1, 0));
+    assert_eq!(statistics.percent_explored(), 100);
+
+    let mut scene = Scene::<ExplorationParameters, _>::new(world).with_ray_observer(exploration);
+    for _ in 0..8 * 3 {
+        scene.on_stimuli(&Forward).unwrap();
+    }
+
+    let position = scene.camera().position();
+    assert_eq!((position.cell_x_int(), position.cell_y_int()), (4, 1));
+    assert!(scene.ray_observer().entered().is_set(4, 1));
+    assert_eq!(statistics_of(scene.ray_observer(), scene.world()).secrets_found(), 1);
+}
+
+#[test]
+fn rebasing_forgets_the_cells_seen_and_entered() {
+    let mut exploration = exploration_of(8, 3);
+    exploration.on_cell_seen(2, 1);
+    exploration.on_cell_entered(4, 1);
+    exploration.on_rebased();
+
+    assert_eq!((exploration.seen().count(), exploration.entered().count()), (0, 0));
+}
+
+#[test]
+fn explorations_larger_than_a_percentage_of_u32_cells_are_still_a_percentage() {
+    let (width_cells, height_cells) = (6600, 6600);
+    let words = CellBitmap::<&[u32]>::words_for(width_cells, height_cells);
+    let everything = Exploration::new(width_cells, height_cells, vec![u32::MAX; words], vec![0; words]);
+
+    let statistics = everything.statistics(&OpenEverywhere);
+    assert!(statistics.open_cells() > u32::MAX / 100);
+    assert_eq!(statistics.percent_explored(), 100);
+}
+
+#[test]
+fn saved_explorations_restore_the_same_cells_and_statistics() {
+    let world = corridor_with_secret();
+    let mut exploration = exploration_of(world.width_cells(), world.height_cells());
+    (1..5).for_each(|cell_x| exploration.on_cell_seen(cell_x, 1));
+    exploration.on_cell_entered(4, 1);
+
+    let mut bytes = vec![0xff; Exploration::<&[u32]>::bytes_for(world.width_cells(), world.height_cells()) + 3];
+    let length = exploration.write_bytes(&mut bytes).unwrap();
+    assert_eq!(length, bytes.len() - 3);
+
+    let words = CellBitmap::<&[u32]>::words_for(world.width_cells(), world.height_cells());
+    let restored = Exploration::from_bytes(&bytes[..length], vec![u32::MAX; words + 1], vec![u32::MAX; words]).unwrap();
+    assert_eq!((restored.seen().width_cells(), restored.seen().height_cells()), (world.width_cells(), world.height_cells()));
+    assert_eq!(restored.seen().words()[..words], exploration.seen().words()[..words]);
+    assert_eq!(restored.seen().words()[words], 0);
+    assert_eq!(restored.entered().words(), exploration.entered().words());
+    assert_eq!(statistics_of(&restored, &world), statistics_of(&exploration, &world));
+}
+
+#[test]
+fn saved_explorations_that_do_not_fit_are_errors() {
+    let exploration = exploration_of(40, 2);
+    let length = Exploration::<&[u32]>::bytes_for(40, 2);
+    assert!(matches!(exploration.write_bytes(&mut vec![0; length - 1]), Err(Error::Str(_))));
+
+    let mut bytes = vec![0; length];
+    exploration.write_bytes(&mut bytes).unwrap();
+
+    let restore = |bytes: &[u8], words| Exploration::from_bytes(bytes, vec![0; words], vec![0; words]).map(|_| ());
+    assert!(restore(&bytes, 3).is_ok());
+    assert!(matches!(restore(&bytes, 2), Err(Error::Str(_))));
+    assert!(matches!(restore(&bytes[..length - 1], 3), Err(Error::Str(_))));
+    assert!(matches!(restore(&bytes[..10], 3), Err(Error::Str(_))));
+    assert!(matches!(restore(b"NTRYSTIM\x01", 3), Err(Error::Str(_))));
+
+    bytes[8] = 2;
+    assert!(matches!(restore(&bytes, 3), Err(Error::Str(_))));
+}
@@ -0,0 +1,131 @@
+use notray_engine::{Colour, FrameBufferCanvas, FrameExport};
+
+const IEND_CRC: u32 = 0xae42_6082;
+
+struct Chunk {
+    chunk_type: [u8; 4],
+    data: Vec<u8>,
+    crc: u32
+}
+
+fn greyscale(colour: Colour) -> (u8, u8, u8) {
+    let index = colour.as_index();
+    (index, index, index)
+}
+
+fn png_of(width_pixels: u16, height_pixels: u16, index_at: impl Fn(u16, u16) -> u8) -> Vec<u8> {
+    let pixels: Vec<Colour> = (0..height_pixels)
+        .flat_map(|y| (0..width_pixels).map(move |x| (x, y)))
+        .map(|(x, y)| Colour::new(index_at(x, y)))
+        .collect();
+
+    let mut png = Vec::new();
+    FrameExport::new(&FrameBufferCanvas::new(width_pixels, height_pixels, pixels), greyscale).write_png(&mut png).unwrap();
+    png
+}
+
+fn chunks_of(png: &[u8]) -> Vec<Chunk> {
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    let (mut chunks, mut at) = (Vec::new(), 8);
+    while at < png.len() {
+        let length = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+        let data_end = at + 8 + length;
+        chunks.push(Chunk {
+            chunk_type: png[at + 4..at + 8].try_into().unwrap(),
+            data: png[at + 8..data_end].to_vec(),
+            crc: u32::from_be_bytes(png[data_end..data_end + 4].try_into().unwrap())
+        });
+
+        at = data_end + 4;
+    }
+
+    chunks
+}
+
+// Table-driven, unlike the exporter's, so that the two do not share their mistakes
+fn crc32(bytes: &[u8]) -> u32 {
+    let table: Vec<u32> = (0..256_u32)
+        .map(|entry| (0..8).fold(entry, |crc, _| if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 }))
+        .collect();
+
+    !bytes.iter().fold(!0_u32, |crc, byte| table[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8))
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1_u64, 0_u64), |(a, b), byte| (a + u64::from(*byte), b + a + u64::from(*byte)));
+    (((b % 65521) << 16) | (a % 65521)) as u32
+}
+
+// Returns the inflated data and the length of each stored block, checking the headers and trailer along the way
+fn inflate_stored(zlib: &[u8]) -> (Vec<u8>, Vec<usize>) {
+    assert_eq!(u16::from_be_bytes([zlib[0], zlib[1]]) % 31, 0);
+    assert_eq!(zlib[0] & 0x0f, 8);
+
+    let (mut data, mut block_lengths, mut at) = (Vec::new(), Vec::new(), 2);
+    loop {
+        let is_final = zlib[at] & 1 != 0;
+        assert_eq!(zlib[at] >> 1, 0, "only stored blocks are expected");
+
+        let length = u16::from_le_bytes([zlib[at + 1], zlib[at + 2]]);
+        assert_eq!(!length, u16::from_le_bytes([zlib[at + 3], zlib[at + 4]]));
+        data.extend_from_slice(&zlib[at + 5..at + 5 + usize::from(length)]);
+        block_lengths.push(usize::from(length));
+        at += 5 + usize::from(length);
+
+        if is_final {
+            break;
+        }
+    }
+
+    assert_eq!(&zlib[at..], adler32(&data).to_be_bytes());
+    (data, block_lengths)
+}
+
+#[test]
+fn every_chunk_has_the_crc_of_its_type_and_data() {
+    let chunks = chunks_of(&png_of(7, 5, |x, y| (x * 31 + y * 7) as u8));
+
+    let chunk_types: Vec<&[u8]> = chunks.iter().map(|chunk| chunk.chunk_type.as_slice()).collect();
+    assert_eq!(chunk_types, [b"IHDR".as_slice(), b"PLTE", b"IDAT", b"IEND"]);
+    for chunk in &chunks {
+        assert_eq!(chunk.crc, crc32(&[chunk.chunk_type.as_slice(), &chunk.data].concat()));
+    }
+
+    assert_eq!(chunks[3].crc, IEND_CRC);
+}
+
+#[test]
+fn the_header_and_palette_describe_an_indexed_image() {
+    let chunks = chunks_of(&png_of(300, 2, |_, _| 0));
+
+    assert_eq!(chunks[0].data, [0, 0, 1, 44, 0, 0, 0, 2, 8, 3, 0, 0, 0]);
+    assert_eq!(chunks[1].data.len(), 256 * 3);
+    assert_eq!(&chunks[1].data[3 * 200..3 * 201], [200, 200, 200]);
+}
+
+#[test]
+fn image_data_inflates_to_unfiltered_scanlines() {
+    let (width_pixels, height_pixels) = (7, 5);
+    let index_at = |x: u16, y: u16| (x * 31 + y * 7) as u8;
+    let chunks = chunks_of(&png_of(width_pixels, height_pixels, index_at));
+
+    let (scanlines, block_lengths) = inflate_stored(&chunks[2].data);
+    assert_eq!(block_lengths, [(usize::from(width_pixels) + 1) * usize::from(height_pixels)]);
+    for (y, scanline) in scanlines.chunks_exact(usize::from(width_pixels) + 1).enumerate() {
+        assert_eq!(scanline[0], 0);
+        assert!(scanline[1..].iter().enumerate().all(|(x, index)| *index == index_at(x as u16, y as u16)));
+    }
+}
+
+#[test]
+fn image_data_larger_than_a_stored_block_is_split_across_several() {
+    let (width_pixels, height_pixels) = (400, 240);
+    let index_at = |x: u16, y: u16| (x ^ y) as u8;
+    let chunks = chunks_of(&png_of(width_pixels, height_pixels, index_at));
+
+    let (scanlines, block_lengths) = inflate_stored(&chunks[2].data);
+    assert_eq!(block_lengths, [65535, 401 * 240 - 65535]);
+    assert_eq!(scanlines.len(), 401 * 240);
+    assert_eq!(scanlines[401 * 239 + 1 + 399], index_at(399, 239));
+}
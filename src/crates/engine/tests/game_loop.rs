@@ -0,0 +1,322 @@
+use fixed::types::{U16F16, U8F24};
+
+use notray_engine::{raycasting_parameters, Canvas, Colour, Error, FrameBufferCanvas, FrameRenderer, GameLoop, HeadlessScreen, OnStimuli, QuitStimuli, Result};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::GridWorld;
+
+raycasting_parameters! {
+    pub struct LoopParameters {
+        canvas: 400 x 240 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+raycasting_parameters! {
+    pub struct WideParameters {
+        canvas: 400 x 240 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+        world_coordinates: U16F16;
+    }
+}
+
+const ROOM_SIZE_CELLS: u16 = 16;
+const FORWARD_PER_TICK_BITS: u16 = 0x0020;
+const TURN_PER_TICK: i16 = 0x0400;
+
+// The direction vectors come from a sine lookup, so a step that is not along the y-axis can be rounded
+const MAX_ROUNDING_PER_TICK_BITS: u16 = 1;
+
+type Room = GridWorld<Vec<CellTag>>;
+
+fn walled_grid(width_cells: u16, height_cells: u16) -> Room {
+    let mut room = GridWorld::new(width_cells, height_cells, vec![CellTag::from_wall_id(0); usize::from(width_cells) * usize::from(height_cells)]);
+    for cell_x in 0..width_cells {
+        room.set_cell(cell_x, 0, CellTag::from_wall_id(1));
+        room.set_cell(cell_x, height_cells - 1, CellTag::from_wall_id(1));
+    }
+
+    for cell_y in 0..height_cells {
+        room.set_cell(0, cell_y, CellTag::from_wall_id(1));
+        room.set_cell(width_cells - 1, cell_y, CellTag::from_wall_id(1));
+    }
+
+    room
+}
+
+fn walled_room(spawn_x: u16, spawn_y: u16, spawn_angle: Angle) -> Room {
+    let mut room = walled_grid(ROOM_SIZE_CELLS, ROOM_SIZE_CELLS);
+    room.set_spawn(spawn_x, spawn_y, spawn_angle);
+    room
+}
+
+fn headless_screen() -> HeadlessScreen<Vec<Colour>> {
+    let (width_pixels, height_pixels) = (LoopParameters::CANVAS_WIDTH_PIXELS, LoopParameters::CANVAS_HEIGHT_PIXELS);
+    HeadlessScreen::new(FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); usize::from(width_pixels) * usize::from(height_pixels)]))
+}
+
+// The screen's canvas cannot be named, so the scenes are bound to this instead
+struct DynCanvas<'c>(&'c mut dyn Canvas);
+
+impl<'c> Canvas for DynCanvas<'c> {
+    fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> Result<()> { self.0.set_pixel(x, y, colour) }
+}
+
+fn run_script<TScene>(scene: &mut TScene, steps: &[ScriptStep]) -> (HeadlessScreen<Vec<Colour>>, u32)
+    where
+        for<'r, 'c> TScene: FrameRenderer<DynCanvas<'c>> + OnStimuli<ReplayStimuli<'r>> {
+
+    let replay = StimuliReplay::new(&StimuliRecording::from_runs(steps.iter().copied()));
+    let screen = headless_screen();
+    {
+        let stimuli = replay.stimuli();
+        let mut screen_canvas = screen.canvas();
+        let mut canvas = DynCanvas(&mut screen_canvas);
+        GameLoop::new(scene, &stimuli, &mut (replay.pollable(), screen.pollable()), &mut canvas).run().unwrap();
+    }
+
+    assert!(replay.stimuli().should_quit());
+    (screen, u32::try_from(replay.ticks_replayed()).unwrap())
+}
+
+fn pose_of(camera: &Camera<LoopParameters>) -> (u16, u16, i16) {
+    (camera.position().x().to_bits(), camera.position().y().to_bits(), camera.direction().to_fixed_point().to_bits())
+}
+
+fn rendered_at<TEngineParameters>(world: Room, camera: &Camera<TEngineParameters>) -> FrameBufferCanvas<Vec<Colour>>
+    where
+        TEngineParameters: EngineParameters + ProjectionPlaneParameters + Trigonometry {
+
+    let mut scene = Scene::<TEngineParameters, _>::new(world);
+    scene.camera_mut().set_position(camera.position());
+    scene.camera_mut().set_direction(camera.direction());
+
+    let screen = headless_screen();
+    scene.render_frame_onto(&mut screen.canvas()).unwrap();
+    screen.into_frame()
+}
+
+fn differing_pixels(first: &FrameBufferCanvas<Vec<Colour>>, second: &FrameBufferCanvas<Vec<Colour>>) -> impl Iterator<Item = (u16, u16)> {
+    let width_pixels = first.width_pixels();
+    first.pixels()
+        .iter()
+        .zip(second.pixels())
+        .enumerate()
+        .filter(|(_, (first, second))| first.as_index() != second.as_index())
+        .map(move |(i, _)| ((i % usize::from(width_pixels)) as u16, (i / usize::from(width_pixels)) as u16))
+}
+
+#[test]
+fn every_tick_renders_and_presents_a_frame() {
+    let mut scene = Scene::<LoopParameters, _>::new(walled_room(8, 8, Angle::FACING_NORTH));
+    let (screen, ticks) = run_script(&mut scene, &[ScriptStep::idle(5), ScriptStep::forward(2), ScriptStep::quit()]);
+
+    assert_eq!(ticks, 8);
+    assert_eq!(screen.frames_presented(), ticks);
+}
+
+#[test]
+fn scripts_without_a_quit_step_quit_when_they_run_out() {
+    let mut scene = Scene::<LoopParameters, _>::new(walled_room(8, 8, Angle::FACING_NORTH));
+    let (screen, ticks) = run_script(&mut scene, &[ScriptStep::turn_left(3)]);
+
+    assert_eq!(ticks, 3);
+    assert_eq!(screen.frames_presented(), 4);
+}
+
+#[test]
+fn moving_forward_moves_a_fixed_distance_per_tick_in_the_facing_direction() {
+    let mut scene = Scene::<LoopParameters, _>::new(walled_room(8, 12, Angle::FACING_NORTH));
+    let (spawn_x, spawn_y, spawn_angle) = pose_of(scene.camera());
+    run_script(&mut scene, &[ScriptStep::forward(10), ScriptStep::quit()]);
+
+    assert_eq!(pose_of(scene.camera()), (spawn_x, spawn_y - 10 * FORWARD_PER_TICK_BITS, spawn_angle));
+}
+
+#[test]
+fn moving_backward_retraces_moving_forward() {
+    let mut scene = Scene::<LoopParameters, _>::new(walled_room(8, 8, Angle::FACING_WEST));
+    let (spawn_x, spawn_y, spawn_angle) = pose_of(scene.camera());
+    run_script(&mut scene, &[ScriptStep::forward(7), ScriptStep::idle(2), ScriptStep::backward(7), ScriptStep::quit()]);
+
+    let (x, y, angle) = pose_of(scene.camera());
+    assert_eq!((y, angle), (spawn_y, spawn_angle));
+    assert!(x.abs_diff(spawn_x) <= 7 * MAX_ROUNDING_PER_TICK_BITS);
+}
+
+#[test]
+fn turning_and_walking_follows_the_script() {
+    let mut scene = Scene::<LoopParameters, _>::new(walled_room(8, 8, Angle::FACING_NORTH));
+    let (spawn_x, spawn_y, spawn_angle) = pose_of(scene.camera());
+    run_script(&mut scene, &[
+        ScriptStep::forward(8),
+        ScriptStep::turn_left(16),
+        ScriptStep::forward(8),
+        ScriptStep::turn_right(8).with_fast(),
+        ScriptStep::quit()]);
+
+    // A quarter of a turn to the left of North is West, which is towards +x
+    let (x, y, angle) = pose_of(scene.camera());
+    assert_eq!(angle, spawn_angle.wrapping_add(8 * TURN_PER_TICK));
+    assert_eq!(y, spawn_y - 8 * FORWARD_PER_TICK_BITS);
+    assert!(x.abs_diff(spawn_x + 8 * FORWARD_PER_TICK_BITS) <= 8 * MAX_ROUNDING_PER_TICK_BITS);
+}
+
+#[test]
+fn walking_off_the_edge_of_a_solid_world_stops_at_the_edge() {
+    // Walls do not block the camera yet, but the edge of the world does
+    let mut scene = Scene::<LoopParameters, _>::new(walled_room(1, 8, Angle::FACING_EAST));
+    let (_, spawn_y, spawn_angle) = pose_of(scene.camera());
+    run_script(&mut scene, &[ScriptStep::forward(40), ScriptStep::quit()]);
+
+    assert_eq!(pose_of(scene.camera()), (0, spawn_y, spawn_angle));
+}
+
+#[test]
+fn the_last_presented_frame_is_the_final_pose() {
+    let room = || walled_room(5, 9, Angle::FACING_NORTH);
+    let mut scene = Scene::<LoopParameters, _>::new(room());
+    let (screen, _) = run_script(&mut scene, &[ScriptStep::turn_left(3), ScriptStep::forward(12), ScriptStep::turn_right(5), ScriptStep::quit()]);
+
+    let expected = rendered_at(room(), scene.camera());
+    assert_eq!(differing_pixels(&screen.frame(), &expected).count(), 0);
+    assert_ne!(pose_of(scene.camera()), pose_of(Scene::<LoopParameters, _>::new(room()).camera()));
+}
+
+#[test]
+fn toggling_the_minimap_only_draws_over_its_own_rectangle() {
+    let (left, top, size_pixels) = (300, 4, 96);
+    let room = || walled_room(8, 8, Angle::FACING_SOUTH);
+    let words = CellBitmap::<&[u32]>::words_for(ROOM_SIZE_CELLS, ROOM_SIZE_CELLS);
+    let exploration = Exploration::new(ROOM_SIZE_CELLS, ROOM_SIZE_CELLS, vec![0; words], vec![0; words]);
+    let mut scene = MinimapOverlay::new(
+        Scene::<LoopParameters, _>::new(room()).with_ray_observer(exploration),
+        Minimap::new(left, top, size_pixels, size_pixels));
+
+    let (screen, _) = run_script(&mut scene, &[ScriptStep::idle(1), ScriptStep::toggle_minimap(), ScriptStep::idle(1), ScriptStep::quit()]);
+    assert!(scene.is_visible());
+
+    let without_minimap = rendered_at(room(), scene.scene().camera());
+    let differences: Vec<(u16, u16)> = differing_pixels(&screen.frame(), &without_minimap).collect();
+    assert!(!differences.is_empty());
+    assert!(differences.iter().all(|&(x, y)| (left..left + size_pixels).contains(&x) && (top..top + size_pixels).contains(&y)));
+}
+
+#[test]
+fn wide_worlds_render_and_can_be_walked_beyond_the_256th_cell() {
+    let wide_room = |pillar_x| {
+        let mut room = walled_grid(600, 8);
+        room.set_cell(pillar_x, 4, CellTag::from_wall_id(1));
+        room.set_spawn(250, 4, Angle::FACING_WEST);
+        room
+    };
+
+    let mut scene = Scene::<WideParameters, _>::new(wide_room(264));
+    let (screen, ticks) = run_script(&mut scene, &[ScriptStep::forward(64), ScriptStep::quit()]);
+    assert_eq!(screen.frames_presented(), ticks);
+
+    let position = scene.camera().position();
+    assert_eq!((position.cell_x_int(), position.cell_y_int()), (258, 4));
+
+    // The pillar is beyond the 256th cell, so it is only seen if the rays can get that far
+    let without_pillar = rendered_at(wide_room(590), scene.camera());
+    assert_eq!(differing_pixels(&screen.frame(), &rendered_at(wide_room(264), scene.camera())).count(), 0);
+    assert_ne!(differing_pixels(&screen.frame(), &without_pillar).count(), 0);
+}
+
+fn corridor(boundary: WorldBoundary, pillar_x: Option<u16>) -> Room {
+    let mut room = GridWorld::new(ROOM_SIZE_CELLS, ROOM_SIZE_CELLS, vec![CellTag::from_wall_id(0); usize::from(ROOM_SIZE_CELLS) * usize::from(ROOM_SIZE_CELLS)]);
+    for cell_x in 0..ROOM_SIZE_CELLS {
+        room.set_cell(cell_x, 7, CellTag::from_wall_id(1));
+        room.set_cell(cell_x, 9, CellTag::from_wall_id(1));
+    }
+
+    if let Some(pillar_x) = pillar_x {
+        room.set_cell(pillar_x, 8, CellTag::from_wall_id(1));
+    }
+
+    room.set_spawn(1, 8, Angle::FACING_EAST);
+    room.with_boundary(boundary)
+}
+
+const WRAP: WorldBoundary = WorldBoundary::Wrap { width_cells: ROOM_SIZE_CELLS, height_cells: ROOM_SIZE_CELLS };
+
+const ERROR: WorldBoundary = WorldBoundary::Error { width_cells: ROOM_SIZE_CELLS, height_cells: ROOM_SIZE_CELLS };
+
+#[test]
+fn walking_off_the_edge_of_a_wrapped_world_comes_back_on_the_other_side() {
+    let mut scene = Scene::<LoopParameters, _>::new(corridor(WRAP, Some(12)));
+    let (_, spawn_y, spawn_angle) = pose_of(scene.camera());
+    let (screen, _) = run_script(&mut scene, &[ScriptStep::forward(16), ScriptStep::quit()]);
+
+    let (x, y, angle) = pose_of(scene.camera());
+    assert_eq!((y, angle), (spawn_y, spawn_angle));
+    assert_eq!(x, (15 << 8) | 0x80);
+
+    // The pillar is only in view across the seam, behind where the camera started
+    assert_eq!(differing_pixels(&screen.frame(), &rendered_at(corridor(WRAP, Some(12)), scene.camera())).count(), 0);
+    assert_ne!(differing_pixels(&screen.frame(), &rendered_at(corridor(WRAP, None), scene.camera())).count(), 0);
+}
+
+#[test]
+fn rays_hit_walls_across_the_seam_of_a_wrapped_world() {
+    let room = corridor(WRAP, Some(13));
+    let hit = RayQuery::<LoopParameters, _>::new(&room)
+        .first_hit_along(room.spawn_at(), Angle::FACING_EAST, U8F24::from_num(8))
+        .unwrap()
+        .expect("the pillar to be hit from the other side of the seam");
+
+    assert_eq!((hit.cell_x(), hit.cell_y(), hit.face()), (13, 8, CellFace::West));
+    assert!(hit.distance().abs_diff(U8F24::lit("3.5")) < U8F24::lit("0.01"));
+
+    let scene = Scene::<LoopParameters, _>::new(corridor(WRAP, Some(13)));
+    let with_pillar = rendered_at(corridor(WRAP, Some(13)), scene.camera());
+    assert_ne!(differing_pixels(&with_pillar, &rendered_at(corridor(WRAP, None), scene.camera())).count(), 0);
+}
+
+#[test]
+fn rays_that_leave_a_world_whose_edge_is_an_error_raise_it() {
+    let room = corridor(ERROR, None);
+    let query = RayQuery::<LoopParameters, _>::new(&room);
+
+    assert!(matches!(
+        query.first_hit_along(room.spawn_at(), Angle::FACING_EAST, U8F24::from_num(8)),
+        Err(Error::RaycastingFellOffTheWorld)));
+
+    let mut scene = Scene::<LoopParameters, _>::new(corridor(ERROR, None));
+    assert!(matches!(scene.render_frame_onto(&mut headless_screen().canvas()), Err(Error::RaycastingFellOffTheWorld)));
+}
+
+#[test]
+fn walking_off_the_edge_of_a_world_whose_edge_is_an_error_is_refused() {
+    let mut scene = Scene::<LoopParameters, _>::new(corridor(ERROR, None));
+    let (_, spawn_y, spawn_angle) = pose_of(scene.camera());
+    for _ in 0..40 {
+        scene.on_stimuli(&StimuliSnapshot::new().with_move_forward(true)).unwrap();
+    }
+
+    assert_eq!(pose_of(scene.camera()), (0, spawn_y, spawn_angle));
+}
+
+#[test]
+fn walking_into_the_edge_of_a_world_whose_edge_is_an_error_slides_along_it() {
+    let mut scene = Scene::<LoopParameters, _>::new(corridor(ERROR, None));
+    let (_, spawn_y, _) = pose_of(scene.camera());
+    let diagonally = StimuliSnapshot::new().with_move_forward(true);
+    scene.camera_mut().turn(Angle::from_raw(8 * TURN_PER_TICK));
+
+    scene.on_stimuli(&diagonally).unwrap();
+    let (_, first_y, _) = pose_of(scene.camera());
+    for _ in 1..40 {
+        scene.on_stimuli(&diagonally).unwrap();
+    }
+
+    let (x, y, _) = pose_of(scene.camera());
+    let expected_y = i32::from(spawn_y) + 40 * (i32::from(first_y) - i32::from(spawn_y));
+    assert!(x < FORWARD_PER_TICK_BITS, "{x} is not at the edge");
+    assert_ne!(first_y, spawn_y);
+    assert!(i32::from(y).abs_diff(expected_y) <= 40 * u32::from(MAX_ROUNDING_PER_TICK_BITS), "{y} is not near {expected_y}");
+}
@@ -0,0 +1,85 @@
+use notray_engine::raycasting_parameters;
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::{self, GridGenerator, GridWorld};
+
+raycasting_parameters! {
+    pub struct GeneratorParameters {
+        canvas: 160 x 96 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+const SEEDS: [u64; 4] = [0, 1, 7, 0x1234_5678_9abc_def0];
+
+type Generated = GridWorld<Vec<CellTag>>;
+
+fn generated(generator: &impl GridGenerator, width_cells: u16, height_cells: u16, seed: u64) -> Generated {
+    let mut world = GridWorld::new(width_cells, height_cells, vec![CellTag::from_wall_id(0); usize::from(width_cells) * usize::from(height_cells)]);
+    generator.generate(&mut world, seed);
+    world
+}
+
+fn assert_deterministic_per_seed(generator: &impl GridGenerator, width_cells: u16, height_cells: u16) {
+    for seed in SEEDS {
+        let (first, second) = (generated(generator, width_cells, height_cells, seed), generated(generator, width_cells, height_cells, seed));
+        assert_eq!(first.cells(), second.cells(), "seed {seed}");
+        assert_eq!(first.spawn_cell(), second.spawn_cell(), "seed {seed}");
+    }
+
+    let differing = SEEDS
+        .iter()
+        .skip(1)
+        .filter(|&&seed| generated(generator, width_cells, height_cells, seed).cells() != generated(generator, width_cells, height_cells, SEEDS[0]).cells())
+        .count();
+
+    assert_eq!(differing, SEEDS.len() - 1);
+}
+
+fn assert_connected(generator: &impl GridGenerator, width_cells: u16, height_cells: u16) {
+    for seed in SEEDS {
+        let world = generated(generator, width_cells, height_cells, seed);
+        let words = vec![0; CellBitmap::<&[u32]>::words_for(width_cells, height_cells)];
+        let mut issues = Vec::new();
+        let summary = MapChecker::<GeneratorParameters, _>::new(width_cells, height_cells, words).check(&world, |issue| issues.push(issue));
+
+        let is_disconnected = |issue: &MapIssue| matches!(issue, MapIssue::SpawnInWall { .. } | MapIssue::OpenBoundary { .. } | MapIssue::UnreachableRegion { .. });
+        assert!(!issues.iter().any(is_disconnected), "seed {seed}: {issues:?}");
+        assert!(summary.reachable_cells() > 1, "seed {seed}");
+    }
+}
+
+#[test]
+fn mazes_are_the_same_for_the_same_seed() {
+    assert_deterministic_per_seed(&worlds::Maze, 31, 21);
+}
+
+#[test]
+fn mazes_can_be_walked_from_the_spawn_to_every_open_cell() {
+    assert_connected(&worlds::Maze, 31, 21);
+    assert_connected(&worlds::Maze, 32, 20);
+}
+
+#[test]
+fn dungeons_are_the_same_for_the_same_seed() {
+    assert_deterministic_per_seed(&worlds::Dungeon::new().with_doors(true), 63, 47);
+}
+
+#[test]
+fn dungeons_can_be_walked_from_the_spawn_to_every_open_cell() {
+    assert_connected(&worlds::Dungeon::new(), 63, 47);
+    assert_connected(&worlds::Dungeon::new().with_doors(true), 63, 47);
+    assert_connected(&worlds::Dungeon::new(), 5, 5);
+}
+
+#[test]
+fn caves_are_the_same_for_the_same_seed() {
+    assert_deterministic_per_seed(&worlds::Caves::new(), 64, 48);
+}
+
+#[test]
+fn caves_can_be_walked_from_the_spawn_to_every_open_cell() {
+    assert_connected(&worlds::Caves::new(), 64, 48);
+    assert_connected(&worlds::Caves::new().with_wall_percent(60).with_iterations(1), 64, 48);
+}
@@ -0,0 +1,174 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use notray_engine::{raycasting_parameters, Colour, FrameBufferCanvas, FrameExport, FrameRenderer};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::{self, assets, GridGenerator, GridWorld};
+
+// The same parameters as the 'notray' binary, so that the references are what players actually see
+raycasting_parameters! {
+    pub struct GoldenParameters {
+        canvas: 400 x 240 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+const BLESS_VARIABLE: &str = "NOTRAY_BLESS_GOLDEN_IMAGES";
+
+struct Pose {
+    name: &'static str,
+    position: WorldCoordinates,
+    angle: Angle,
+    max_differing_pixels: usize
+}
+
+impl Pose {
+    // Raw bits rather than floats so that the poses are exactly representable and cannot drift with rounding
+    fn new(name: &'static str, x_bits: u16, y_bits: u16, angle_bits: u16) -> Self {
+        let position = WorldCoordinates::new(WorldAbsoluteCoordinate::from_bits(x_bits), WorldAbsoluteCoordinate::from_bits(y_bits));
+        Self { name, position, angle: Angle::from_raw(angle_bits as i16), max_differing_pixels: 0 }
+    }
+
+    fn spawn_of(world: &impl World) -> Self {
+        Self { name: "spawn", position: world.spawn_at(), angle: world.spawn_angle(), max_differing_pixels: 0 }
+    }
+
+    const fn with_max_differing_pixels(self, max_differing_pixels: usize) -> Self {
+        Self { max_differing_pixels, ..self }
+    }
+}
+
+fn render_frame<TWorld: World + WorldRendering>(world: TWorld, pose: &Pose) -> FrameBufferCanvas<Vec<Colour>> {
+    let (width_pixels, height_pixels) = (GoldenParameters::CANVAS_WIDTH_PIXELS, GoldenParameters::CANVAS_HEIGHT_PIXELS);
+    let mut canvas = FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); usize::from(width_pixels) * usize::from(height_pixels)]);
+
+    let mut scene = Scene::<GoldenParameters, _>::new(world);
+    scene.camera_mut().set_position(pose.position);
+    scene.camera_mut().set_direction(pose.angle);
+    scene.render_frame_onto(&mut canvas).unwrap();
+    canvas
+}
+
+fn reference_path_for(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(format!("{name}.pgm"))
+}
+
+fn failure_path_for(name: &str, suffix: &str) -> PathBuf {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden_images");
+    fs::create_dir_all(&directory).unwrap();
+    directory.join(format!("{name}-{suffix}"))
+}
+
+// References are binary PGMs whose grey levels are palette indices, so that they stay small and exact
+fn read_reference(path: &Path) -> Option<(u16, u16, Vec<u8>)> {
+    let contents = fs::read(path).ok()?;
+    let mut fields = contents.splitn(4, |byte| byte.is_ascii_whitespace());
+    let (magic, width, height) = (fields.next()?, fields.next()?, fields.next()?);
+    let rest = fields.next()?;
+    let header_end = rest.iter().position(|byte| byte.is_ascii_whitespace())?;
+    if magic != b"P5" || &rest[..header_end] != b"255" {
+        return None;
+    }
+
+    let width = std::str::from_utf8(width).ok()?.parse().ok()?;
+    let height = std::str::from_utf8(height).ok()?.parse().ok()?;
+    Some((width, height, rest[header_end + 1..].to_vec()))
+}
+
+fn write_reference(path: &Path, frame: &FrameBufferCanvas<Vec<Colour>>) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut file = fs::File::create(path).unwrap();
+    write!(file, "P5\n{} {}\n255\n", frame.width_pixels(), frame.height_pixels()).unwrap();
+    file.write_all(&frame.pixels().iter().map(|colour| colour.as_index()).collect::<Vec<_>>()).unwrap();
+}
+
+// Differences are magenta over a darkened copy of the reference, which is usually enough to spot the cause
+fn write_diff(path: &Path, frame: &FrameBufferCanvas<Vec<Colour>>, reference: &[u8]) {
+    let mut diff = format!("P6\n{} {}\n255\n", frame.width_pixels(), frame.height_pixels()).into_bytes();
+    for (actual, expected) in frame.pixels().iter().zip(reference) {
+        if actual.as_index() == *expected {
+            let (red, green, blue) = assets::Palette::rgb_for(Colour::new(*expected));
+            diff.extend([red / 4, green / 4, blue / 4]);
+        } else {
+            diff.extend([0xff, 0x00, 0xff]);
+        }
+    }
+
+    fs::write(path, diff).unwrap();
+}
+
+fn compare_with_reference(name: &str, pose: &Pose, frame: &FrameBufferCanvas<Vec<Colour>>) -> Option<String> {
+    let reference_path = reference_path_for(name);
+    if std::env::var_os(BLESS_VARIABLE).is_some() {
+        write_reference(&reference_path, frame);
+        return None;
+    }
+
+    let Some((width, height, reference)) = read_reference(&reference_path) else {
+        return Some(format!("{name}: no readable reference at {}", reference_path.display()));
+    };
+
+    if (width, height) != (frame.width_pixels(), frame.height_pixels()) || reference.len() != frame.pixels().len() {
+        return Some(format!("{name}: reference is {width}x{height} but the frame is {}x{}", frame.width_pixels(), frame.height_pixels()));
+    }
+
+    let differing_pixels = frame.pixels().iter().zip(&reference).filter(|(actual, expected)| actual.as_index() != **expected).count();
+    if differing_pixels <= pose.max_differing_pixels {
+        return None;
+    }
+
+    let actual_path = failure_path_for(name, "actual.png");
+    let diff_path = failure_path_for(name, "diff.ppm");
+    FrameExport::new(frame, assets::Palette::rgb_for).save(&actual_path).unwrap();
+    write_diff(&diff_path, frame, &reference);
+    Some(format!(
+        "{name}: {differing_pixels} pixels differ (at most {} allowed); see {} and {}",
+        pose.max_differing_pixels,
+        actual_path.display(),
+        diff_path.display()))
+}
+
+fn assert_matches_golden_images<TWorld: World + WorldRendering>(world_name: &str, new_world: impl Fn() -> TWorld, poses: &[Pose]) {
+    let failures: Vec<String> = poses
+        .iter()
+        .filter_map(|pose| {
+            let name = format!("{world_name}-{}", pose.name);
+            compare_with_reference(&name, pose, &render_frame(new_world(), pose))
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "Frames differ from their golden images (set {BLESS_VARIABLE}=1 to accept the new frames):\n{}",
+        failures.join("\n"));
+}
+
+fn generated(generator: impl GridGenerator, size_cells: u16, seed: u64) -> GridWorld<Vec<CellTag>> {
+    let mut world = GridWorld::new(size_cells, size_cells, vec![CellTag::from_wall_id(0); usize::from(size_cells) * usize::from(size_cells)]);
+    generator.generate(&mut world, seed);
+    world
+}
+
+#[test]
+fn world1_matches_golden_images() {
+    assert_matches_golden_images("world1", worlds::World1::new, &[
+        Pose::new("spawn", 0x0280, 0x0280, 0xc000),
+        Pose::new("open-room-facing-north", 0x0780, 0x0c80, 0x4000),
+        Pose::new("corridor-facing-west", 0x0380, 0x0e80, 0x8000),
+        Pose::new("oblique", 0x0500, 0x0740, 0x2345),
+        Pose::new("close-to-wall", 0x0133, 0x0acd, 0x6000).with_max_differing_pixels(16),
+        Pose::new("pillar-diagonal", 0x0880, 0x0880, 0xe000)
+    ]);
+}
+
+#[test]
+fn generated_worlds_match_golden_images() {
+    let maze = || generated(worlds::Maze, 31, 7);
+    assert_matches_golden_images("maze", maze, &[Pose::spawn_of(&maze())]);
+
+    let dungeon = || generated(worlds::Dungeon::new().with_doors(true), 63, 3);
+    assert_matches_golden_images("dungeon", dungeon, &[Pose::spawn_of(&dungeon())]);
+}
@@ -0,0 +1,129 @@
+use core::cell::Cell;
+
+use notray_engine::{raycasting_parameters, Colour, FrameBufferCanvas, FrameRenderer};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::{cached_chunks_for_reach, ChunkCells, ChunkGenerator, Corridors, InfiniteWorld, CHUNK_SIZE_CELLS};
+
+raycasting_parameters! {
+    pub struct InfiniteParameters {
+        canvas: 160 x 96 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+const SEED: u64 = 0x5eed_cafe;
+
+const CACHED_CHUNKS: usize = cached_chunks_for_reach(InfiniteParameters::MAX_RAY_CELL_PROBES);
+
+struct CountingGenerator<'c, TGenerator: ChunkGenerator> {
+    generator: TGenerator,
+    chunks_generated: &'c Cell<u32>
+}
+
+impl<'c, TGenerator: ChunkGenerator> ChunkGenerator for CountingGenerator<'c, TGenerator> {
+    fn generate_chunk(&self, seed: u64, chunk_x: i32, chunk_y: i32, cells: &mut ChunkCells) {
+        self.chunks_generated.set(self.chunks_generated.get() + 1);
+        self.generator.generate_chunk(seed, chunk_x, chunk_y, cells);
+    }
+}
+
+// Nothing to stop the rays, so that they go as far as they can
+struct OpenField;
+
+impl ChunkGenerator for OpenField {
+    fn generate_chunk(&self, _seed: u64, _chunk_x: i32, _chunk_y: i32, cells: &mut ChunkCells) {
+        cells.fill(CellTag::from_wall_id(0));
+    }
+}
+
+fn cell_at<const CACHED: usize>(world: &InfiniteWorld<impl ChunkGenerator, CACHED>, cell_x: u16, cell_y: u16) -> Option<CellTag> {
+    match world.probe_cell(&CellProbe::new(<WorldCoordinates>::from_cell_centre(cell_x, cell_y))) {
+        CellProbeResult::Empty => None,
+        CellProbeResult::Opaque(cell) | CellProbeResult::Transparent(cell) | CellProbeResult::PossiblyTransparent(cell) => Some(cell)
+    }
+}
+
+fn chunk_of<const CACHED: usize>(world: &InfiniteWorld<impl ChunkGenerator, CACHED>, chunk_x: u16, chunk_y: u16) -> Vec<Option<CellTag>> {
+    (0..CHUNK_SIZE_CELLS)
+        .flat_map(|offset_y| (0..CHUNK_SIZE_CELLS).map(move |offset_x| (offset_x, offset_y)))
+        .map(|(offset_x, offset_y)| cell_at(world, chunk_x * CHUNK_SIZE_CELLS + offset_x, chunk_y * CHUNK_SIZE_CELLS + offset_y))
+        .collect()
+}
+
+fn render_facing(scene: &mut Scene<InfiniteParameters, impl World + WorldRendering>, direction: Angle) {
+    let (width_pixels, height_pixels) = (InfiniteParameters::CANVAS_WIDTH_PIXELS, InfiniteParameters::CANVAS_HEIGHT_PIXELS);
+    let mut canvas = FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); usize::from(width_pixels) * usize::from(height_pixels)]);
+    scene.camera_mut().set_direction(direction);
+    scene.render_frame_onto(&mut canvas).unwrap();
+}
+
+#[test]
+fn the_cache_covers_every_chunk_that_rays_can_reach() {
+    assert_eq!(cached_chunks_for_reach(64), 9 * 9);
+    assert_eq!(cached_chunks_for_reach(16), 3 * 3);
+    assert_eq!(cached_chunks_for_reach(17), 5 * 5);
+}
+
+#[test]
+fn chunks_generate_the_same_cells_after_being_evicted() {
+    let chunks_generated = Cell::new(0);
+    let world = InfiniteWorld::<_, 1>::new(CountingGenerator { generator: Corridors, chunks_generated: &chunks_generated }, SEED);
+    let roomy = InfiniteWorld::<_, CACHED_CHUNKS>::new(Corridors, SEED);
+
+    let first = chunk_of(&world, 5, 7);
+    let neighbour = chunk_of(&world, 6, 7);
+    assert_eq!(chunk_of(&world, 5, 7), first);
+    assert_eq!(chunks_generated.get(), 3);
+
+    assert_ne!(first, neighbour);
+    assert_eq!(chunk_of(&roomy, 5, 7), first);
+    assert_eq!(chunk_of(&roomy, 6, 7), neighbour);
+    assert_ne!(chunk_of(&InfiniteWorld::<_, 1>::new(Corridors, SEED + 1), 5, 7), first);
+}
+
+#[test]
+fn frames_from_one_place_generate_each_chunk_once() {
+    let chunks_generated = Cell::new(0);
+    let world = InfiniteWorld::<_, CACHED_CHUNKS>::new(CountingGenerator { generator: OpenField, chunks_generated: &chunks_generated }, SEED);
+    let mut scene = Scene::<InfiniteParameters, _>::new(world);
+
+    let facings = [Angle::FACING_NORTH, Angle::FACING_EAST, Angle::FACING_SOUTH, Angle::FACING_WEST];
+    for facing in facings {
+        render_facing(&mut scene, facing);
+    }
+
+    let generated_looking_around = chunks_generated.get();
+    assert!(generated_looking_around as usize <= CACHED_CHUNKS, "{generated_looking_around} chunks generated");
+
+    for facing in facings {
+        render_facing(&mut scene, facing);
+    }
+
+    assert_eq!(chunks_generated.get(), generated_looking_around);
+}
+
+#[test]
+fn rebasing_keeps_the_same_global_cells_around_the_camera() {
+    let mut world = InfiniteWorld::<_, CACHED_CHUNKS>::new(Corridors, SEED);
+    let position = <WorldCoordinates>::from_cell_centre(10, 250);
+    let around = |world: &InfiniteWorld<Corridors, CACHED_CHUNKS>, position: WorldCoordinates| -> Vec<Option<CellTag>> {
+        let (cell_x, cell_y) = (position.cell_x_int(), position.cell_y_int());
+        (cell_y - 5..=cell_y + 5)
+            .flat_map(|y| (cell_x - 8..=cell_x + 8).map(move |x| (x, y)))
+            .map(|(x, y)| cell_at(world, x, y))
+            .collect()
+    };
+
+    let before = around(&world, position);
+    let rebased = world.rebase(position).expect("a camera this close to the edge to be rebased");
+    assert_ne!((rebased.cell_x_int(), rebased.cell_y_int()), (10, 250));
+    assert_eq!((rebased.cell_x_frac(), rebased.cell_y_frac()), (position.cell_x_frac(), position.cell_y_frac()));
+    assert_eq!(around(&world, rebased), before);
+    assert!(world.rebase(rebased).is_none());
+
+    let (origin_x, origin_y) = world.origin_chunk();
+    let shift_chunks = |local: u16, rebased: u16| (i32::from(local) - i32::from(rebased)) / i32::from(CHUNK_SIZE_CELLS);
+    assert_eq!((origin_x, origin_y), (shift_chunks(10, rebased.cell_x_int()), shift_chunks(250, rebased.cell_y_int())));
+}
@@ -0,0 +1,186 @@
+use notray_engine::raycasting_parameters;
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::GridWorld;
+
+raycasting_parameters! {
+    pub struct CheckerParameters {
+        canvas: 160 x 96 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+const OPEN: CellTag = CellTag::from_wall_id(0);
+const WALL: CellTag = CellTag::from_wall_id(1);
+const UNBOUND_WALL_ID: u16 = 0x7fff;
+
+type Map = GridWorld<Vec<CellTag>>;
+
+// Rows of '#' for walls and anything else for open cells, with the spawn at the given cell
+fn map_of(rows: &[&str], spawn_x: u16, spawn_y: u16) -> Map {
+    let (width_cells, height_cells) = (rows[0].len() as u16, rows.len() as u16);
+    let cells = rows.iter().flat_map(|row| row.bytes()).map(|cell| if cell == b'#' { WALL } else { OPEN }).collect();
+    let mut map = GridWorld::new(width_cells, height_cells, cells);
+    map.set_spawn(spawn_x, spawn_y, Angle::FACING_SOUTH);
+    map
+}
+
+fn walled(width_cells: u16, height_cells: u16) -> Map {
+    let mut map = GridWorld::new(width_cells, height_cells, vec![OPEN; usize::from(width_cells) * usize::from(height_cells)]);
+    for cell_x in 0..width_cells {
+        map.set_cell(cell_x, 0, WALL);
+        map.set_cell(cell_x, height_cells - 1, WALL);
+    }
+
+    for cell_y in 0..height_cells {
+        map.set_cell(0, cell_y, WALL);
+        map.set_cell(width_cells - 1, cell_y, WALL);
+    }
+
+    map.set_spawn(1, 1, Angle::FACING_SOUTH);
+    map
+}
+
+fn check_region(map: &Map, width_cells: u16, height_cells: u16) -> (MapSummary, Vec<MapIssue>, CellBitmap<Vec<u32>>) {
+    let words = vec![0; CellBitmap::<&[u32]>::words_for(width_cells, height_cells)];
+    let mut checker = MapChecker::<CheckerParameters, _>::new(width_cells, height_cells, words);
+    let mut issues = Vec::new();
+    let summary = checker.check(map, |issue| issues.push(issue));
+    let reachable = CellBitmap::new(width_cells, height_cells, checker.reachable().words().to_vec());
+    (summary, issues, reachable)
+}
+
+fn check(map: &Map) -> (MapSummary, Vec<MapIssue>, CellBitmap<Vec<u32>>) {
+    check_region(map, map.width_cells(), map.height_cells())
+}
+
+#[test]
+fn a_closed_room_has_no_issues() {
+    let (summary, issues, _) = check(&walled(8, 6));
+
+    assert!(summary.is_ok(), "{issues:?}");
+    assert_eq!((summary.reachable_cells(), summary.unreachable_cells()), (6 * 4, 0));
+    assert_eq!(summary.longest_corridor_cells(), 6);
+}
+
+#[test]
+fn a_spawn_inside_a_wall_is_the_only_issue() {
+    let mut map = walled(8, 6);
+    map.set_spawn(3, 0, Angle::FACING_SOUTH);
+    let (summary, issues, _) = check(&map);
+
+    assert_eq!(issues, [MapIssue::SpawnInWall { cell_x: 3, cell_y: 0 }]);
+    assert_eq!(summary.issues(), 1);
+}
+
+#[test]
+fn a_spawn_outside_the_checked_region_is_the_only_issue() {
+    let mut map = walled(8, 6);
+    map.set_spawn(6, 4, Angle::FACING_SOUTH);
+    let (_, issues, _) = check_region(&map, 4, 4);
+
+    assert_eq!(issues, [MapIssue::SpawnOutsideRegion { cell_x: 6, cell_y: 4 }]);
+}
+
+#[test]
+fn open_cells_at_the_edge_of_the_region_are_open_boundaries_unless_the_world_walls_them() {
+    let map = map_of(&[
+        "######",
+        "#.....",
+        "#.####",
+        "#.#..#",
+        "######"], 1, 1);
+
+    let (_, issues, _) = check_region(&map, 5, 5);
+    assert_eq!(issues, [MapIssue::OpenBoundary { cell_x: 4, cell_y: 1 }, MapIssue::UnreachableRegion { cell_x: 3, cell_y: 3, region_cells: 2 }]);
+
+    let (summary, issues, _) = check(&map);
+    assert_eq!(issues, [MapIssue::UnreachableRegion { cell_x: 3, cell_y: 3, region_cells: 2 }]);
+    assert_eq!(summary.unreachable_cells(), 2);
+}
+
+#[test]
+fn wrapped_worlds_have_no_boundary_and_are_reached_across_their_edges() {
+    let map = map_of(&[
+        "#.###",
+        "#.###",
+        "..#..",
+        "#####"], 1, 2).with_boundary(WorldBoundary::Wrap { width_cells: 5, height_cells: 4 });
+
+    let (summary, issues, reachable) = check(&map);
+    assert!(summary.is_ok(), "{issues:?}");
+    assert_eq!(summary.reachable_cells(), 6);
+    assert!(reachable.is_set(3, 2) && reachable.is_set(4, 2));
+}
+
+#[test]
+fn only_unbound_walls_beside_reachable_cells_are_reported() {
+    let mut map = walled(8, 6);
+    map.set_cell(7, 2, CellTag::from_wall_id(UNBOUND_WALL_ID));
+    map.set_cell(4, 3, CellTag::from_wall_id(UNBOUND_WALL_ID));
+    map.set_cell(4, 4, WALL);
+    map.set_cell(3, 5, CellTag::from_wall_id(UNBOUND_WALL_ID));
+    let (_, issues, _) = check(&map);
+
+    assert_eq!(issues, [
+        MapIssue::UnboundWall { cell_x: 7, cell_y: 2, wall_id: UNBOUND_WALL_ID },
+        MapIssue::UnboundWall { cell_x: 4, cell_y: 3, wall_id: UNBOUND_WALL_ID },
+        MapIssue::UnboundWall { cell_x: 3, cell_y: 5, wall_id: UNBOUND_WALL_ID }]);
+}
+
+#[test]
+fn corridors_longer_than_a_ray_can_probe_are_reported_once_per_run() {
+    let max_ray_cell_probes = CheckerParameters::MAX_RAY_CELL_PROBES;
+    let length_cells = max_ray_cell_probes as u16 + 1;
+    let (summary, issues, _) = check(&walled(length_cells + 2, 3));
+    assert_eq!(issues, [MapIssue::CorridorTooLong { cell_x: 1, cell_y: 1, length_cells, max_ray_cell_probes }]);
+    assert_eq!(summary.longest_corridor_cells(), length_cells);
+
+    let (summary, issues, _) = check(&walled(length_cells + 1, 3));
+    assert!(summary.is_ok(), "{issues:?}");
+}
+
+#[test]
+fn unreachable_cells_are_reported_as_one_issue_per_region() {
+    let map = map_of(&[
+        "##########",
+        "#..#.....#",
+        "#..#.###.#",
+        "####.#.#.#",
+        "#..#.###.#",
+        "#..#.....#",
+        "##########"], 1, 1);
+
+    let (summary, issues, reachable) = check(&map);
+    assert_eq!(issues, [
+        MapIssue::UnreachableRegion { cell_x: 4, cell_y: 1, region_cells: 16 },
+        MapIssue::UnreachableRegion { cell_x: 6, cell_y: 3, region_cells: 1 },
+        MapIssue::UnreachableRegion { cell_x: 1, cell_y: 4, region_cells: 4 }]);
+    assert_eq!((summary.reachable_cells(), summary.unreachable_cells()), (4, 21));
+    assert_eq!(reachable.count(), 4);
+}
+
+#[test]
+fn flood_fills_reach_every_cell_of_maps_with_more_branches_than_seeds() {
+    // A comb of single-cell teeth off a spine, then a serpentine that doubles back on every row
+    let (width_cells, height_cells) = (255, 201);
+    let mut map = walled(width_cells, height_cells);
+    for cell_y in 2..height_cells / 2 {
+        for cell_x in (2..width_cells - 1).step_by(2) {
+            map.set_cell(cell_x, cell_y, WALL);
+        }
+    }
+
+    for cell_y in (height_cells / 2 + 1..height_cells - 1).step_by(2) {
+        let gap_x = if cell_y % 4 == 1 { 1 } else { width_cells - 2 };
+        for cell_x in (1..width_cells - 1).filter(|&cell_x| cell_x != gap_x) {
+            map.set_cell(cell_x, cell_y, WALL);
+        }
+    }
+
+    let open_cells = map.cells().iter().filter(|&&cell| cell == OPEN).count() as u32;
+    let (summary, issues, reachable) = check(&map);
+    assert_eq!(summary.unreachable_cells(), 0, "{issues:?}");
+    assert_eq!((summary.reachable_cells(), reachable.count()), (open_cells, open_cells));
+}
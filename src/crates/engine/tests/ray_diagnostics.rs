@@ -0,0 +1,86 @@
+use fixed::types::I8F24;
+
+use notray_engine::{raycasting_parameters, Colour, FrameBufferCanvas, FrameRenderer, HeadlessScreen, Vector2d};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::GridWorld;
+
+raycasting_parameters! {
+    pub struct EdgeParameters {
+        canvas: 400 x 240 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+const EDGE_CELL: u16 = 255;
+const PROBES: usize = 4;
+
+type Strip = GridWorld<Vec<CellTag>>;
+
+// The width of the whole coordinate space, open along the middle row right up to the last cell
+fn strip_to_the_edge(spawn_angle: Angle) -> Strip {
+    let width_cells = EDGE_CELL + 1;
+    let mut strip = GridWorld::new(width_cells, 3, vec![CellTag::from_wall_id(0); usize::from(width_cells) * 3]);
+    for cell_x in 0..width_cells {
+        strip.set_cell(cell_x, 0, CellTag::from_wall_id(1));
+        strip.set_cell(cell_x, 2, CellTag::from_wall_id(1));
+    }
+
+    strip.set_cell(0, 1, CellTag::from_wall_id(1));
+    strip.set_spawn(EDGE_CELL, 1, spawn_angle);
+    strip
+}
+
+fn saturations_along(ray_direction: Vector2d<I8F24>) -> SaturationCounts {
+    let strip = strip_to_the_edge(Angle::FACING_WEST);
+    let mut traversal = RayTraversal::new(&strip, <WorldCoordinates>::from_cell_centre(EDGE_CELL, 1), ray_direction, PROBES);
+    for crossing in traversal.by_ref() {
+        crossing.unwrap();
+    }
+
+    *traversal.saturations()
+}
+
+fn diagnostics_for_a_frame_facing(spawn_angle: Angle) -> SaturationCounts {
+    let (width_pixels, height_pixels) = (EdgeParameters::CANVAS_WIDTH_PIXELS, EdgeParameters::CANVAS_HEIGHT_PIXELS);
+    let screen = HeadlessScreen::new(FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); usize::from(width_pixels) * usize::from(height_pixels)]));
+
+    let mut scene = Scene::<EdgeParameters, _>::new(strip_to_the_edge(spawn_angle));
+    scene.render_frame_onto(&mut screen.canvas()).unwrap();
+    *scene.diagnostics().counts()
+}
+
+#[test]
+fn rays_along_the_edge_do_not_saturate_the_initial_cell_edge() {
+    let saturations = saturations_along(Vector2d::new(I8F24::ZERO, I8F24::ONE));
+
+    assert_eq!(saturations.count(SaturationSite::InitialCellEdge), 0);
+    assert_eq!(saturations.count(SaturationSite::SolidBoundary), 0);
+}
+
+#[test]
+fn rays_into_the_edge_saturate_the_initial_cell_edge_once_and_the_boundary_on_every_probe() {
+    let saturations = saturations_along(Vector2d::new(I8F24::ONE, I8F24::ZERO));
+
+    assert_eq!(saturations.count(SaturationSite::InitialCellEdge), 1);
+    assert_eq!(saturations.count(SaturationSite::SolidBoundary), PROBES as u32);
+}
+
+#[test]
+fn rays_away_from_the_edge_do_not_saturate() {
+    assert!(saturations_along(Vector2d::new(I8F24::NEG_ONE, I8F24::ZERO)).is_clean());
+}
+
+#[test]
+fn every_column_of_a_camera_facing_into_the_edge_saturates_the_initial_cell_edge() {
+    let saturations = diagnostics_for_a_frame_facing(Angle::FACING_WEST);
+
+    assert_eq!(saturations.count(SaturationSite::InitialCellEdge), u32::from(EdgeParameters::CANVAS_WIDTH_PIXELS));
+    assert!(saturations.count(SaturationSite::SolidBoundary) > 0);
+}
+
+#[test]
+fn a_camera_facing_away_from_the_edge_does_not_saturate() {
+    assert!(diagnostics_for_a_frame_facing(Angle::FACING_EAST).is_clean());
+}
@@ -0,0 +1,118 @@
+use fixed::types::{I8F24, U8F24, U8F8};
+
+use notray_engine::{raycasting_parameters, Error, Vector2d};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::GridWorld;
+
+raycasting_parameters! {
+    pub struct TraversalParameters {
+        canvas: 160 x 96 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 16 bits;
+        sine_lookup_size: 360 degrees;
+    }
+}
+
+const OPEN: CellTag = CellTag::from_wall_id(0);
+const WALL: CellTag = CellTag::from_wall_id(1);
+const FAR_WALL: CellTag = CellTag::from_wall_id(2);
+const DOOR: CellTag = OPEN.with_door_id(1);
+const PROBES: usize = 64;
+
+type Corridor = GridWorld<Vec<CellTag>>;
+
+// Open along the middle row from the spawn at (1, 1), through a door at (3, 1), to a wall at (6, 1) and another at (8, 1) beyond it
+fn corridor() -> Corridor {
+    let (width_cells, height_cells) = (10, 3);
+    let mut corridor = GridWorld::new(width_cells, height_cells, vec![WALL; usize::from(width_cells) * usize::from(height_cells)]);
+    for cell_x in 1..width_cells - 1 {
+        corridor.set_cell(cell_x, 1, OPEN);
+    }
+
+    corridor.set_cell(3, 1, DOOR);
+    corridor.set_cell(6, 1, WALL);
+    corridor.set_cell(8, 1, FAR_WALL);
+    corridor.set_spawn(1, 1, Angle::FACING_WEST);
+    corridor
+}
+
+fn room(size_cells: u16) -> Corridor {
+    let mut room = GridWorld::new(size_cells, size_cells, vec![OPEN; usize::from(size_cells) * usize::from(size_cells)]);
+    for cell in 0..size_cells {
+        room.set_cell(cell, 0, WALL);
+        room.set_cell(cell, size_cells - 1, WALL);
+        room.set_cell(0, cell, WALL);
+        room.set_cell(size_cells - 1, cell, WALL);
+    }
+
+    room
+}
+
+fn crossings(world: &Corridor, origin: WorldCoordinates, ray_direction: Vector2d<I8F24>, max_probes: usize) -> Vec<RayCrossing<U8F8>> {
+    RayTraversal::new(world, origin, ray_direction, max_probes).map(Result::unwrap).collect()
+}
+
+fn along_the_corridor() -> Vec<RayCrossing<U8F8>> {
+    crossings(&corridor(), <WorldCoordinates>::from_cell_centre(1, 1), Vector2d::new(I8F24::ONE, I8F24::ZERO), 8)
+}
+
+#[test]
+fn crossings_come_in_order_of_distance_and_each_is_one_cell_on_from_the_last() {
+    let origin = <WorldCoordinates>::from_cell_centre(2, 3);
+    let crossings = crossings(&room(16), origin, Vector2d::new(I8F24::lit("0.75"), I8F24::lit("0.5")), 20);
+    assert_eq!(crossings.len(), 20);
+
+    let mut previous = (origin.cell_x_int(), origin.cell_y_int(), U8F24::ZERO);
+    for crossing in &crossings {
+        let (cell_x, cell_y) = (crossing.cell_x(), crossing.cell_y());
+        assert!(crossing.distance() >= previous.2);
+        assert_eq!(cell_x.abs_diff(previous.0) + cell_y.abs_diff(previous.1), 1);
+        assert_eq!(crossing.face(), if cell_x != previous.0 { CellFace::East } else { CellFace::North });
+        assert_eq!(crossing.is_horizontal_intersection(), cell_x != previous.0);
+        previous = (cell_x, cell_y, crossing.distance());
+    }
+}
+
+#[test]
+fn transparent_and_opaque_cells_are_crossed_without_ending_the_walk() {
+    let crossings = along_the_corridor();
+    let cells: Vec<(u16, u16)> = crossings.iter().map(|crossing| (crossing.cell_x(), crossing.cell_y())).collect();
+    assert_eq!(cells, [(2, 1), (3, 1), (4, 1), (5, 1), (6, 1), (7, 1), (8, 1), (9, 1)]);
+
+    let distances: Vec<U8F24> = crossings.iter().map(RayCrossing::distance).collect();
+    assert_eq!(distances, ["0.5", "1.5", "2.5", "3.5", "4.5", "5.5", "6.5", "7.5"].map(U8F24::lit));
+
+    assert!(matches!(crossings[0].probe_result(), CellProbeResult::Empty));
+    assert!(matches!(crossings[1].probe_result(), CellProbeResult::Transparent(door) if *door == DOOR));
+    assert!(matches!(crossings[4].probe_result(), CellProbeResult::Opaque(wall) if *wall == WALL));
+    assert!(matches!(crossings[5].probe_result(), CellProbeResult::Empty));
+    assert!(matches!(crossings[6].probe_result(), CellProbeResult::Opaque(wall) if *wall == FAR_WALL));
+}
+
+#[test]
+fn walks_end_after_their_probes_or_at_the_first_boundary_error() {
+    assert_eq!(crossings(&corridor(), <WorldCoordinates>::from_cell_centre(1, 1), Vector2d::new(I8F24::ONE, I8F24::ZERO), 3).len(), 3);
+
+    let bounded = corridor().with_boundary(WorldBoundary::Error { width_cells: 10, height_cells: 3 });
+    let walk: Vec<_> = RayTraversal::new(&bounded, <WorldCoordinates>::from_cell_centre(1, 1), Vector2d::new(I8F24::NEG_ONE, I8F24::ZERO), PROBES).collect();
+    assert_eq!(walk.len(), 2);
+    assert!(matches!(walk[0], Ok(ref crossing) if crossing.cell_x() == 0));
+    assert!(matches!(walk[1], Err(Error::RaycastingFellOffTheWorld)));
+}
+
+#[test]
+fn the_nearest_hit_is_the_first_opaque_crossing() {
+    let corridor = corridor();
+    let query = RayQuery::<TraversalParameters, _>::new(&corridor);
+    let origin = <WorldCoordinates>::from_cell_centre(1, 1);
+    let first_opaque = along_the_corridor().into_iter().find(|crossing| matches!(crossing.probe_result(), CellProbeResult::Opaque(_))).unwrap();
+
+    let hit = query.first_hit_along(origin, Angle::FACING_WEST, U8F24::lit("8")).unwrap().unwrap();
+    assert_eq!((hit.cell_x(), hit.cell_y(), hit.face()), (first_opaque.cell_x(), first_opaque.cell_y(), first_opaque.face()));
+    assert_eq!((hit.cell_x(), hit.face(), hit.cell_tag()), (6, CellFace::East, WALL));
+    assert!(hit.distance().abs_diff(U8F24::lit("4.5")) < U8F24::lit("0.001"), "{}", hit.distance());
+
+    assert!(query.first_hit_along(origin, Angle::FACING_WEST, U8F24::lit("4")).unwrap().is_none());
+    assert!(!query.has_line_of_sight(origin, <WorldCoordinates>::from_cell_centre(7, 1)).unwrap());
+    assert!(query.has_line_of_sight(origin, <WorldCoordinates>::from_cell_centre(5, 1)).unwrap());
+}
@@ -0,0 +1,82 @@
+use fixed::types::U8F8;
+
+use notray_engine::raycasting_parameters;
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::GridWorld;
+
+raycasting_parameters! {
+    pub struct ReferenceParameters {
+        canvas: 160 x 96 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 16 bits;
+        sine_lookup_size: 360 degrees;
+    }
+}
+
+const OPEN: CellTag = CellTag::from_wall_id(0);
+const WALL: CellTag = CellTag::from_wall_id(1);
+const ROOM_CELLS: u16 = 8;
+
+type Room = GridWorld<Vec<CellTag>>;
+
+fn room() -> Room {
+    let mut room = GridWorld::new(ROOM_CELLS, ROOM_CELLS, vec![OPEN; usize::from(ROOM_CELLS) * usize::from(ROOM_CELLS)]);
+    for cell in 0..ROOM_CELLS {
+        room.set_cell(cell, 0, WALL);
+        room.set_cell(cell, ROOM_CELLS - 1, WALL);
+        room.set_cell(0, cell, WALL);
+        room.set_cell(ROOM_CELLS - 1, cell, WALL);
+    }
+
+    room
+}
+
+// Off-centre and off-axis, so that the poses are not only the symmetrical cases
+fn poses() -> impl Iterator<Item = (WorldCoordinates, Angle)> {
+    let positions = [(1, 1), (3, 4), (6, 2), (2, 6)].map(|(cell_x, cell_y)| WorldCoordinates::new(
+        U8F8::from_num(cell_x) + U8F8::lit("0.357"),
+        U8F8::from_num(cell_y) + U8F8::lit("0.613")));
+
+    positions
+        .into_iter()
+        .flat_map(|position| (0..16_u16).map(move |i| (position, Angle::from_raw((i * 0x1000).wrapping_add(0x0123) as i16))))
+}
+
+#[test]
+fn the_centre_column_hits_the_wall_straight_ahead() {
+    let centre_column = ReferenceParameters::CANVAS_WIDTH_PIXELS / 2;
+    let reference = ReferenceRaycaster::<ReferenceParameters>::new();
+    let hit = reference.cast_column(&room(), <WorldCoordinates>::from_cell_centre(2, 3), Angle::FACING_WEST, centre_column).unwrap();
+
+    assert_eq!((hit.cell_x(), hit.cell_y(), hit.face(), hit.cell_tag()), (7, 3, CellFace::East, WALL));
+    assert_eq!(hit.distance(), 4.5);
+    assert!((hit.cell_offset() - 0.5).abs() < 1e-9);
+    assert!((hit.projected_wall_height() - 96.0 * 96.0 / 160.0 / 4.5).abs() < 1e-9);
+}
+
+#[test]
+fn rays_that_leave_an_unwalled_world_hit_nothing() {
+    let open = GridWorld::new(ROOM_CELLS, ROOM_CELLS, vec![OPEN; usize::from(ROOM_CELLS) * usize::from(ROOM_CELLS)])
+        .with_boundary(WorldBoundary::Error { width_cells: ROOM_CELLS, height_cells: ROOM_CELLS });
+
+    let reference = ReferenceRaycaster::<ReferenceParameters>::new();
+    assert!(reference.cast_column(&open, <WorldCoordinates>::from_cell_centre(2, 3), Angle::FACING_WEST, 0).is_none());
+}
+
+#[test]
+fn the_fixed_point_walls_agree_with_the_reference() {
+    let world = room();
+    let mut analysis = AccuracyAnalysis::<ReferenceParameters>::new();
+    for (position, direction) in poses() {
+        analysis.analyse_pose(&world, position, direction).unwrap();
+    }
+
+    // Rays within a fraction of a texel of a corner can still pick the neighbouring face, but only a handful of them
+    let overall = analysis.overall();
+    assert_eq!(analysis.poses(), 64);
+    assert!(overall.compared() > 64 * u32::from(ReferenceParameters::CANVAS_WIDTH_PIXELS) * 9 / 10, "{}", overall.compared());
+    assert!(overall.mismatched() * 100 < overall.compared(), "{}", overall.mismatched());
+    assert!(overall.max_wall_height_error() < 4.0, "{}", overall.max_wall_height_error());
+    assert!(overall.max_cell_offset_error() < 1.0 / 32.0, "{}", overall.max_cell_offset_error());
+    assert!(overall.mean_cell_offset_error() < 1.0 / 256.0, "{}", overall.mean_cell_offset_error());
+}
@@ -0,0 +1,150 @@
+use notray_engine::{raycasting_parameters, Colour, FrameBufferCanvas, GameLoop, Pollable, QuitStimuli};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::World1;
+
+raycasting_parameters! {
+    pub struct ReplayParameters {
+        canvas: 400 x 240 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 6 bits;
+        sine_lookup_size: 90 degrees;
+    }
+}
+
+type Pose = (u16, u16, i16);
+
+fn recording_of(runs: &[(StimuliSnapshot, u32)]) -> StimuliRecording {
+    let mut recording = StimuliRecording::new();
+    for &(snapshot, ticks) in runs {
+        for _ in 0..ticks {
+            recording.push(snapshot);
+        }
+    }
+
+    recording
+}
+
+fn walk_and_turn() -> StimuliRecording {
+    let forward = StimuliSnapshot::new().with_move_forward(true);
+    recording_of(&[
+        (StimuliSnapshot::new(), 3),
+        (forward, 8),
+        (StimuliSnapshot::new().with_turn_left(true), 4),
+        (forward.with_toggle_minimap(true), 1),
+        (forward, 6),
+        (StimuliSnapshot::new().with_turn_right(true).with_fast(true), 2),
+        (StimuliSnapshot::new().with_quit(true), 1)])
+}
+
+fn pose_of(camera: &Camera<ReplayParameters>) -> Pose {
+    (camera.position().x().to_bits(), camera.position().y().to_bits(), camera.direction().to_fixed_point().to_bits())
+}
+
+fn spawn_pose() -> Pose {
+    pose_of(Scene::<ReplayParameters, _>::new(World1::new()).camera())
+}
+
+fn replay(recording: &StimuliRecording) -> (Pose, StimuliRecording) {
+    let (width_pixels, height_pixels) = (ReplayParameters::CANVAS_WIDTH_PIXELS, ReplayParameters::CANVAS_HEIGHT_PIXELS);
+    let mut canvas = FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); usize::from(width_pixels) * usize::from(height_pixels)]);
+    let mut scene = Scene::<ReplayParameters, _>::new(World1::new());
+
+    let replay = StimuliReplay::new(recording);
+    let recorder = StimuliRecorder::new();
+    {
+        let stimuli = replay.stimuli();
+        let mut pollable = (replay.pollable(), recorder.pollable(stimuli));
+        GameLoop::new(&mut scene, &stimuli, &mut pollable, &mut canvas).run().unwrap();
+    }
+
+    assert_eq!(replay.ticks_replayed(), recording.ticks());
+    (pose_of(scene.camera()), recorder.into_recording())
+}
+
+#[test]
+fn recordings_round_trip_through_bytes() {
+    let recording = recording_of(&[
+        (StimuliSnapshot::new(), 1),
+        (StimuliSnapshot::new().with_move_backward(true), 300),
+        (StimuliSnapshot::new().with_quit(true), 1)]);
+
+    let bytes = recording.to_bytes();
+    assert_eq!(bytes.len(), 8 + 1 + 2 + 3 + 2);
+    assert_eq!(recording.ticks(), 302);
+    assert_eq!(StimuliRecording::from_bytes(&bytes).unwrap(), recording);
+}
+
+#[test]
+fn recordings_from_runs_are_the_same_as_pushing_each_tick() {
+    let forward = StimuliSnapshot::new().with_move_forward(true);
+    let runs = [(forward, 3), (forward, 2), (StimuliSnapshot::new(), 0), (forward, 1), (StimuliSnapshot::new().with_quit(true), 1)];
+
+    assert_eq!(StimuliRecording::from_runs(runs), recording_of(&runs));
+    assert_eq!(StimuliRecording::from_runs(runs).to_bytes().len(), 8 + 1 + 2 + 2);
+    assert_eq!(StimuliRecording::from_runs([ScriptStep::forward(6), ScriptStep::quit()]), recording_of(&runs));
+}
+
+#[test]
+fn corrupt_recordings_are_rejected() {
+    let bytes = walk_and_turn().to_bytes();
+    let mut wrong_version = bytes.clone();
+    wrong_version[8] += 1;
+    let mut unknown_stimuli = bytes.clone();
+    unknown_stimuli[9] = 0x80;
+
+    assert!(StimuliRecording::from_bytes(b"NOTSTIMS").is_err());
+    assert!(StimuliRecording::from_bytes(&wrong_version).is_err());
+    assert!(StimuliRecording::from_bytes(&unknown_stimuli).is_err());
+    assert!(StimuliRecording::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(StimuliRecording::from_bytes(&[&bytes[..], &[0x01, 0xff, 0xff, 0xff, 0xff, 0x7f]].concat()).is_err());
+}
+
+#[test]
+fn replays_are_deterministic_and_can_be_re_recorded() {
+    let recording = walk_and_turn();
+    let (first_pose, re_recording) = replay(&recording);
+    let (second_pose, _) = replay(&StimuliRecording::from_bytes(&recording.to_bytes()).unwrap());
+
+    assert_ne!(first_pose, spawn_pose());
+    assert_eq!(first_pose, second_pose);
+    assert_eq!(re_recording, recording);
+}
+
+#[test]
+fn replays_quit_when_the_recording_runs_out() {
+    let recording = recording_of(&[(StimuliSnapshot::new().with_turn_left(true), 5)]);
+    let (pose, re_recording) = replay(&recording);
+
+    let (spawn_x, spawn_y, spawn_angle) = spawn_pose();
+    assert_eq!(pose, (spawn_x, spawn_y, spawn_angle.wrapping_add(5 * 0x0400)));
+    assert_eq!(re_recording.ticks(), 6);
+}
+
+#[test]
+fn replays_walk_the_runs_without_expanding_them() {
+    let forward = StimuliSnapshot::new().with_move_forward(true);
+    let turn_left = StimuliSnapshot::new().with_turn_left(true);
+    let replay = StimuliReplay::new(&StimuliRecording::from_runs([(turn_left, 2), (forward, u32::MAX), (forward.with_fast(true), u32::MAX)]));
+    let (stimuli, mut pollable) = (replay.stimuli(), replay.pollable());
+
+    assert!(!stimuli.should_turn_left() && !stimuli.should_move_forward());
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        pollable.poll().unwrap();
+        seen.push((stimuli.should_turn_left(), stimuli.should_move_forward(), stimuli.should_quit()));
+    }
+
+    assert_eq!(seen, [(true, false, false), (true, false, false), (false, true, false), (false, true, false)]);
+    assert_eq!(replay.ticks_replayed(), 4);
+    assert!(!replay.is_finished());
+}
+
+#[test]
+fn empty_recordings_quit_on_the_first_tick() {
+    let replay = StimuliReplay::new(&StimuliRecording::new());
+    replay.pollable().poll().unwrap();
+
+    assert!(replay.stimuli().should_quit());
+    assert!(replay.is_finished());
+    assert_eq!(replay.ticks_replayed(), 0);
+}
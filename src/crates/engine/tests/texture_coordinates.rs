@@ -0,0 +1,191 @@
+use notray_engine::{raycasting_parameters, Canvas, Colour, Result};
+use notray_engine::raycasting::*;
+
+raycasting_parameters! {
+    pub struct ReferenceParameters {
+        canvas: 400 x 240 pixels;
+        field_of_view: 62.5 degrees;
+        sine_lookup_msbs: 16 bits;
+        sine_lookup_size: 360 degrees;
+    }
+}
+
+const WORLD_SIZE_CELLS: u16 = 16;
+const SPAWN_AT: WorldCoordinates = <WorldCoordinates>::from_cell_centre(2, 2);
+
+struct PillaredRoom;
+
+impl PillaredRoom {
+    fn is_wall(cell_x: u16, cell_y: u16) -> bool {
+        let is_boundary = cell_x == 0 || cell_y == 0 || cell_x == WORLD_SIZE_CELLS - 1 || cell_y == WORLD_SIZE_CELLS - 1;
+        let is_pillar = cell_x.is_multiple_of(4) && cell_y.is_multiple_of(4);
+        is_boundary || is_pillar
+    }
+
+    fn cell_id(cell_x: u16, cell_y: u16) -> u16 { cell_y * WORLD_SIZE_CELLS + cell_x }
+}
+
+impl World for PillaredRoom {
+    fn spawn_at(&self) -> WorldCoordinates { SPAWN_AT }
+
+    fn spawn_angle(&self) -> Angle { Angle::FACING_SOUTH }
+
+    fn probe_cell(&self, probe: &CellProbe) -> CellProbeResult {
+        let (cell_x, cell_y) = (probe.at().cell_x_int(), probe.at().cell_y_int());
+        if cell_x >= WORLD_SIZE_CELLS || cell_y >= WORLD_SIZE_CELLS || Self::is_wall(cell_x, cell_y) {
+            CellProbeResult::Opaque(CellTag::from_wall_id(Self::cell_id(cell_x.min(15), cell_y.min(15))))
+        } else {
+            CellProbeResult::Empty
+        }
+    }
+}
+
+struct NullCanvas;
+
+impl Canvas for NullCanvas {
+    fn set_pixel(&mut self, _x: u16, _y: u16, _colour: Colour) -> Result<()> { Ok(()) }
+}
+
+#[derive(Debug)]
+struct ReferenceIntersection {
+    cell_id: u16,
+    is_horizontal_intersection: bool,
+    cell_offset: f64,
+    distance_to_nearest_corner: f64
+}
+
+fn reference_intersection_for(camera: &Camera<ReferenceParameters>, column: u16) -> ReferenceIntersection {
+    // The camera's (quantised) vectors are used just like the engine's so that only the intersection is under test
+    let (origin_x, origin_y) = (camera.position().x().to_num::<f64>(), camera.position().y().to_num::<f64>());
+    let (direction, projection_plane) = (camera.direction_vector(), camera.projection_plane_vector());
+    let column_scaling = quantised_column_scaling_for(column);
+
+    let ray_x = direction.x().to_num::<f64>() + column_scaling * projection_plane.x().to_num::<f64>();
+    let ray_y = direction.y().to_num::<f64>() + column_scaling * projection_plane.y().to_num::<f64>();
+
+    let (delta_x, delta_y) = ((1.0 / ray_x).abs(), (1.0 / ray_y).abs());
+    let (mut cell_x, mut cell_y) = (origin_x.floor() as i32, origin_y.floor() as i32);
+    let (step_x, mut distance_x) = if ray_x >= 0.0 { (1, (cell_x as f64 + 1.0 - origin_x) * delta_x) } else { (-1, (origin_x - cell_x as f64) * delta_x) };
+    let (step_y, mut distance_y) = if ray_y >= 0.0 { (1, (cell_y as f64 + 1.0 - origin_y) * delta_y) } else { (-1, (origin_y - cell_y as f64) * delta_y) };
+
+    loop {
+        let is_horizontal_intersection = distance_x < distance_y;
+        let distance = if is_horizontal_intersection {
+            cell_x += step_x;
+            distance_x += delta_x;
+            distance_x - delta_x
+        } else {
+            cell_y += step_y;
+            distance_y += delta_y;
+            distance_y - delta_y
+        };
+
+        if cell_x < 0 || cell_y < 0 || cell_x >= WORLD_SIZE_CELLS as i32 || cell_y >= WORLD_SIZE_CELLS as i32 {
+            panic!("Reference ray escaped the world");
+        }
+
+        if PillaredRoom::is_wall(cell_x as u16, cell_y as u16) {
+            let (along_wall, is_mirrored) = if is_horizontal_intersection {
+                (origin_y + distance * ray_y, ray_x > 0.0)
+            } else {
+                (origin_x + distance * ray_x, ray_y < 0.0)
+            };
+
+            let cell_offset = along_wall - along_wall.floor();
+            return ReferenceIntersection {
+                cell_id: PillaredRoom::cell_id(cell_x as u16, cell_y as u16),
+                is_horizontal_intersection,
+                cell_offset: if is_mirrored { 1.0 - cell_offset } else { cell_offset },
+                distance_to_nearest_corner: cell_offset.min(1.0 - cell_offset)
+            };
+        }
+    }
+}
+
+fn quantised_column_scaling_for(column: u16) -> f64 {
+    let normalising_factor_bits = ReferenceParameters::CANVAS_COLUMN_NORMALISING_FACTOR.to_bits() as u32;
+    let scaling_bits = ((normalising_factor_bits * column as u32) >> 2) & 0xffff;
+    scaling_bits as f64 / 16384.0 - 1.0
+}
+
+fn camera_poses() -> impl Iterator<Item = (WorldCoordinates, Angle)> {
+    let positions = (0..48_u16)
+        .map(|i| (0x0140 + i * 0x1b7 % 0x0d00, 0x0180 + i * 0x2c9 % 0x0c80))
+        .map(|(x, y)| WorldCoordinates::new(WorldAbsoluteCoordinate::from_bits(x), WorldAbsoluteCoordinate::from_bits(y)))
+        .filter(|position| !PillaredRoom::is_wall(position.cell_x_int(), position.cell_y_int()));
+
+    positions.flat_map(|position| (0..32_u16).map(move |i| (position, Angle::from_raw((i * 0x0800 + 0x0123) as i16))))
+}
+
+fn cell_offsets_for(camera: &Camera<ReferenceParameters>) -> Vec<RayCellIntersection> {
+    let world = PillaredRoom;
+    let mut context = RaycastingContext::<ReferenceParameters>::default();
+    let mut canvas = NullCanvas;
+    context.on_frame_start(camera, &mut canvas).unwrap();
+
+    let mut intersections = Vec::with_capacity(ReferenceParameters::CANVAS_WIDTH_PIXELS as usize);
+    loop {
+        context.cast_ray(&world).unwrap();
+        intersections.push(context.cell_intersection().expect("Every ray must hit a wall in a closed room"));
+        if !context.next_column().unwrap() {
+            break;
+        }
+    }
+
+    intersections
+}
+
+#[test]
+fn cell_offsets_match_floating_point_model_to_within_a_64th_of_a_64_texel_wide_texel() {
+    const TOLERANCE: f64 = 1.0 / 4096.0;
+    const AMBIGUOUS_CORNER_DISTANCE: f64 = 1.0 / 4096.0;
+
+    let (mut compared, mut worst_error) = (0, 0.0_f64);
+    for (position, direction) in camera_poses() {
+        let camera = Camera::<ReferenceParameters>::new(Object::new(position, direction));
+        for (column, intersection) in cell_offsets_for(&camera).iter().enumerate() {
+            let reference = reference_intersection_for(&camera, column as u16);
+            if reference.distance_to_nearest_corner < AMBIGUOUS_CORNER_DISTANCE {
+                continue;
+            }
+
+            assert_eq!(
+                (intersection.cell_tag().wall_id(), intersection.is_horizontal_intersection()),
+                (reference.cell_id, reference.is_horizontal_intersection),
+                "Different wall face hit at column {} for pose ({}, {}) @ {:#06x}",
+                column, position.x(), position.y(), direction.to_fixed_point().to_bits());
+
+            let error = (intersection.cell_offset().to_num::<f64>() - reference.cell_offset).abs();
+            assert!(
+                error <= TOLERANCE,
+                "Cell offset {} differs from reference {} at column {} for pose ({}, {}) @ {:#06x}",
+                intersection.cell_offset(), reference.cell_offset, column, position.x(), position.y(), direction.to_fixed_point().to_bits());
+
+            worst_error = worst_error.max(error);
+            compared += 1;
+        }
+    }
+
+    assert!(compared > 100_000, "Too few columns were compared ({compared}) for the test to be meaningful");
+    assert!(worst_error > 0.0, "Suspiciously perfect agreement with the floating-point model");
+}
+
+#[test]
+fn cell_offsets_increase_from_left_to_right_across_every_face() {
+    for (position, direction) in camera_poses() {
+        let intersections = cell_offsets_for(&Camera::new(Object::new(position, direction)));
+        for (column, pair) in intersections.windows(2).enumerate() {
+            let (left, right) = (&pair[0], &pair[1]);
+            let is_same_face =
+                left.cell_tag().wall_id() == right.cell_tag().wall_id() &&
+                left.is_horizontal_intersection() == right.is_horizontal_intersection();
+
+            if is_same_face {
+                assert!(
+                    right.cell_offset() >= left.cell_offset(),
+                    "Texture is flipped between columns {} and {} for pose ({}, {}) @ {:#06x}",
+                    column, column + 1, position.x(), position.y(), direction.to_fixed_point().to_bits());
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use notray_engine::{include_texture, Colour};
+use notray_engine::raycasting::*;
+use notray_engine::raycasting::worlds::assets::Palette;
+
+const TRANSPARENT_INDEX: u8 = 0;
+
+// Each texel is its own index into the pixels, so a fetch shows exactly where in them it came from
+fn numbered_pixels(count: usize) -> Vec<u8> {
+    (0..count).map(|index| index as u8).collect()
+}
+
+fn texel_index(texture: &impl Texture, x: &str, y: &str, mip_level: u8) -> Option<u8> {
+    texture
+        .get_texel_at_mip_level(TextureCoordinates::new(TextureCoordinate::lit(x), TextureCoordinate::lit(y)), mip_level)
+        .map(Colour::as_index)
+}
+
+fn step_of(texels_across: u32, texels_per_cell: u32) -> TextureCoordinate {
+    TextureCoordinate::from_bits((texels_across << TextureCoordinate::FRAC_NBITS).div_ceil(texels_per_cell) as u16)
+}
+
+#[test]
+fn each_mip_level_starts_where_the_one_before_it_ends() {
+    let pixels = numbered_pixels(8 * 4 + 4 * 2 + 2);
+    let texture = TextureDescriptor::new(8, 4, TextureWrapping::Stretched, &pixels).with_mip_levels(3);
+
+    assert_eq!(texture.memory_bytes(), pixels.len());
+    assert_eq!(texel_index(&texture, "0", "0", 0), Some(0));
+    assert_eq!(texel_index(&texture, "0", "0", 1), Some(8 * 4));
+    assert_eq!(texel_index(&texture, "0.875", "0.75", 1), Some(8 * 4 + 4 + 3));
+    assert_eq!(texel_index(&texture, "0.75", "0", 2), Some(8 * 4 + 4 * 2 + 1));
+    assert_eq!(texel_index(&texture, "0.75", "0", 7), Some(8 * 4 + 4 * 2 + 1));
+}
+
+#[test]
+fn repeated_textures_find_their_mip_levels_at_the_same_offsets() {
+    let pixels = numbered_pixels(16 * 8 + 8 * 4);
+    let texture = TextureDescriptor::new(16, 8, TextureWrapping::Repeated, &pixels).with_mip_levels(2);
+
+    assert_eq!(texel_index(&texture, "0", "0", 1), Some(16 * 8));
+    assert_eq!(texel_index(&texture, "0.03125", "0.015625", 1), Some(16 * 8 + 2 * 8 + 4));
+}
+
+#[test]
+fn mip_levels_follow_the_texels_squeezed_into_each_pixel_down_the_wall() {
+    let pixels = numbered_pixels(64 * 64 + 32 * 32 + 16 * 16 + 8 * 8);
+    let texture = TextureDescriptor::new(64, 64, TextureWrapping::Stretched, &pixels).with_mip_levels(4);
+    let levels: Vec<u8> = [128, 64, 33, 32, 16, 8, 1, 0].into_iter().map(|height_pixels| texture.mip_level_for(height_pixels, TextureCoordinate::ZERO)).collect();
+
+    assert_eq!(levels, [0, 0, 0, 1, 2, 3, 3, 3]);
+}
+
+#[test]
+fn mip_levels_follow_the_texels_stepped_across_the_wall_when_that_squeezes_them_more() {
+    let pixels = numbered_pixels(64 * 32 + 32 * 16 + 16 * 8);
+    let stretched = TextureDescriptor::new(64, 32, TextureWrapping::Stretched, &pixels).with_mip_levels(3);
+    let across = |texels_across| stretched.mip_level_for(32, step_of(texels_across, 64));
+
+    assert_eq!([across(0), across(1), across(2), across(3), across(4), across(16)], [0, 0, 1, 1, 2, 2]);
+    assert_eq!(stretched.mip_level_for(8, step_of(2, 64)), 2);
+
+    let repeated = TextureDescriptor::new(64, 32, TextureWrapping::Repeated, &pixels).with_mip_levels(3);
+    assert_eq!(repeated.mip_level_for(256, step_of(2, 256)), 1);
+    assert_eq!(repeated.mip_level_for(256, step_of(1, 256)), 0);
+}
+
+#[test]
+fn generated_mip_levels_average_only_the_opaque_texels_and_keep_mostly_transparent_blocks_transparent() {
+    // The blocks of the first level are: three holes and a texel; two holes and two grass; all sky; and all grass
+    const HOLES: TextureDescriptor<'static> = include_texture! {
+        pixels: "tests/textures/holes-4x4.raw";
+        palette: "src/raycasting/worlds/palette.rgb";
+        size: 4 x 4 pixels;
+        wrapping: Stretched;
+        mip_levels: 3;
+        transparent_colour: 0;
+    };
+
+    let rgb_at = |x, y, mip_level| texel_index(&HOLES, x, y, mip_level).map(|index| Palette::rgb_for(Colour::new(index)));
+    let (grass, sky) = (Palette::rgb_for(Palette::GRASS_LIGHTEST), Palette::rgb_for(Palette::SKY_LIGHTEST));
+
+    assert_eq!(HOLES.transparent_colour().map(Colour::as_index), Some(TRANSPARENT_INDEX));
+    assert_eq!(texel_index(&HOLES, "0", "0", 0), None);
+    assert_eq!(rgb_at("0.125", "0.125", 1), None);
+    assert_eq!(rgb_at("0.625", "0.125", 1), Some(grass));
+    assert_eq!(rgb_at("0.125", "0.625", 1), Some(sky));
+    assert_eq!(rgb_at("0.625", "0.625", 1), Some(grass));
+
+    let smallest = texel_index(&HOLES, "0", "0", 2);
+    assert!(smallest.is_some_and(|index| index != TRANSPARENT_INDEX), "{smallest:?}");
+}
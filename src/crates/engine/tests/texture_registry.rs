@@ -0,0 +1,95 @@
+use notray_engine::{Canvas, Colour, Result};
+use notray_engine::raycasting::*;
+
+const TRANSPARENT_INDEX: u8 = 7;
+const UNBOUND_COLOUR: u8 = 99;
+
+static BRICK_PIXELS: [u8; 4] = [1, 2, 3, 4];
+static GRATE_PIXELS: [u8; 8] = [5, TRANSPARENT_INDEX, 5, TRANSPARENT_INDEX, TRANSPARENT_INDEX, 6, TRANSPARENT_INDEX, 6];
+
+static TEXTURES: [Option<TextureDescriptor<'static>>; 4] = [
+    None,
+    Some(TextureDescriptor::new(2, 2, TextureWrapping::Stretched, &BRICK_PIXELS)),
+    None,
+    Some(TextureDescriptor::new(4, 2, TextureWrapping::Stretched, &GRATE_PIXELS).with_transparent_colour(Colour::new(TRANSPARENT_INDEX)))
+];
+
+const TABLE: TextureTable<'static> = TextureTable::new(&TEXTURES);
+
+// Only records what was drawn in the first column, which is all that a single RenderingColumn draws
+struct ColumnCanvas(Vec<u8>);
+
+impl Canvas for ColumnCanvas {
+    fn set_pixel(&mut self, _x: u16, _y: u16, colour: Colour) -> Result<()> {
+        self.0.push(colour.as_index());
+        Ok(())
+    }
+}
+
+fn texel_index(texture: &TextureDescriptor, x: &str, y: &str) -> Option<u8> {
+    texture
+        .get_texel_at(TextureCoordinates::new(TextureCoordinate::lit(x), TextureCoordinate::lit(y)))
+        .map(Colour::as_index)
+}
+
+fn width_for(wall_id: u16) -> Option<u8> {
+    TABLE.texture_for(CellTag::from_wall_id(wall_id)).map(TextureDescriptor::width_pixels)
+}
+
+#[test]
+fn textures_are_looked_up_by_the_cell_tags_wall_id_alone() {
+    assert_eq!((TABLE.len(), TABLE.is_empty()), (4, false));
+    assert_eq!(width_for(1), Some(2));
+    assert_eq!(width_for(3), Some(4));
+
+    let decorated = CellTag::from_wall_id(3)
+        .with_floor_texture_id(1)
+        .with_ceiling_texture_id(2)
+        .with_light_level(200)
+        .with_flags(CellFlags::SECRET);
+
+    assert_eq!(TABLE.texture_for(decorated).map(TextureDescriptor::width_pixels), Some(4));
+    assert_eq!(TABLE.memory_bytes(), BRICK_PIXELS.len() + GRATE_PIXELS.len());
+}
+
+#[test]
+fn wall_ids_without_a_texture_are_unbound() {
+    assert_eq!(width_for(0), None);
+    assert_eq!(width_for(2), None);
+    assert_eq!(width_for(4), None);
+    assert_eq!(width_for(u16::MAX), None);
+    assert!(TextureTable::new(&[]).is_empty());
+}
+
+#[test]
+fn unbound_walls_are_drawn_in_the_unbound_colour() {
+    for cell_tag in [Some(CellTag::from_wall_id(2)), Some(CellTag::from_wall_id(300)), None] {
+        let mut column = RenderingColumn::new(0, 0, None);
+        column.next_span(3, 0, 3);
+
+        let mut renderer = TABLE.wall_renderer_for(cell_tag, None, Colour::new(UNBOUND_COLOUR), &mut column);
+        assert!(matches!(renderer, RegisteredTextureColumnRenderer::Unbound(_)));
+
+        let mut canvas = ColumnCanvas(Vec::new());
+        renderer.render_column_onto(&mut canvas).unwrap();
+        assert_eq!(canvas.0, [UNBOUND_COLOUR; 3]);
+    }
+
+    let mut column = RenderingColumn::new(0, 0, None);
+    assert!(matches!(TABLE.wall_renderer_for(Some(CellTag::from_wall_id(1)), None, Colour::new(UNBOUND_COLOUR), &mut column), RegisteredTextureColumnRenderer::Bound(_)));
+}
+
+#[test]
+fn only_textures_with_a_transparent_colour_have_holes() {
+    let (brick, grate) = (TEXTURES[1].unwrap(), TEXTURES[3].unwrap());
+
+    assert_eq!(brick.transparent_colour().map(Colour::as_index), None);
+    assert_eq!(texel_index(&brick, "0", "0"), Some(1));
+    assert_eq!(texel_index(&brick, "0.5", "0.5"), Some(4));
+
+    assert_eq!(grate.transparent_colour().map(Colour::as_index), Some(TRANSPARENT_INDEX));
+    assert_eq!(texel_index(&grate, "0", "0"), Some(5));
+    assert_eq!(texel_index(&grate, "0.25", "0"), None);
+    assert_eq!(texel_index(&grate, "0", "0.5"), None);
+    assert_eq!(texel_index(&grate, "0.25", "0.5"), Some(6));
+}
@@ -0,0 +1,88 @@
+use notray_engine::raycasting::{AccuracyAnalysis, Angle, CellProbe, CellProbeResult, ColumnAccuracy, ProjectionPlaneParameters, World, WorldCoordinates, WorldRendering};
+
+use crate::{RaycastingParameters, Result, ResultCoalescing, WorldCommand, WorldCoordinate};
+
+pub struct Accuracy;
+
+impl Accuracy {
+    const MAX_POSITIONS_PER_AXIS: u16 = 16;
+    const UNBOUNDED_REGION_CELLS: u16 = 16;
+    const ANGLES_PER_POSITION: u16 = 64;
+    const COLUMNS_PER_ROW: u16 = 20;
+
+    // The wall textures are 64 texels wide, which makes texture errors easier to judge than fractions of a cell
+    const TEXELS_PER_CELL: f64 = 64.0;
+
+    // Slightly off-centre and off-axis, so that the sweep does not only sample the symmetrical cases
+    const POSITION_OFFSET_CELLS: (f64, f64) = (0.357, 0.613);
+    const ANGLE_OFFSET: u16 = 0x0123;
+
+    fn region_for<TWorld: World<WorldCoordinate>>(world: &TWorld, size_cells: Option<(u16, u16)>) -> (u16, u16, u16, u16) {
+        match size_cells {
+            Some((width_cells, height_cells)) => (0, 0, width_cells, height_cells),
+            None => {
+                let spawn = world.spawn_at();
+                let half = Self::UNBOUNDED_REGION_CELLS / 2;
+                (spawn.cell_x_int().saturating_sub(half), spawn.cell_y_int().saturating_sub(half), Self::UNBOUNDED_REGION_CELLS, Self::UNBOUNDED_REGION_CELLS)
+            }
+        }
+    }
+
+    fn positions_in<TWorld: World<WorldCoordinate>>(world: &TWorld, region: (u16, u16, u16, u16)) -> Vec<WorldCoordinates<WorldCoordinate>> {
+        let (left, top, width_cells, height_cells) = region;
+        let (stride_x, stride_y) = (width_cells.div_ceil(Self::MAX_POSITIONS_PER_AXIS).max(1), height_cells.div_ceil(Self::MAX_POSITIONS_PER_AXIS).max(1));
+        let cells = (0..height_cells)
+            .step_by(usize::from(stride_y))
+            .flat_map(|y| (0..width_cells).step_by(usize::from(stride_x)).map(move |x| (left.saturating_add(x), top.saturating_add(y))));
+
+        cells
+            .filter(|&(cell_x, cell_y)| matches!(world.probe_cell(&CellProbe::new(WorldCoordinates::<WorldCoordinate>::from_cell_centre(cell_x, cell_y))), CellProbeResult::Empty))
+            .filter_map(|(cell_x, cell_y)| Some(WorldCoordinates::new(
+                WorldCoordinate::checked_from_num(f64::from(cell_x) + Self::POSITION_OFFSET_CELLS.0)?,
+                WorldCoordinate::checked_from_num(f64::from(cell_y) + Self::POSITION_OFFSET_CELLS.1)?)))
+            .collect()
+    }
+
+    fn print_row(label: &str, accuracy: &ColumnAccuracy) {
+        println!(
+            "{label:>9} {:>9} {:>10} {:>9.4} {:>9.4} {:>9.4} {:>9.4}",
+            accuracy.compared(),
+            accuracy.mismatched(),
+            accuracy.max_wall_height_error(),
+            accuracy.mean_wall_height_error(),
+            accuracy.max_cell_offset_error() * Self::TEXELS_PER_CELL,
+            accuracy.mean_cell_offset_error() * Self::TEXELS_PER_CELL);
+    }
+}
+
+impl WorldCommand for Accuracy {
+    fn run<TWorld: World<WorldCoordinate> + WorldRendering>(self, world: TWorld, size_cells: Option<(u16, u16)>) -> Result<()> {
+        let positions = Self::positions_in(&world, Self::region_for(&world, size_cells));
+        let mut analysis = AccuracyAnalysis::<RaycastingParameters>::new();
+        for position in &positions {
+            for i in 0..Self::ANGLES_PER_POSITION {
+                let raw_angle = (u32::from(i) * 0x1_0000 / u32::from(Self::ANGLES_PER_POSITION)) as u16;
+                let angle = Angle::from_raw(raw_angle.wrapping_add(Self::ANGLE_OFFSET) as i16);
+                analysis.analyse_pose(&world, *position, angle).coalesce_err()?;
+            }
+        }
+
+        println!(
+            "{} poses ({} positions x {} angles) over {} columns",
+            analysis.poses(),
+            positions.len(),
+            Self::ANGLES_PER_POSITION,
+            RaycastingParameters::CANVAS_WIDTH_PIXELS);
+
+        println!("  columns  compared mismatched   height (pixels)     texture (texels)");
+        println!("                                     max      mean       max      mean");
+        for (row, columns) in analysis.columns().chunks(usize::from(Self::COLUMNS_PER_ROW)).enumerate() {
+            let first_column = row * usize::from(Self::COLUMNS_PER_ROW);
+            let accuracy = columns.iter().fold(ColumnAccuracy::default(), ColumnAccuracy::merged);
+            Self::print_row(&format!("{first_column}-{}", first_column + columns.len() - 1), &accuracy);
+        }
+
+        Self::print_row("all", &analysis.overall());
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use notray_engine::raycasting::{CellBitmap, MapChecker, MapIssue, World, WorldRendering};
+
+use crate::{Error, RaycastingParameters, Result, WorldCommand, WorldCoordinate};
+
+pub struct Check;
+
+impl Check {
+    fn describe(issue: &MapIssue) -> String {
+        match *issue {
+            MapIssue::SpawnInWall { cell_x, cell_y } =>
+                format!("({cell_x}, {cell_y}): spawn is inside a wall"),
+
+            MapIssue::SpawnOutsideRegion { cell_x, cell_y } =>
+                format!("({cell_x}, {cell_y}): spawn is outside the map"),
+
+            MapIssue::UnreachableRegion { cell_x, cell_y, region_cells } =>
+                format!("({cell_x}, {cell_y}): region of {region_cells} open cell(s) is unreachable from the spawn"),
+
+            MapIssue::OpenBoundary { cell_x, cell_y } =>
+                format!("({cell_x}, {cell_y}): rays can escape through the edge of the map"),
+
+            MapIssue::UnboundWall { cell_x, cell_y, wall_id } =>
+                format!("({cell_x}, {cell_y}): wall id {wall_id} has no texture"),
+
+            MapIssue::CorridorTooLong { cell_x, cell_y, length_cells, max_ray_cell_probes } =>
+                format!("({cell_x}, {cell_y}): corridor of {length_cells} cells is longer than the {max_ray_cell_probes} cells a ray can probe")
+        }
+    }
+}
+
+impl WorldCommand for Check {
+    fn run<TWorld: World<WorldCoordinate> + WorldRendering>(self, world: TWorld, size_cells: Option<(u16, u16)>) -> Result<()> {
+        let (width_cells, height_cells) = size_cells.ok_or(Error::Str("Only maps with bounds can be checked"))?;
+        let words = vec![0; CellBitmap::<&[u32]>::words_for(width_cells, height_cells)];
+        let mut checker = MapChecker::<RaycastingParameters, _>::new(width_cells, height_cells, words);
+        let summary = checker.check(&world, |issue| println!("{}", Self::describe(&issue)));
+
+        println!(
+            "{width_cells}x{height_cells} cells; {} reachable, {} unreachable, longest corridor {} cells",
+            summary.reachable_cells(),
+            summary.unreachable_cells(),
+            summary.longest_corridor_cells());
+
+        if summary.is_ok() {
+            Ok(())
+        } else {
+            Err(Error::String(format!("{} issue(s) found", summary.issues())))
+        }
+    }
+}
@@ -3,18 +3,19 @@ use std::pin::Pin;
 
 use notcurses::{Notcurses, Plane, Size, Visual, VisualBuilder};
 
-use notray_engine::Colour;
+use notray_engine::{Canvas, Colour, FrameBufferCanvas};
 use notray_engine::raycasting::worlds::assets;
 
 use crate::{Error, Result};
 use super::ResultCoalescing;
 
+// Colour indices are kept alongside the RGBA so that screenshots are of the frame rather than of the terminal
 pub struct NotcursesCanvas<'nc> {
     nc: &'nc RefCell<Notcurses>,
     nc_plane: Plane,
+    frame: FrameBufferCanvas<Vec<Colour>>,
     pixels: Pin<Box<[Pixel]>>,
-    dimensions: Size,
-    width_pixels: u16
+    dimensions: Size
 }
 
 impl<'nc> NotcursesCanvas<'nc> {
@@ -34,23 +35,25 @@ impl<'nc> NotcursesCanvas<'nc> {
             Ok(Self {
                 nc,
                 nc_plane,
+                frame: FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); (width_pixels_i32 * height_pixels_i32) as usize]),
                 pixels: rgba,
-                dimensions: Size::new(width_pixels_i32, height_pixels_i32),
-                width_pixels
+                dimensions: Size::new(width_pixels_i32, height_pixels_i32)
             })
         }
     }
 
     pub fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> Result<()> {
-        let (x, y, width) = (x as usize, y as usize, self.width_pixels as usize);
-        let index = y * width + x;
-
-        let (red, green, blue) = assets::Palette::rgb_for(colour);
-        *self.pixels.get_mut(index).unwrap() = Pixel { red, green, blue, alpha: 0xff };
-        Ok(())
+        self.frame.set_pixel(x, y, colour).coalesce_err()
     }
 
+    pub fn frame(&self) -> &FrameBufferCanvas<Vec<Colour>> { &self.frame }
+
     pub fn blit(&mut self) -> Result<()> {
+        for (pixel, colour) in self.pixels.iter_mut().zip(self.frame.pixels()) {
+            let (red, green, blue) = assets::Palette::rgb_for(*colour);
+            *pixel = Pixel { red, green, blue, alpha: 0xff };
+        }
+
         let mut visual = self.new_visual()?;
         visual
             .blit_child(&self.nc.borrow(), &mut self.nc_plane)
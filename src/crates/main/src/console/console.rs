@@ -1,10 +1,11 @@
 use std::cell::RefCell;
 
 use notray_engine::{Canvas, Pollable, QuitStimuli, Result as EngineResult, Stimuli};
-use notray_engine::raycasting::CameraStimuli;
+use notray_engine::raycasting::{CameraStimuli, MinimapStimuli};
 
 use crate::Result;
-use super::{NotcursesKeyboard, ResultCoalescing, NotcursesScreen};
+use crate::editor::EditorStimuli;
+use super::{GifCapture, NotcursesKeyboard, ResultCoalescing, NotcursesScreen, Screenshots};
 
 pub struct NotcursesConsole<'nc> {
     keyboard: NotcursesKeyboard<'nc>,
@@ -23,14 +24,16 @@ impl<'nc> NotcursesConsole<'nc> {
         })
     }
 
-    pub fn stimuli(&self) -> impl Stimuli + QuitStimuli + CameraStimuli {
+    pub fn stimuli(&self) -> impl Stimuli + QuitStimuli + CameraStimuli + MinimapStimuli + EditorStimuli + Clone {
         self.keyboard.stimuli()
     }
 
     pub fn pollable(&'nc self) -> impl Pollable {
         NotcursesPollable {
             keyboard: self.keyboard.pollable(),
-            screen: self.screen.pollable()
+            screen: self.screen.pollable(),
+            screenshots: Screenshots::new(&self.screen, self.keyboard.stimuli()),
+            gif_capture: GifCapture::new(&self.screen, self.keyboard.stimuli())
         }
     }
 
@@ -39,14 +42,18 @@ impl<'nc> NotcursesConsole<'nc> {
     }
 }
 
-struct NotcursesPollable<K: Pollable, S: Pollable> {
+struct NotcursesPollable<K: Pollable, S: Pollable, P: Pollable, G: Pollable> {
     keyboard: K,
-    screen: S
+    screen: S,
+    screenshots: P,
+    gif_capture: G
 }
 
-impl<K: Pollable, S: Pollable> Pollable for NotcursesPollable<K, S> {
+impl<K: Pollable, S: Pollable, P: Pollable, G: Pollable> Pollable for NotcursesPollable<K, S, P, G> {
     fn poll(&mut self) -> EngineResult<()> {
         self.screen.poll()?;
+        self.screenshots.poll()?;
+        self.gif_capture.poll()?;
         self.keyboard.poll()
     }
 }
@@ -0,0 +1,79 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use notray_engine::{Pollable, Result as EngineResult};
+use notray_engine::raycasting::worlds::assets;
+
+use crate::GifRecorder;
+use super::{timestamped_file_name, NotcursesScreen};
+
+pub trait GifCaptureStimuli {
+    fn should_toggle_gif_capture(&self) -> bool;
+}
+
+/*
+    Starts and stops an animated GIF of what is on the screen; a capture that is still running is finished on quitting.
+    A capture that cannot be written is reported and abandoned, but the game carries on.
+*/
+pub struct GifCapture<'nc, TStimuli: GifCaptureStimuli> {
+    screen: &'nc NotcursesScreen<'nc>,
+    stimuli: TStimuli,
+    path: PathBuf,
+    recorder: Option<GifRecorder<BufWriter<File>>>
+}
+
+impl<'nc, TStimuli: GifCaptureStimuli> GifCapture<'nc, TStimuli> {
+    pub fn new(screen: &'nc NotcursesScreen<'nc>, stimuli: TStimuli) -> Self {
+        Self { screen, stimuli, path: PathBuf::new(), recorder: None }
+    }
+
+    fn start(&mut self) {
+        let canvas = self.screen.canvas_ref();
+        self.path = timestamped_file_name(SystemTime::now(), "gif");
+        let recorder = File::create(&self.path)
+            .and_then(|file| GifRecorder::new(BufWriter::new(file), canvas.frame().width_pixels(), canvas.frame().height_pixels(), assets::Palette::rgb_for));
+
+        match recorder {
+            Ok(recorder) => self.recorder = Some(recorder),
+            Err(error) => self.report(error)
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(recorder) = self.recorder.take() && let Err(error) = recorder.finish() {
+            self.report(error);
+        }
+    }
+
+    fn report(&self, error: impl Display) {
+        eprintln!("{}: {error}", self.path.display());
+    }
+}
+
+impl<'nc, TStimuli: GifCaptureStimuli> Pollable for GifCapture<'nc, TStimuli> {
+    fn poll(&mut self) -> EngineResult<()> {
+        if self.stimuli.should_toggle_gif_capture() {
+            if self.recorder.is_some() {
+                self.stop();
+            } else {
+                self.start();
+            }
+        }
+
+        if let Some(recorder) = &mut self.recorder && let Err(error) = recorder.record(self.screen.canvas_ref().frame()) {
+            self.report(error);
+            self.recorder = None;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'nc, TStimuli: GifCaptureStimuli> Drop for GifCapture<'nc, TStimuli> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
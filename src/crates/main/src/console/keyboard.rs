@@ -5,7 +5,10 @@ use std::thread::{spawn, JoinHandle};
 use notcurses::{Input, InputType, Key, KeyMod, Notcurses, Received};
 
 use notray_engine::{Pollable, QuitStimuli, Result, Stimuli};
-use notray_engine::raycasting::CameraStimuli;
+use notray_engine::raycasting::{CameraStimuli, MinimapStimuli};
+
+use crate::editor::EditorStimuli;
+use super::{GifCaptureStimuli, ScreenshotStimuli};
 
 pub struct NotcursesKeyboard<'nc> {
     _nc: &'nc RefCell<Notcurses>,
@@ -53,7 +56,19 @@ struct KeyboardState {
     right_arrow: KeyState,
     up_arrow: KeyState,
     down_arrow: KeyState,
-    shift: KeyState
+    shift: KeyState,
+    space: KeyState,
+    enter: KeyState,
+    g: KeyState,
+    m: KeyState,
+    p: KeyState,
+    f12: KeyState,
+    open_bracket: KeyState,
+    close_bracket: KeyState,
+    r: KeyState,
+    u: KeyState,
+    w: KeyState,
+    x: KeyState
 }
 
 #[derive(Copy, Clone)]
@@ -89,12 +104,24 @@ impl<'nc> NotcursesKeyboard<'nc> {
                 down_arrow: KeyState::default(),
                 left_arrow: KeyState::default(),
                 right_arrow: KeyState::default(),
-                shift: KeyState::default()
+                shift: KeyState::default(),
+                space: KeyState::default(),
+                enter: KeyState::default(),
+                g: KeyState::default(),
+                m: KeyState::default(),
+                p: KeyState::default(),
+                f12: KeyState::default(),
+                open_bracket: KeyState::default(),
+                close_bracket: KeyState::default(),
+                r: KeyState::default(),
+                u: KeyState::default(),
+                w: KeyState::default(),
+                x: KeyState::default()
             })
         }
     }
 
-    pub fn stimuli(&self) -> impl Stimuli + QuitStimuli + CameraStimuli {
+    pub fn stimuli(&self) -> impl Stimuli + QuitStimuli + CameraStimuli + MinimapStimuli + EditorStimuli + ScreenshotStimuli + GifCaptureStimuli + Clone {
         KeyboardStimuli::new(&self.state)
     }
 
@@ -112,6 +139,18 @@ impl KeyboardState {
         self.left_arrow = Self::reset_key_state(self.left_arrow);
         self.right_arrow = Self::reset_key_state(self.right_arrow);
         self.shift = Self::reset_key_state(self.shift);
+        self.space = Self::reset_key_state(self.space);
+        self.enter = Self::reset_key_state(self.enter);
+        self.g = Self::reset_key_state(self.g);
+        self.m = Self::reset_key_state(self.m);
+        self.p = Self::reset_key_state(self.p);
+        self.f12 = Self::reset_key_state(self.f12);
+        self.open_bracket = Self::reset_key_state(self.open_bracket);
+        self.close_bracket = Self::reset_key_state(self.close_bracket);
+        self.r = Self::reset_key_state(self.r);
+        self.u = Self::reset_key_state(self.u);
+        self.w = Self::reset_key_state(self.w);
+        self.x = Self::reset_key_state(self.x);
     }
 
     fn reset_key_state(state: KeyState) -> KeyState {
@@ -126,6 +165,18 @@ impl KeyboardState {
             Received::Key(Key::Down) => self.down_arrow = Self::set_key_state(self.down_arrow),
             Received::Key(Key::Left) => self.left_arrow = Self::set_key_state(self.left_arrow),
             Received::Key(Key::Right) => self.right_arrow = Self::set_key_state(self.right_arrow),
+            Received::Char(' ') => self.space = Self::set_key_state(self.space),
+            Received::Key(Key::Enter) => self.enter = Self::set_key_state(self.enter),
+            Received::Char('g') | Received::Char('G') => self.g = Self::set_key_state(self.g),
+            Received::Char('m') | Received::Char('M') => self.m = Self::set_key_state(self.m),
+            Received::Char('p') | Received::Char('P') => self.p = Self::set_key_state(self.p),
+            Received::Key(Key::F12) => self.f12 = Self::set_key_state(self.f12),
+            Received::Char('[') => self.open_bracket = Self::set_key_state(self.open_bracket),
+            Received::Char(']') => self.close_bracket = Self::set_key_state(self.close_bracket),
+            Received::Char('r') | Received::Char('R') => self.r = Self::set_key_state(self.r),
+            Received::Char('u') | Received::Char('U') => self.u = Self::set_key_state(self.u),
+            Received::Char('w') | Received::Char('W') => self.w = Self::set_key_state(self.w),
+            Received::Char('x') | Received::Char('X') => self.x = Self::set_key_state(self.x),
             _ => { }
         }
 
@@ -164,6 +215,7 @@ impl<'kb> Pollable for KeyboardPollable<'kb> {
     }
 }
 
+#[derive(Clone)]
 struct KeyboardStimuli<'kb> {
     state: &'kb RefCell<KeyboardState>
 }
@@ -204,3 +256,74 @@ impl<'kb> CameraStimuli for KeyboardStimuli<'kb> {
         self.state.borrow().shift.is_pressed
     }
 }
+
+impl<'kb> MinimapStimuli for KeyboardStimuli<'kb> {
+    fn should_toggle_minimap(&self) -> bool {
+        self.state.borrow().m.is_pressed
+    }
+}
+
+impl<'kb> ScreenshotStimuli for KeyboardStimuli<'kb> {
+    fn should_take_screenshot(&self) -> bool {
+        // Only on the initial press, so that holding the key (or its auto-repeat) does not fill the directory
+        let state = self.state.borrow();
+        (state.p.is_pressed && !state.p.was_pressed) || (state.f12.is_pressed && !state.f12.was_pressed)
+    }
+}
+
+impl<'kb> GifCaptureStimuli for KeyboardStimuli<'kb> {
+    fn should_toggle_gif_capture(&self) -> bool {
+        let state = self.state.borrow();
+        state.g.is_pressed && !state.g.was_pressed
+    }
+}
+
+impl<'kb> EditorStimuli for KeyboardStimuli<'kb> {
+    fn should_move_cursor_up(&self) -> bool {
+        self.state.borrow().up_arrow.is_pressed
+    }
+
+    fn should_move_cursor_down(&self) -> bool {
+        self.state.borrow().down_arrow.is_pressed
+    }
+
+    fn should_move_cursor_left(&self) -> bool {
+        self.state.borrow().left_arrow.is_pressed
+    }
+
+    fn should_move_cursor_right(&self) -> bool {
+        self.state.borrow().right_arrow.is_pressed
+    }
+
+    fn should_paint(&self) -> bool {
+        self.state.borrow().space.is_pressed
+    }
+
+    fn should_select_previous_cell_type(&self) -> bool {
+        self.state.borrow().open_bracket.is_pressed
+    }
+
+    fn should_select_next_cell_type(&self) -> bool {
+        self.state.borrow().close_bracket.is_pressed
+    }
+
+    fn should_place_spawn(&self) -> bool {
+        self.state.borrow().x.is_pressed
+    }
+
+    fn should_undo(&self) -> bool {
+        self.state.borrow().u.is_pressed
+    }
+
+    fn should_redo(&self) -> bool {
+        self.state.borrow().r.is_pressed
+    }
+
+    fn should_save(&self) -> bool {
+        self.state.borrow().w.is_pressed
+    }
+
+    fn should_preview(&self) -> bool {
+        self.state.borrow().enter.is_pressed
+    }
+}
@@ -10,12 +10,18 @@ use canvas::*;
 mod console;
 use console::*;
 
+mod gif_capture;
+use gif_capture::*;
+
 mod keyboard;
 use keyboard::*;
 
 mod screen;
 use screen::*;
 
+mod screenshot;
+use screenshot::*;
+
 pub struct Notcurses {
     nc: RefCell<notcurses::Notcurses>
 }
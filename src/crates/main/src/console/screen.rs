@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
 
 use notcurses::Notcurses;
 
@@ -29,6 +29,10 @@ impl<'nc> NotcursesScreen<'nc> {
     pub fn canvas(&'nc self) -> impl Canvas {
         SharedCanvas { canvas: &self.canvas }
     }
+
+    pub fn canvas_ref(&self) -> Ref<'_, NotcursesCanvas<'nc>> {
+        self.canvas.borrow()
+    }
 }
 
 struct SharedCanvas<'c> {
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use notray_engine::{FrameExport, Pollable, Result as EngineResult};
+use notray_engine::raycasting::worlds::assets;
+
+use super::NotcursesScreen;
+
+pub trait ScreenshotStimuli {
+    fn should_take_screenshot(&self) -> bool;
+}
+
+/*
+    Polled after the frame has been blitted, so the screenshot is exactly what was on the screen; it is taken
+    from the frame's colour indices at native resolution, whatever blitter the terminal happens to be using.
+*/
+pub struct Screenshots<'nc, TStimuli: ScreenshotStimuli> {
+    screen: &'nc NotcursesScreen<'nc>,
+    stimuli: TStimuli
+}
+
+impl<'nc, TStimuli: ScreenshotStimuli> Screenshots<'nc, TStimuli> {
+    pub fn new(screen: &'nc NotcursesScreen<'nc>, stimuli: TStimuli) -> Self {
+        Self { screen, stimuli }
+    }
+}
+
+impl<'nc, TStimuli: ScreenshotStimuli> Pollable for Screenshots<'nc, TStimuli> {
+    fn poll(&mut self) -> EngineResult<()> {
+        if self.stimuli.should_take_screenshot() {
+            let path = timestamped_file_name(SystemTime::now(), "png");
+            let canvas = self.screen.canvas_ref();
+            // A screenshot that cannot be written is not worth ending the game over
+            if let Err(error) = FrameExport::new(canvas.frame(), assets::Palette::rgb_for).save(&path) {
+                eprintln!("{}: {error}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Named by UTC time down to the millisecond, so that files sort in the order that they were taken
+pub fn timestamped_file_name(time: SystemTime, extension: &str) -> PathBuf {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (days, seconds_of_day) = (since_epoch.as_secs() / 86400, since_epoch.as_secs() % 86400);
+    let (year, month, day) = civil_from_days(days);
+    PathBuf::from(format!(
+        "notray-{year:04}{month:02}{day:02}-{:02}{:02}{:02}-{:03}.{extension}",
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_millis()))
+}
+
+// Howard Hinnant's days-to-civil algorithm, for UTC dates without pulling in a date and time crate
+fn civil_from_days(days_since_epoch: u64) -> (u64, u64, u64) {
+    let days = days_since_epoch + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests;
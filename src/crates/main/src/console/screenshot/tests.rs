@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::*;
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+fn at(days: u64, seconds_of_day: u64, millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY + seconds_of_day) + Duration::from_millis(millis)
+}
+
+#[test]
+fn days_since_the_epoch_start_on_the_first_of_january_1970() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(31), (1970, 2, 1));
+    assert_eq!(timestamped_file_name(UNIX_EPOCH, "png"), PathBuf::from("notray-19700101-000000-000.png"));
+}
+
+#[test]
+fn leap_days_are_only_in_the_leap_years() {
+    assert_eq!(civil_from_days(19782), (2024, 2, 29));
+    assert_eq!(civil_from_days(19783), (2024, 3, 1));
+    assert_eq!(civil_from_days(11016), (2000, 2, 29));
+    assert_eq!(civil_from_days(47540), (2100, 2, 28));
+    assert_eq!(civil_from_days(47541), (2100, 3, 1));
+}
+
+#[test]
+fn the_last_millisecond_of_a_year_is_followed_by_the_first_of_the_next() {
+    assert_eq!(civil_from_days(10956), (1999, 12, 31));
+    assert_eq!(civil_from_days(10957), (2000, 1, 1));
+
+    let last = at(10956, SECONDS_PER_DAY - 1, 999);
+    assert_eq!(timestamped_file_name(last, "gif"), PathBuf::from("notray-19991231-235959-999.gif"));
+    assert_eq!(timestamped_file_name(last + Duration::from_millis(1), "gif"), PathBuf::from("notray-20000101-000000-000.gif"));
+}
+
+#[test]
+fn times_before_the_epoch_are_named_as_the_epoch() {
+    assert_eq!(timestamped_file_name(UNIX_EPOCH - Duration::from_secs(1), "png"), PathBuf::from("notray-19700101-000000-000.png"));
+}
@@ -0,0 +1,324 @@
+use std::path::{Path, PathBuf};
+
+use notray_engine::{Canvas, FrameRenderer, GameLoop, OnStimuli, Pollable, QuitStimuli, Result as EngineResult, Stimuli};
+use notray_engine::raycasting::{Angle, CellFlags, CellTag, ProjectionPlaneParameters, Scene, TextureRegistry, TextureTable, World};
+use notray_engine::raycasting::worlds::{assets, GridWorld};
+
+use crate::{console, Error, RaycastingParameters, Result, ResultCoalescing, WorldCoordinate};
+use crate::tiled::{TiledExport, TiledImport};
+use super::{Edit, History, MapView, Spawn};
+
+pub trait EditorStimuli {
+    fn should_move_cursor_up(&self) -> bool;
+
+    fn should_move_cursor_down(&self) -> bool;
+
+    fn should_move_cursor_left(&self) -> bool;
+
+    fn should_move_cursor_right(&self) -> bool;
+
+    fn should_paint(&self) -> bool;
+
+    fn should_select_previous_cell_type(&self) -> bool;
+
+    fn should_select_next_cell_type(&self) -> bool;
+
+    fn should_place_spawn(&self) -> bool;
+
+    fn should_undo(&self) -> bool;
+
+    fn should_redo(&self) -> bool;
+
+    fn should_save(&self) -> bool;
+
+    fn should_preview(&self) -> bool;
+}
+
+pub struct Editor {
+    world: GridWorld<Vec<CellTag>>,
+    path: PathBuf,
+    view: MapView,
+    textures: TextureTable<'static>,
+    cell_types: Vec<CellTag>,
+    selected: usize,
+    cursor_x: u16,
+    cursor_y: u16,
+    history: History,
+    is_modified: bool,
+    is_quitting: bool,
+    failed_saves: Vec<Error>,
+    kept_path: Option<PathBuf>,
+    has_saved: bool
+}
+
+impl Editor {
+    const NEW_MAP_SIZE_CELLS: u16 = 32;
+    const NEW_MAP_WALL_ID: u16 = 1;
+
+    pub fn open(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new(Self::new_world(), path.to_path_buf()));
+        }
+
+        let import = TiledImport::from_file(path)?;
+        for warning in import.warnings() {
+            eprintln!("{}: {warning}", path.display());
+        }
+
+        // Saving over a map with parts that were not imported would lose them, so the edits are saved alongside it
+        if import.warnings().is_empty() {
+            Ok(Self::new(import.into_world(), path.to_path_buf()))
+        } else {
+            Ok(Self { kept_path: Some(path.to_path_buf()), ..Self::new(import.into_world(), Self::edited_path_for(path)) })
+        }
+    }
+
+    fn edited_path_for(path: &Path) -> PathBuf {
+        let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+        file_name.push(".edited");
+        if let Some(extension) = path.extension() {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+
+        path.with_file_name(file_name)
+    }
+
+    pub fn new(world: GridWorld<Vec<CellTag>>, path: PathBuf) -> Self {
+        let textures = assets::TEXTURES;
+        let walls = (1..textures.len() as u16)
+            .map(CellTag::from_wall_id)
+            .filter(|cell| textures.texture_for(*cell).is_some());
+
+        let cell_types = core::iter::once(CellTag::from_wall_id(0))
+            .chain(walls)
+            .chain(core::iter::once(CellTag::from_wall_id(0).with_door_id(0)))
+            .collect();
+
+        let (cursor_x, cursor_y) = world.spawn_cell();
+        Self {
+            world,
+            path,
+            view: MapView::new(RaycastingParameters::CANVAS_WIDTH_PIXELS, RaycastingParameters::CANVAS_HEIGHT_PIXELS),
+            textures,
+            cell_types,
+            selected: 1,
+            cursor_x,
+            cursor_y,
+            history: History::new(),
+            is_modified: false,
+            is_quitting: false,
+            failed_saves: Vec::new(),
+            kept_path: None,
+            has_saved: false
+        }
+    }
+
+    fn new_world() -> GridWorld<Vec<CellTag>> {
+        let size = Self::NEW_MAP_SIZE_CELLS;
+        let mut world = GridWorld::new(size, size, vec![CellTag::from_wall_id(0); usize::from(size) * usize::from(size)]);
+        for index in 0..size {
+            for (cell_x, cell_y) in [(index, 0), (index, size - 1), (0, index), (size - 1, index)] {
+                world.set_cell(cell_x, cell_y, CellTag::from_wall_id(Self::NEW_MAP_WALL_ID));
+            }
+        }
+
+        world.set_spawn(1, 1, Angle::FACING_SOUTH);
+        world
+    }
+
+    pub fn run(mut self) -> Result<()> {
+        let result = self.edit();
+
+        // Failed saves are only readable once the console has given the terminal back
+        for error in &self.failed_saves {
+            eprintln!("{error}");
+        }
+
+        if self.has_saved && let Some(kept_path) = &self.kept_path {
+            eprintln!("Saved to '{}' rather than over '{}', which has parts that the editor does not keep", self.path.display(), kept_path.display());
+        }
+
+        result
+    }
+
+    fn edit(&mut self) -> Result<()> {
+        let nc = console::Notcurses::new()?;
+        let console = nc.console(RaycastingParameters::CANVAS_WIDTH_PIXELS, RaycastingParameters::CANVAS_HEIGHT_PIXELS)?;
+        let stimuli = console.stimuli();
+        let mut pollable = console.pollable();
+        let mut canvas = console.canvas();
+
+        loop {
+            self.render_frame_onto(&mut canvas).coalesce_err()?;
+            pollable.poll().coalesce_err()?;
+            if stimuli.should_quit() {
+                if !self.is_modified || self.is_quitting {
+                    return Ok(());
+                }
+
+                // Unsaved changes need a second press to be thrown away; the cursor turns red in the meantime
+                self.is_quitting = true;
+                continue;
+            }
+
+            if stimuli.should_preview() {
+                self.is_quitting = false;
+                let mut scene = Scene::<RaycastingParameters, _>::new(self.preview_world());
+                GameLoop::new(&mut scene, &stimuli, &mut pollable, &mut canvas).run().coalesce_err()?;
+
+                // The key that ended the preview must not end the editor as well
+                pollable.poll().coalesce_err()?;
+                continue;
+            }
+
+            self.on_stimuli(&stimuli).coalesce_err()?;
+        }
+    }
+
+    fn preview_world(&self) -> GridWorld<&[CellTag]> {
+        let mut world = GridWorld::new(self.world.width_cells(), self.world.height_cells(), self.world.cells());
+        world.set_spawn(self.cursor_x, self.cursor_y, self.spawn().angle);
+        world
+    }
+
+    fn spawn(&self) -> Spawn {
+        let (cell_x, cell_y) = self.world.spawn_cell();
+        Spawn { cell_x, cell_y, angle: World::<WorldCoordinate>::spawn_angle(&self.world) }
+    }
+
+    fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let moved = |cursor: u16, delta: i32, extent: u16| (i32::from(cursor) + delta).clamp(0, i32::from(extent) - 1) as u16;
+        self.cursor_x = moved(self.cursor_x, dx, self.world.width_cells());
+        self.cursor_y = moved(self.cursor_y, dy, self.world.height_cells());
+    }
+
+    fn paint(&mut self) {
+        let Some(before) = self.world.cell_at(self.cursor_x, self.cursor_y) else { return; };
+        let painted = self.cell_types[self.selected];
+        let painted = match painted.door_id() {
+            Some(_) => painted.with_door_id(self.next_door_id()),
+            None => painted
+        };
+
+        let after = painted
+            .with_floor_texture_id(before.floor_texture_id())
+            .with_ceiling_texture_id(before.ceiling_texture_id())
+            .with_light_level(before.light_level())
+            .with_flags(CellFlags::from_bits(before.flags().bits()));
+
+        if after != before {
+            self.apply(Edit::Cell { cell_x: self.cursor_x, cell_y: self.cursor_y, before, after });
+        }
+    }
+
+    fn next_door_id(&self) -> u8 {
+        self.world.cells()
+            .iter()
+            .filter_map(|cell| cell.door_id())
+            .max()
+            .map_or(0, |door_id| door_id.wrapping_add(1))
+    }
+
+    fn place_spawn(&mut self) {
+        // Placing the spawn where it already is turns it instead, so that it can face any of the four ways
+        let before = self.spawn();
+        let after = if (before.cell_x, before.cell_y) == (self.cursor_x, self.cursor_y) {
+            Spawn { angle: before.angle + Angle::QUADRANT_AXIS_0_1, ..before }
+        } else {
+            Spawn { cell_x: self.cursor_x, cell_y: self.cursor_y, ..before }
+        };
+
+        self.apply(Edit::Spawn { before, after });
+    }
+
+    fn apply(&mut self, edit: Edit) {
+        self.history.record(edit);
+        self.apply_without_history(edit);
+    }
+
+    fn apply_without_history(&mut self, edit: Edit) {
+        match edit {
+            Edit::Cell { cell_x, cell_y, after, .. } => self.world.set_cell(cell_x, cell_y, after),
+            Edit::Spawn { after, .. } => self.world.set_spawn(after.cell_x, after.cell_y, after.angle)
+        }
+
+        self.is_modified = true;
+    }
+
+    fn is_editing<TStimuli: EditorStimuli>(stimuli: &TStimuli) -> bool {
+        stimuli.should_move_cursor_up() ||
+            stimuli.should_move_cursor_down() ||
+            stimuli.should_move_cursor_left() ||
+            stimuli.should_move_cursor_right() ||
+            stimuli.should_paint() ||
+            stimuli.should_select_previous_cell_type() ||
+            stimuli.should_select_next_cell_type() ||
+            stimuli.should_place_spawn() ||
+            stimuli.should_undo() ||
+            stimuli.should_redo() ||
+            stimuli.should_save()
+    }
+
+    fn save(&mut self) -> Result<()> {
+        TiledExport::from_world(&self.world).save(&self.path)?;
+        self.is_modified = false;
+        self.has_saved = true;
+        Ok(())
+    }
+}
+
+impl<TStimuli: Stimuli + EditorStimuli> OnStimuli<TStimuli> for Editor {
+    fn on_stimuli(&mut self, stimuli: &TStimuli) -> EngineResult<()> {
+        // The quit confirmation stands until it is answered; doing anything else in the meantime cancels it
+        if Self::is_editing(stimuli) {
+            self.is_quitting = false;
+        }
+
+        if stimuli.should_move_cursor_up() { self.move_cursor(0, -1); }
+        if stimuli.should_move_cursor_down() { self.move_cursor(0, 1); }
+        if stimuli.should_move_cursor_left() { self.move_cursor(-1, 0); }
+        if stimuli.should_move_cursor_right() { self.move_cursor(1, 0); }
+
+        if stimuli.should_select_previous_cell_type() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.cell_types.len() - 1);
+        }
+
+        if stimuli.should_select_next_cell_type() {
+            self.selected = (self.selected + 1) % self.cell_types.len();
+        }
+
+        if stimuli.should_paint() {
+            self.paint();
+        }
+
+        if stimuli.should_place_spawn() {
+            self.place_spawn();
+        }
+
+        if stimuli.should_undo() && let Some(edit) = self.history.undo() {
+            self.apply_without_history(edit);
+        }
+
+        if stimuli.should_redo() && let Some(edit) = self.history.redo() {
+            self.apply_without_history(edit);
+        }
+
+        // A failed save keeps the edits and the editor; the error is reported when the editor closes
+        if stimuli.should_save() && let Err(error) = self.save() {
+            self.failed_saves.push(error);
+        }
+
+        Ok(())
+    }
+}
+
+impl<TCanvas: Canvas> FrameRenderer<TCanvas> for Editor {
+    fn render_frame_onto(&mut self, canvas: &mut TCanvas) -> EngineResult<()> {
+        self.view.render_map_onto(canvas, &self.world, (self.cursor_x, self.cursor_y), self.is_quitting, &self.textures)?;
+        self.view.render_palette_onto(canvas, &self.cell_types, self.selected, &self.textures)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+
+use notray_engine::raycasting::{CellFlags, CellTag};
+use notray_engine::raycasting::worlds::GridWorld;
+
+use crate::tiled::{TiledExport, TiledImport};
+use super::*;
+
+fn room() -> GridWorld<Vec<CellTag>> {
+    let mut world = GridWorld::new(4, 3, vec![CellTag::from_wall_id(1); 12]);
+    world.set_cell(1, 1, CellTag::from_wall_id(0).with_light_level(120).with_flags(CellFlags::SECRET));
+    world.set_cell(2, 1, CellTag::from_wall_id(0).with_door_id(3).with_light_level(60));
+    world.set_spawn(1, 1, Angle::FACING_SOUTH);
+    world
+}
+
+fn scratch_path(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("notray-editor-{}-{name}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    directory.join("map.json")
+}
+
+#[test]
+fn maps_that_imported_without_warnings_are_saved_over() {
+    let path = scratch_path("clean");
+    TiledExport::from_world(&room()).save(&path).unwrap();
+
+    let mut editor = Editor::open(&path).unwrap();
+    editor.save().unwrap();
+
+    assert_eq!(editor.path, path);
+    assert!(editor.kept_path.is_none());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn maps_that_imported_with_warnings_are_saved_alongside_rather_than_over() {
+    let path = scratch_path("warnings");
+    let mut map: serde_json::Value = serde_json::from_str(&TiledExport::from_world(&room()).to_json()).unwrap();
+    map["properties"] = serde_json::json!([{ "name": "author", "type": "string", "value": "someone" }]);
+    let original = map.to_string();
+    std::fs::write(&path, &original).unwrap();
+
+    let mut editor = Editor::open(&path).unwrap();
+    editor.save().unwrap();
+
+    let edited_path = path.with_file_name("map.edited.json");
+    assert_eq!((&editor.path, editor.kept_path.as_ref()), (&edited_path, Some(&path)));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+    assert_eq!(TiledImport::from_file(&edited_path).unwrap().into_world().cells(), room().cells());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn painting_keeps_the_light_level_and_flags_of_the_cell() {
+    let mut editor = Editor::new(room(), PathBuf::from("unsaved.json"));
+    editor.selected = 1;
+
+    editor.paint();
+    editor.move_cursor(1, 0);
+    editor.paint();
+
+    let painted = [editor.world.cell_at(1, 1).unwrap(), editor.world.cell_at(2, 1).unwrap()];
+    assert_eq!(painted, [
+        CellTag::from_wall_id(1).with_light_level(120).with_flags(CellFlags::SECRET),
+        CellTag::from_wall_id(1).with_light_level(60)]);
+}
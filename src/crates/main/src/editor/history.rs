@@ -0,0 +1,54 @@
+use notray_engine::raycasting::{Angle, CellTag};
+
+#[derive(Copy, Clone)]
+pub struct Spawn {
+    pub cell_x: u16,
+    pub cell_y: u16,
+    pub angle: Angle
+}
+
+#[derive(Copy, Clone)]
+pub enum Edit {
+    Cell { cell_x: u16, cell_y: u16, before: CellTag, after: CellTag },
+    Spawn { before: Spawn, after: Spawn }
+}
+
+impl Edit {
+    pub fn reversed(self) -> Self {
+        match self {
+            Edit::Cell { cell_x, cell_y, before, after } => Edit::Cell { cell_x, cell_y, before: after, after: before },
+            Edit::Spawn { before, after } => Edit::Spawn { before: after, after: before }
+        }
+    }
+}
+
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new()
+        }
+    }
+
+    pub fn record(&mut self, edit: Edit) {
+        self.undo.push(edit);
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo.pop()?;
+        self.redo.push(edit);
+        Some(edit.reversed())
+    }
+
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo.pop()?;
+        self.undo.push(edit);
+        Some(edit)
+    }
+}
@@ -0,0 +1,135 @@
+use fixed::types::U0F16;
+
+use notray_engine::{Canvas, Colour, Result};
+use notray_engine::raycasting::{Angle, CellTag, Texture, TextureCoordinates, TextureRegistry, TextureTable, World, WorldAbsoluteCoordinate};
+use notray_engine::raycasting::worlds::{assets, GridWorld};
+
+/*
+    A top-down view drawn onto the same pixel canvas as the raycaster, one cell to a small square of pixels,
+    so that wall textures can be previewed in miniature rather than needing any text in the terminal.
+*/
+pub struct MapView {
+    width_pixels: u16,
+    height_pixels: u16
+}
+
+impl MapView {
+    pub const CELL_PIXELS: u16 = 8;
+    const PALETTE_HEIGHT_PIXELS: u16 = 32;
+    const PALETTE_ENTRY_PIXELS: u16 = 32;
+    const PALETTE_PREVIEW_PIXELS: u16 = 24;
+
+    const EMPTY: Colour = Colour::new(28);
+    const GRID: Colour = Colour::new(24);
+    const UNBOUND_WALL: Colour = Colour::new(72);
+    const DOOR: Colour = Colour::new(168);
+    const DOOR_FRAME: Colour = Colour::new(184);
+    const SPAWN: Colour = Colour::new(64);
+    const CURSOR: Colour = Colour::new(80);
+    const CURSOR_QUITTING: Colour = Colour::new(64);
+
+    pub const fn new(width_pixels: u16, height_pixels: u16) -> Self {
+        Self { width_pixels, height_pixels }
+    }
+
+    pub const fn visible_cells(&self) -> (u16, u16) {
+        (self.width_pixels / Self::CELL_PIXELS, (self.height_pixels - Self::PALETTE_HEIGHT_PIXELS) / Self::CELL_PIXELS)
+    }
+
+    pub fn render_map_onto<TCanvas, TCells>(&self, canvas: &mut TCanvas, world: &GridWorld<TCells>, cursor: (u16, u16), is_quitting: bool, textures: &TextureTable) -> Result<()>
+        where
+            TCanvas: Canvas,
+            TCells: AsRef<[CellTag]> {
+
+        let (visible_x, visible_y) = self.visible_cells();
+        let origin_x = Self::viewport_origin(cursor.0, visible_x, world.width_cells());
+        let origin_y = Self::viewport_origin(cursor.1, visible_y, world.height_cells());
+        let spawn = world.spawn_cell();
+        let spawn_angle = World::<WorldAbsoluteCoordinate>::spawn_angle(world);
+        for y in 0..visible_y * Self::CELL_PIXELS {
+            for x in 0..self.width_pixels {
+                let (cell_x, cell_y) = (origin_x + x / Self::CELL_PIXELS, origin_y + y / Self::CELL_PIXELS);
+                let (u, v) = (x % Self::CELL_PIXELS, y % Self::CELL_PIXELS);
+                let is_edge = u == 0 || v == 0 || u == Self::CELL_PIXELS - 1 || v == Self::CELL_PIXELS - 1;
+
+                let colour = match world.cell_at(cell_x, cell_y) {
+                    None => assets::Palette::BLACK,
+                    Some(_) if (cell_x, cell_y) == cursor && is_edge => if is_quitting { Self::CURSOR_QUITTING } else { Self::CURSOR },
+                    Some(_) if (cell_x, cell_y) == spawn && Self::is_spawn_marker(spawn_angle, u, v) => Self::SPAWN,
+                    Some(cell) => Self::cell_colour(cell, textures, Self::fraction(u, Self::CELL_PIXELS), Self::fraction(v, Self::CELL_PIXELS), is_edge)
+                };
+
+                canvas.set_pixel(x, y, colour)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn render_palette_onto<TCanvas: Canvas>(&self, canvas: &mut TCanvas, cell_types: &[CellTag], selected: usize, textures: &TextureTable) -> Result<()> {
+        let top = self.height_pixels - Self::PALETTE_HEIGHT_PIXELS;
+        let margin = (Self::PALETTE_ENTRY_PIXELS - Self::PALETTE_PREVIEW_PIXELS) / 2;
+        for y in 0..Self::PALETTE_HEIGHT_PIXELS {
+            for x in 0..self.width_pixels {
+                let (index, u, v) = (usize::from(x / Self::PALETTE_ENTRY_PIXELS), x % Self::PALETTE_ENTRY_PIXELS, y);
+                let is_preview = (margin..margin + Self::PALETTE_PREVIEW_PIXELS).contains(&u) && (margin..margin + Self::PALETTE_PREVIEW_PIXELS).contains(&v);
+                let is_selection = index == selected && !is_preview && (1..Self::PALETTE_ENTRY_PIXELS - 1).contains(&u) && (1..Self::PALETTE_HEIGHT_PIXELS - 1).contains(&v);
+
+                let colour = match cell_types.get(index) {
+                    Some(cell) if is_preview => {
+                        let (u, v) = (u - margin, v - margin);
+                        let is_edge = u == 0 || v == 0 || u == Self::PALETTE_PREVIEW_PIXELS - 1 || v == Self::PALETTE_PREVIEW_PIXELS - 1;
+                        Self::cell_colour(*cell, textures, Self::fraction(u, Self::PALETTE_PREVIEW_PIXELS), Self::fraction(v, Self::PALETTE_PREVIEW_PIXELS), is_edge)
+                    },
+
+                    Some(_) if is_selection => Self::CURSOR,
+                    _ => assets::Palette::BLACK
+                };
+
+                canvas.set_pixel(x, top + y, colour)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn viewport_origin(cursor: u16, visible: u16, extent: u16) -> u16 {
+        cursor.saturating_sub(visible / 2).min(extent.saturating_sub(visible))
+    }
+
+    fn fraction(numerator: u16, denominator: u16) -> U0F16 {
+        U0F16::from_bits(((u32::from(numerator) << 16) / u32::from(denominator)) as u16)
+    }
+
+    fn cell_colour(cell: CellTag, textures: &TextureTable, u: U0F16, v: U0F16, is_edge: bool) -> Colour {
+        if cell.door_id().is_some() {
+            if is_edge { Self::DOOR_FRAME } else { Self::DOOR }
+        } else if cell.wall_id() != 0 {
+            textures
+                .texture_for(cell)
+                .and_then(|texture| texture.get_texel_at(TextureCoordinates::new(u, v)))
+                .unwrap_or(Self::UNBOUND_WALL)
+        } else if is_edge {
+            Self::GRID
+        } else {
+            Self::EMPTY
+        }
+    }
+
+    fn is_spawn_marker(angle: Angle, u: u16, v: u16) -> bool {
+        // A dot in the middle of the cell with a nub on the side that the camera will be facing
+        let (centre, nub) = (3..5, 1..3);
+        let is_centre = centre.contains(&u) && centre.contains(&v);
+        let is_nub = if angle == Angle::FACING_NORTH {
+            centre.contains(&u) && nub.contains(&v)
+        } else if angle == Angle::FACING_EAST {
+            centre.contains(&v) && nub.contains(&u)
+        } else if angle == Angle::FACING_WEST {
+            centre.contains(&v) && (5..7).contains(&u)
+        } else {
+            centre.contains(&u) && (5..7).contains(&v)
+        };
+
+        is_centre || is_nub
+    }
+}
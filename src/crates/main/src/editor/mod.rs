@@ -0,0 +1,8 @@
+mod editor;
+pub use editor::*;
+
+mod history;
+use history::*;
+
+mod map_view;
+use map_view::*;
@@ -1,18 +1,59 @@
+use std::path::PathBuf;
+
 use notray_engine::{
     raycasting::{
+        CameraStimuli,
+        CellBitmap,
+        CellTag,
+        EngineParameters,
+        Exploration,
+        Minimap,
+        MinimapOverlay,
+        MinimapStimuli,
         ProjectionPlaneParameters,
         Scene,
+        StimuliRecorder,
+        StimuliRecording,
+        StimuliReplay,
+        World,
+        WorldCoordinate as _,
+        WorldRendering,
         worlds
     },
+    Canvas,
+    FrameRenderer,
     GameLoop,
+    OnStimuli,
+    Pollable,
+    QuitStimuli,
+    Stimuli,
     raycasting_parameters
 };
 
+mod accuracy;
+use accuracy::*;
+
+mod check;
+use check::*;
+
 mod console;
 
+mod editor;
+
+mod options;
+use options::*;
+
+mod recording;
+use recording::*;
+
+mod render;
+use render::*;
+
 mod result;
 use result::*;
 
+mod tiled;
+
 raycasting_parameters! {
     pub struct RaycastingParameters {
         canvas: 400 x 240 pixels;
@@ -22,19 +63,186 @@ raycasting_parameters! {
     }
 }
 
+type WorldCoordinate = <RaycastingParameters as EngineParameters>::Coordinate;
+
 fn main() -> Result<()> {
-    let nc = console::Notcurses::new()?;
-    let console = nc.console(RaycastingParameters::CANVAS_WIDTH_PIXELS, RaycastingParameters::CANVAS_HEIGHT_PIXELS)?;
-    let stimuli = console.stimuli();
-    let mut pollable = console.pollable();
-    let mut canvas = console.canvas();
-
-    let mut scene = Scene::<RaycastingParameters, _>::new(worlds::World1::new());
-    let mut game_loop = GameLoop::new(
-        &mut scene,
-        &stimuli,
-        &mut pollable,
-        &mut canvas);
-
-    game_loop.run().coalesce_err()
+    let options = Options::from_args(std::env::args())?;
+    match options.command() {
+        CommandOption::Play => with_world(&options, Play::new(options.record().cloned(), options.record_input().cloned(), options.replay().cloned())),
+        CommandOption::Check => with_world(&options, Check),
+        CommandOption::Edit(path) => editor::Editor::open(path)?.run(),
+        CommandOption::Render => {
+            if options.out().is_none() && options.record().is_none() {
+                return Err(Error::Str("Rendering needs an '--out' or a '--record' file"));
+            }
+
+            with_world(&options, Render::new(options.pose(), options.out().cloned(), options.record().cloned()))
+        },
+        CommandOption::Accuracy => with_world(&options, Accuracy)
+    }
+}
+
+trait WorldCommand {
+    fn run<TWorld: World<WorldCoordinate> + WorldRendering>(self, world: TWorld, size_cells: Option<(u16, u16)>) -> Result<()>;
+}
+
+const GENERATED_SIZE_CELLS: u16 = 63;
+
+const INFINITE_CACHED_CHUNKS: usize = worlds::cached_chunks_for_reach(RaycastingParameters::MAX_RAY_CELL_PROBES);
+
+fn with_world(options: &Options, command: impl WorldCommand) -> Result<()> {
+    let generated_size = Some((GENERATED_SIZE_CELLS, GENERATED_SIZE_CELLS));
+    match options.world() {
+        WorldOption::World1 => command.run(worlds::World1::new(), Some((worlds::World1::WIDTH_CELLS, worlds::World1::HEIGHT_CELLS))),
+        WorldOption::Infinite => command.run(worlds::InfiniteWorld::<_, INFINITE_CACHED_CHUNKS>::new(worlds::Corridors, options.seed()), None),
+        WorldOption::Generated(GeneratorOption::Maze) => command.run(generated(worlds::Maze, options.seed()), generated_size),
+        WorldOption::Generated(GeneratorOption::Dungeon) => command.run(generated(worlds::Dungeon::new().with_doors(true), options.seed()), generated_size),
+        WorldOption::Generated(GeneratorOption::Caves) => command.run(generated(worlds::Caves::new(), options.seed()), generated_size),
+        WorldOption::Tiled(path) => {
+            let import = tiled::TiledImport::from_file(path)?;
+            for warning in import.warnings() {
+                eprintln!("{}: {warning}", path.display());
+            }
+
+            let world = import.into_world();
+            let size_cells = Some((world.width_cells(), world.height_cells()));
+            command.run(world, size_cells)
+        }
+    }
+}
+
+fn generated(generator: impl worlds::GridGenerator, seed: u64) -> worlds::GridWorld<Vec<CellTag>> {
+    let cells = usize::from(GENERATED_SIZE_CELLS) * usize::from(GENERATED_SIZE_CELLS);
+    let mut world = worlds::GridWorld::new(GENERATED_SIZE_CELLS, GENERATED_SIZE_CELLS, vec![CellTag::from_wall_id(0); cells]);
+    generator.generate(&mut world, seed);
+    world
+}
+
+struct Play {
+    record: Option<PathBuf>,
+    record_input: Option<PathBuf>,
+    replay: Option<PathBuf>
+}
+
+impl Play {
+    const MINIMAP_SIZE_PIXELS: u16 = 96;
+    const MINIMAP_MARGIN_PIXELS: u16 = 4;
+    const MINIMAP_RAY_COLUMN_STRIDE: u16 = 8;
+
+    fn new(record: Option<PathBuf>, record_input: Option<PathBuf>, replay: Option<PathBuf>) -> Self {
+        Self { record, record_input, replay }
+    }
+
+    // Created before the terminal is taken over, so that a bad path is reported without any mess
+    fn recorder(&self) -> Result<Option<Y4mRecorder<Box<dyn std::io::Write>>>> {
+        self.record
+            .as_ref()
+            .map(|path| {
+                if path.as_os_str() == "-" {
+                    Err(Error::Str("Standard output is the terminal when playing; record to a file instead"))
+                } else {
+                    Y4mRecorder::create(path, RaycastingParameters::CANVAS_WIDTH_PIXELS, RaycastingParameters::CANVAS_HEIGHT_PIXELS, worlds::assets::Palette::rgb_for)
+                }
+            })
+            .transpose()
+    }
+
+    fn replay(&self) -> Result<Option<StimuliReplay>> {
+        self.replay
+            .as_ref()
+            .map(|path| {
+                let bytes = std::fs::read(path).map_err(|error| Error::String(format!("{}: {error}", path.display())))?;
+                StimuliRecording::from_bytes(&bytes).coalesce_err().map(|recording| StimuliReplay::new(&recording))
+            })
+            .transpose()
+    }
+
+    fn save_input(&self, input_recorder: Option<StimuliRecorder>) -> Result<()> {
+        let (Some(input_recorder), Some(path)) = (input_recorder, &self.record_input) else {
+            return Ok(());
+        };
+
+        let recording = input_recorder.into_recording();
+        std::fs::write(path, recording.to_bytes()).map_err(|error| Error::String(format!("{}: {error}", path.display())))?;
+        println!("Recorded {} ticks of input to {}", recording.ticks(), path.display());
+        Ok(())
+    }
+
+    fn finish_video(&self, recorder: Option<Y4mRecorder<Box<dyn std::io::Write>>>) -> Result<()> {
+        let (Some(recorder), Some(path)) = (recorder, &self.record) else {
+            return Ok(());
+        };
+
+        let frames = recorder.frames();
+        recorder.finish().map_err(|error| Error::String(format!("{}: {error}", path.display())))?;
+        println!("Recorded {frames} frames to {}", path.display());
+        Ok(())
+    }
+
+    fn run_game_loop<TScene, TStimuli, TPollable, TCanvas>(
+        scene: &mut TScene,
+        stimuli: TStimuli,
+        pollable: TPollable,
+        mut canvas: TCanvas,
+        input_recorder: Option<&StimuliRecorder>) -> Result<()>
+        where
+            TScene: FrameRenderer<TCanvas> + OnStimuli<TStimuli>,
+            TStimuli: Stimuli + QuitStimuli + CameraStimuli + MinimapStimuli + Clone,
+            TPollable: Pollable,
+            TCanvas: Canvas {
+
+        // Whatever drives the scene is what gets recorded, so a replay can itself be re-recorded
+        let mut pollable = Tee::new(pollable, input_recorder.map(|recorder| recorder.pollable(stimuli.clone())));
+        GameLoop::new(scene, &stimuli, &mut pollable, &mut canvas).run().coalesce_err()
+    }
+}
+
+impl WorldCommand for Play {
+    fn run<TWorld: World<WorldCoordinate> + WorldRendering>(self, world: TWorld, size_cells: Option<(u16, u16)>) -> Result<()> {
+        // Unbounded worlds are explored in local coordinates, which are forgotten whenever the world is rebased
+        let local_extent_cells = WorldCoordinate::MAX.cell_int().saturating_add(1);
+        let (width_cells, height_cells) = size_cells.unwrap_or((local_extent_cells, local_extent_cells));
+        let words = CellBitmap::<&[u32]>::words_for(width_cells, height_cells);
+        let exploration = Exploration::new(width_cells, height_cells, vec![0; words], vec![0; words]);
+
+        let recorder = self.recorder()?;
+        let replay = self.replay()?;
+        let input_recorder = self.record_input.as_ref().map(|_| StimuliRecorder::new());
+        let played = {
+            let nc = console::Notcurses::new()?;
+            let console = nc.console(RaycastingParameters::CANVAS_WIDTH_PIXELS, RaycastingParameters::CANVAS_HEIGHT_PIXELS)?;
+            let pollable = Tee::new(console.pollable(), recorder.as_ref().map(Y4mRecorder::pollable));
+            let canvas = Tee::new(console.canvas(), recorder.as_ref().map(Y4mRecorder::canvas));
+
+            let minimap = Minimap::new(
+                RaycastingParameters::CANVAS_WIDTH_PIXELS - Self::MINIMAP_SIZE_PIXELS - Self::MINIMAP_MARGIN_PIXELS,
+                Self::MINIMAP_MARGIN_PIXELS,
+                Self::MINIMAP_SIZE_PIXELS,
+                Self::MINIMAP_SIZE_PIXELS)
+                .with_rays(Self::MINIMAP_RAY_COLUMN_STRIDE);
+
+            let mut scene = MinimapOverlay::new(Scene::<RaycastingParameters, _>::new(world).with_ray_observer(exploration), minimap);
+            let played = match &replay {
+                Some(replay) => Self::run_game_loop(&mut scene, replay.stimuli(), Tee::new(pollable, replay.pollable()), canvas, input_recorder.as_ref()),
+                None => Self::run_game_loop(&mut scene, console.stimuli(), pollable, canvas, input_recorder.as_ref())
+            };
+
+            played.map(|_| scene.scene().ray_observer().statistics(scene.scene().world()))
+        };
+
+        // The input is what reproduces a failed game, so it is written, and the video finished, however the game ended
+        let input_saved = self.save_input(input_recorder);
+        let video_finished = self.finish_video(recorder);
+        let statistics = played?;
+        input_saved?;
+        video_finished?;
+
+        println!(
+            "Explored {}% of the map and found {} of {} secrets",
+            statistics.percent_explored(),
+            statistics.secrets_found(),
+            statistics.secrets());
+
+        Ok(())
+    }
 }
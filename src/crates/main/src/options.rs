@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use crate::{Error, Result};
+
+pub enum CommandOption {
+    Play,
+    Check,
+    Edit(PathBuf),
+    Render,
+    Accuracy
+}
+
+pub enum WorldOption {
+    World1,
+    Infinite,
+    Generated(GeneratorOption),
+    Tiled(PathBuf)
+}
+
+pub enum GeneratorOption {
+    Maze,
+    Dungeon,
+    Caves
+}
+
+impl core::str::FromStr for WorldOption {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "world1" => Ok(Self::World1),
+            "infinite" => Ok(Self::Infinite),
+            _ if value.ends_with(".tmj") || value.ends_with(".json") => Ok(Self::Tiled(PathBuf::from(value))),
+            _ => value
+                .parse()
+                .map(Self::Generated)
+                .map_err(|_| Error::String(format!("Unrecognised map '{value}'")))
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct PoseOption {
+    x_cells: f64,
+    y_cells: f64,
+    angle_degrees: f64
+}
+
+impl PoseOption {
+    pub fn x_cells(&self) -> f64 { self.x_cells }
+
+    pub fn y_cells(&self) -> f64 { self.y_cells }
+
+    pub fn angle_degrees(&self) -> f64 { self.angle_degrees }
+}
+
+impl core::str::FromStr for PoseOption {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let components = value
+            .split(',')
+            .map(|component| component.trim().parse::<f64>())
+            .collect::<core::result::Result<Vec<_>, _>>()
+            .map_err(|_| Error::String(format!("Unrecognised pose '{value}'")))?;
+
+        match components[..] {
+            [x_cells, y_cells, angle_degrees] => Ok(Self { x_cells, y_cells, angle_degrees }),
+            _ => Err(Error::String(format!("Pose '{value}' should be 'x,y,angle'")))
+        }
+    }
+}
+
+impl core::str::FromStr for GeneratorOption {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "maze" => Ok(Self::Maze),
+            "dungeon" => Ok(Self::Dungeon),
+            "caves" => Ok(Self::Caves),
+            _ => Err(Error::String(format!("Unrecognised generator '{value}'")))
+        }
+    }
+}
+
+pub struct Options {
+    command: CommandOption,
+    world: WorldOption,
+    seed: u64,
+    pose: Option<PoseOption>,
+    out: Option<PathBuf>,
+    record: Option<PathBuf>,
+    record_input: Option<PathBuf>,
+    replay: Option<PathBuf>
+}
+
+impl Options {
+    const DEFAULT_SEED: u64 = 0x6e6f_7472_6179;
+
+    pub fn from_args(args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self {
+            command: CommandOption::Play,
+            world: WorldOption::World1,
+            seed: Self::DEFAULT_SEED,
+            pose: None,
+            out: None,
+            record: None,
+            record_input: None,
+            replay: None
+        };
+
+        let mut args = args.skip(1).peekable();
+        match args.next_if(|arg| arg == "check" || arg == "edit" || arg == "render" || arg == "accuracy").as_deref() {
+            Some("check") => {
+                options.command = CommandOption::Check;
+                options.world = Self::value_for("check", args.next())?;
+            },
+
+            Some("edit") => options.command = CommandOption::Edit(Self::value_for("edit", args.next())?),
+            Some("render") => options.command = CommandOption::Render,
+            Some("accuracy") => options.command = CommandOption::Accuracy,
+            _ => { }
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--infinite" => options.world = WorldOption::Infinite,
+                "--generate" => options.world = WorldOption::Generated(Self::value_for("--generate", args.next())?),
+                "--map" => options.world = WorldOption::Tiled(Self::value_for("--map", args.next())?),
+                "--seed" => options.seed = Self::value_for("--seed", args.next())?,
+                "--pose" => options.pose = Some(Self::value_for("--pose", args.next())?),
+                "--out" => options.out = Some(Self::value_for("--out", args.next())?),
+                "--record" => options.record = Some(Self::value_for("--record", args.next())?),
+                "--record-input" => options.record_input = Some(Self::value_for("--record-input", args.next())?),
+                "--replay" => options.replay = Some(Self::value_for("--replay", args.next())?),
+                _ => return Err(Error::String(format!("Unrecognised option '{arg}'")))
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn value_for<T: core::str::FromStr>(option: &str, value: Option<String>) -> Result<T> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| Error::String(format!("Option '{option}' needs a valid value")))
+    }
+
+    pub fn command(&self) -> &CommandOption { &self.command }
+
+    pub fn world(&self) -> &WorldOption { &self.world }
+
+    pub fn seed(&self) -> u64 { self.seed }
+
+    pub fn pose(&self) -> Option<PoseOption> { self.pose }
+
+    pub fn out(&self) -> Option<&PathBuf> { self.out.as_ref() }
+
+    pub fn record(&self) -> Option<&PathBuf> { self.record.as_ref() }
+
+    pub fn record_input(&self) -> Option<&PathBuf> { self.record_input.as_ref() }
+
+    pub fn replay(&self) -> Option<&PathBuf> { self.replay.as_ref() }
+}
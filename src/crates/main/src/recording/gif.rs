@@ -0,0 +1,159 @@
+use std::io::{self, Write};
+use std::time::Instant;
+
+use notray_engine::{Colour, FrameBufferCanvas};
+
+use super::LzwEncoder;
+
+/*
+    Writes an animated GIF straight from the colour indices, with the palette as the global colour table so
+    that no quantisation is needed.  Each frame after the first only covers the rectangle that changed, and
+    is left in place for the next one to be drawn over.
+*/
+pub struct GifEncoder<TWriter: Write> {
+    writer: TWriter,
+    previous: Option<Vec<u8>>,
+    width_pixels: u16,
+    height_pixels: u16
+}
+
+impl<TWriter: Write> GifEncoder<TWriter> {
+    const LOGICAL_SCREEN_FLAGS_GLOBAL_256_COLOURS: u8 = 0xf7;
+    const GRAPHIC_CONTROL_FLAGS_DO_NOT_DISPOSE: u8 = 0x04;
+    const MAX_SUB_BLOCK_BYTES: usize = 255;
+
+    pub fn new(mut writer: TWriter, width_pixels: u16, height_pixels: u16, palette: fn(Colour) -> (u8, u8, u8)) -> io::Result<Self> {
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&width_pixels.to_le_bytes())?;
+        writer.write_all(&height_pixels.to_le_bytes())?;
+        writer.write_all(&[Self::LOGICAL_SCREEN_FLAGS_GLOBAL_256_COLOURS, 0, 0])?;
+        for index in 0..=u8::MAX {
+            let (red, green, blue) = palette(Colour::new(index));
+            writer.write_all(&[red, green, blue])?;
+        }
+
+        // Loop forever
+        writer.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+        Ok(Self { writer, previous: None, width_pixels, height_pixels })
+    }
+
+    pub fn write_frame<TPixels: AsRef<[Colour]>>(&mut self, frame: &FrameBufferCanvas<TPixels>, delay_centiseconds: u16) -> io::Result<()> {
+        if (frame.width_pixels(), frame.height_pixels()) != (self.width_pixels, self.height_pixels) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame dimensions differ from the animation's"));
+        }
+
+        let indices: Vec<u8> = frame.pixels().iter().map(|colour| colour.as_index()).collect();
+        let (left, top, width_pixels, height_pixels) = self.changed_rectangle(&indices);
+
+        self.writer.write_all(&[0x21, 0xf9, 0x04, Self::GRAPHIC_CONTROL_FLAGS_DO_NOT_DISPOSE])?;
+        self.writer.write_all(&delay_centiseconds.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+
+        self.writer.write_all(&[0x2c])?;
+        for value in [left, top, width_pixels, height_pixels] {
+            self.writer.write_all(&value.to_le_bytes())?;
+        }
+
+        self.writer.write_all(&[0, LzwEncoder::MIN_CODE_BITS])?;
+        let rows = indices
+            .chunks_exact(usize::from(self.width_pixels))
+            .skip(usize::from(top))
+            .take(usize::from(height_pixels));
+
+        let compressed = LzwEncoder::new().encode(rows.flat_map(|row| row[usize::from(left)..usize::from(left + width_pixels)].iter().copied()));
+        for sub_block in compressed.chunks(Self::MAX_SUB_BLOCK_BYTES) {
+            self.writer.write_all(&[sub_block.len() as u8])?;
+            self.writer.write_all(sub_block)?;
+        }
+
+        self.writer.write_all(&[0])?;
+        self.previous = Some(indices);
+        Ok(())
+    }
+
+    // An unchanged frame still needs an image, so it becomes a single pixel redrawn in the same colour
+    fn changed_rectangle(&self, indices: &[u8]) -> (u16, u16, u16, u16) {
+        let Some(previous) = &self.previous else {
+            return (0, 0, self.width_pixels, self.height_pixels);
+        };
+
+        let width_pixels = usize::from(self.width_pixels);
+        let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+        for (i, _) in indices.iter().zip(previous).enumerate().filter(|(_, (index, previous))| index != previous) {
+            let (x, y) = (i % width_pixels, i / width_pixels);
+            (left, top, right, bottom) = (left.min(x), top.min(y), right.max(x), bottom.max(y));
+        }
+
+        if left == usize::MAX {
+            (0, 0, 1, 1)
+        } else {
+            (left as u16, top as u16, (right - left + 1) as u16, (bottom - top + 1) as u16)
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<TWriter> {
+        self.writer.write_all(&[0x3b])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/*
+    A frame's delay is only known when the next, different, frame arrives, so the most recent frame is held
+    back until then; delays are rounded against the total elapsed time, so that the rounding does not drift.
+*/
+pub struct GifRecorder<TWriter: Write> {
+    encoder: GifEncoder<TWriter>,
+    held: Option<FrameBufferCanvas<Vec<Colour>>>,
+    started_at: Instant,
+    written_centiseconds: u64
+}
+
+impl<TWriter: Write> GifRecorder<TWriter> {
+    // Most viewers play anything shorter than this far more slowly, rather than faster
+    const MIN_DELAY_CENTISECONDS: u16 = 2;
+
+    pub fn new(writer: TWriter, width_pixels: u16, height_pixels: u16, palette: fn(Colour) -> (u8, u8, u8)) -> io::Result<Self> {
+        Ok(Self {
+            encoder: GifEncoder::new(writer, width_pixels, height_pixels, palette)?,
+            held: None,
+            started_at: Instant::now(),
+            written_centiseconds: 0
+        })
+    }
+
+    pub fn record<TPixels: AsRef<[Colour]>>(&mut self, frame: &FrameBufferCanvas<TPixels>) -> io::Result<()> {
+        let now = Instant::now();
+        if let Some(held) = &self.held {
+            if held.pixels().iter().map(|colour| colour.as_index()).eq(frame.pixels().iter().map(|colour| colour.as_index())) {
+                return Ok(());
+            }
+
+            self.release(now)?;
+        } else {
+            self.started_at = now;
+        }
+
+        self.held = Some(FrameBufferCanvas::new(frame.width_pixels(), frame.height_pixels(), frame.pixels().to_vec()));
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<TWriter> {
+        self.release(Instant::now())?;
+        self.encoder.finish()
+    }
+
+    fn release(&mut self, now: Instant) -> io::Result<()> {
+        let Some(held) = self.held.take() else {
+            return Ok(());
+        };
+
+        let elapsed_centiseconds = (now.duration_since(self.started_at).as_millis() / 10) as u64;
+        let delay_centiseconds = elapsed_centiseconds
+            .saturating_sub(self.written_centiseconds)
+            .clamp(u64::from(Self::MIN_DELAY_CENTISECONDS), u64::from(u16::MAX));
+
+        self.written_centiseconds += delay_centiseconds;
+        self.encoder.write_frame(&held, delay_centiseconds as u16)
+    }
+}
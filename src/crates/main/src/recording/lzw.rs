@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+/*
+    The variable-length LZW flavour that GIF uses, for 8-bit colour indices; codes are packed least
+    significant bit first and the table is cleared, rather than frozen, when it reaches 4096 entries.
+*/
+pub struct LzwEncoder {
+    table: HashMap<(u16, u8), u16>,
+    next_code: u16,
+    code_bits: u8,
+    bits: u32,
+    bit_count: u8,
+    bytes: Vec<u8>
+}
+
+impl LzwEncoder {
+    pub const MIN_CODE_BITS: u8 = 8;
+    const CLEAR_CODE: u16 = 1 << Self::MIN_CODE_BITS;
+    const END_OF_INFORMATION_CODE: u16 = Self::CLEAR_CODE + 1;
+    const FIRST_FREE_CODE: u16 = Self::CLEAR_CODE + 2;
+    const MAX_CODE_BITS: u8 = 12;
+    const MAX_CODES: u16 = 1 << Self::MAX_CODE_BITS;
+
+    pub fn new() -> Self {
+        Self {
+            table: HashMap::new(),
+            next_code: Self::FIRST_FREE_CODE,
+            code_bits: Self::MIN_CODE_BITS + 1,
+            bits: 0,
+            bit_count: 0,
+            bytes: Vec::new()
+        }
+    }
+
+    pub fn encode(mut self, indices: impl IntoIterator<Item = u8>) -> Vec<u8> {
+        self.write_code(Self::CLEAR_CODE);
+
+        let mut indices = indices.into_iter();
+        let Some(first) = indices.next() else {
+            self.write_code(Self::END_OF_INFORMATION_CODE);
+            return self.flush();
+        };
+
+        let mut prefix = u16::from(first);
+        for index in indices {
+            if let Some(&code) = self.table.get(&(prefix, index)) {
+                prefix = code;
+            } else {
+                self.write_code(prefix);
+                self.grow(Some((prefix, index)));
+                prefix = u16::from(index);
+            }
+        }
+
+        self.write_code(prefix);
+        self.grow(None);
+        self.write_code(Self::END_OF_INFORMATION_CODE);
+        self.flush()
+    }
+
+    // The decoder adds its entries one code behind the encoder, so the code width grows one entry later too
+    fn grow(&mut self, entry: Option<(u16, u8)>) {
+        if self.next_code < Self::MAX_CODES {
+            if let Some(entry) = entry {
+                self.table.insert(entry, self.next_code);
+            }
+
+            self.next_code += 1;
+            if self.next_code > 1 << self.code_bits && self.code_bits < Self::MAX_CODE_BITS {
+                self.code_bits += 1;
+            }
+        } else if entry.is_some() {
+            self.write_code(Self::CLEAR_CODE);
+            self.table.clear();
+            self.next_code = Self::FIRST_FREE_CODE;
+            self.code_bits = Self::MIN_CODE_BITS + 1;
+        }
+    }
+
+    fn write_code(&mut self, code: u16) {
+        self.bits |= u32::from(code) << self.bit_count;
+        self.bit_count += self.code_bits;
+        while self.bit_count >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    fn flush(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+
+        self.bytes
+    }
+}
+
+impl Default for LzwEncoder {
+    fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,14 @@
+mod gif;
+pub use gif::*;
+
+mod lzw;
+use lzw::*;
+
+mod tee;
+pub use tee::*;
+
+mod y4m;
+pub use y4m::*;
+
+#[cfg(test)]
+mod tests;
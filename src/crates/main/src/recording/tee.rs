@@ -0,0 +1,27 @@
+use notray_engine::{Canvas, Colour, Pollable, Result as EngineResult};
+
+// Sends the frame to two places, such as the terminal and a recording; the first is always polled first
+pub struct Tee<TFirst, TSecond> {
+    first: TFirst,
+    second: TSecond
+}
+
+impl<TFirst, TSecond> Tee<TFirst, TSecond> {
+    pub fn new(first: TFirst, second: TSecond) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<TFirst: Canvas, TSecond: Canvas> Canvas for Tee<TFirst, TSecond> {
+    fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> EngineResult<()> {
+        self.first.set_pixel(x, y, colour)?;
+        self.second.set_pixel(x, y, colour)
+    }
+}
+
+impl<TFirst: Pollable, TSecond: Pollable> Pollable for Tee<TFirst, TSecond> {
+    fn poll(&mut self) -> EngineResult<()> {
+        self.first.poll()?;
+        self.second.poll()
+    }
+}
@@ -0,0 +1,240 @@
+use notray_engine::{Canvas, Colour, FrameBufferCanvas, Pollable};
+
+use super::*;
+
+const WIDTH_PIXELS: u16 = 128;
+const HEIGHT_PIXELS: u16 = 96;
+
+struct Image {
+    left: u16,
+    top: u16,
+    width_pixels: u16,
+    height_pixels: u16,
+    delay_centiseconds: u16,
+    screen: Vec<u8>
+}
+
+// Just enough of a decoder for what the encoder writes; returns the codes, and how often the table was cleared after the first code
+fn lzw_decode(bytes: &[u8], min_code_bits: u8) -> (Vec<u8>, usize) {
+    let clear_code = 1_usize << min_code_bits;
+    let end_of_information_code = clear_code + 1;
+    let initial_table = || (0..=end_of_information_code).map(|code| vec![code as u8]).collect::<Vec<_>>();
+
+    let (mut table, mut code_bits, mut previous) = (initial_table(), min_code_bits + 1, None::<Vec<u8>>);
+    let (mut bits, mut bit_count, mut bytes) = (0_u32, 0_u8, bytes.iter());
+    let (mut decoded, mut clears) = (Vec::new(), 0);
+    loop {
+        while bit_count < code_bits {
+            bits |= u32::from(*bytes.next().expect("end of information code")) << bit_count;
+            bit_count += 8;
+        }
+
+        let code = (bits & ((1 << code_bits) - 1)) as usize;
+        (bits, bit_count) = (bits >> code_bits, bit_count - code_bits);
+        if code == clear_code {
+            clears += usize::from(previous.is_some() || !decoded.is_empty());
+            (table, code_bits, previous) = (initial_table(), min_code_bits + 1, None);
+            continue;
+        }
+
+        if code == end_of_information_code {
+            return (decoded, clears);
+        }
+
+        let entry = match (table.get(code), &previous) {
+            (Some(entry), _) => entry.clone(),
+            (None, Some(previous)) if code == table.len() => [previous.as_slice(), &previous[..1]].concat(),
+            _ => panic!("code {code} is not in the table of {}", table.len())
+        };
+
+        decoded.extend_from_slice(&entry);
+        if let Some(previous) = previous && table.len() < 4096 {
+            table.push([previous.as_slice(), &entry[..1]].concat());
+        }
+
+        if table.len() == 1 << code_bits && code_bits < 12 {
+            code_bits += 1;
+        }
+
+        previous = Some(entry);
+    }
+}
+
+fn gif_decode(bytes: &[u8]) -> Vec<Image> {
+    let le = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    assert_eq!(&bytes[..6], b"GIF89a");
+    assert_eq!((le(6), le(8)), (WIDTH_PIXELS, HEIGHT_PIXELS));
+
+    let mut screen = vec![0; usize::from(WIDTH_PIXELS) * usize::from(HEIGHT_PIXELS)];
+    let (mut images, mut delay_centiseconds, mut at) = (Vec::new(), 0, 13 + 256 * 3);
+    let sub_blocks = |mut at: usize| {
+        let mut data = Vec::new();
+        while bytes[at] != 0 {
+            data.extend_from_slice(&bytes[at + 1..at + 1 + usize::from(bytes[at])]);
+            at += 1 + usize::from(bytes[at]);
+        }
+
+        (data, at + 1)
+    };
+
+    loop {
+        match bytes[at] {
+            0x21 => {
+                if bytes[at + 1] == 0xf9 {
+                    delay_centiseconds = le(at + 4);
+                }
+
+                at = sub_blocks(at + 2).1;
+            },
+
+            0x2c => {
+                let (left, top, width_pixels, height_pixels) = (le(at + 1), le(at + 3), le(at + 5), le(at + 7));
+                let (compressed, next) = sub_blocks(at + 11);
+                let (indices, _) = lzw_decode(&compressed, bytes[at + 10]);
+                assert_eq!(indices.len(), usize::from(width_pixels) * usize::from(height_pixels));
+
+                for (row, row_indices) in indices.chunks_exact(usize::from(width_pixels)).enumerate() {
+                    let start = (usize::from(top) + row) * usize::from(WIDTH_PIXELS) + usize::from(left);
+                    screen[start..start + row_indices.len()].copy_from_slice(row_indices);
+                }
+
+                images.push(Image { left, top, width_pixels, height_pixels, delay_centiseconds, screen: screen.clone() });
+                at = next;
+            },
+
+            0x3b => return images,
+            byte => panic!("unexpected block 0x{byte:02x} at {at}")
+        }
+    }
+}
+
+fn noise(count: usize, seed: u32) -> Vec<u8> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+fn frame_of(indices: &[u8]) -> FrameBufferCanvas<Vec<Colour>> {
+    FrameBufferCanvas::new(WIDTH_PIXELS, HEIGHT_PIXELS, indices.iter().copied().map(Colour::new).collect())
+}
+
+fn with_rectangle(indices: &[u8], left: u16, top: u16, width_pixels: u16, height_pixels: u16, index: u8) -> Vec<u8> {
+    let mut indices = indices.to_vec();
+    for y in top..top + height_pixels {
+        for x in left..left + width_pixels {
+            indices[usize::from(y) * usize::from(WIDTH_PIXELS) + usize::from(x)] = index;
+        }
+    }
+
+    indices
+}
+
+#[test]
+fn lzw_round_trips_through_table_clears() {
+    let indices = noise(20_000, 0x1234_5678);
+    let (decoded, clears) = lzw_decode(&LzwEncoder::new().encode(indices.iter().copied()), LzwEncoder::MIN_CODE_BITS);
+
+    assert_eq!(decoded, indices);
+    assert!(clears >= 2, "only {clears} table clear(s)");
+}
+
+#[test]
+fn lzw_round_trips_runs_and_short_inputs() {
+    let runs: Vec<u8> = (0..=u8::MAX).flat_map(|index| core::iter::repeat_n(index, usize::from(index) * 7)).collect();
+    for indices in [Vec::new(), vec![42], vec![7, 7], vec![0; 100_000], runs] {
+        assert_eq!(lzw_decode(&LzwEncoder::new().encode(indices.iter().copied()), LzwEncoder::MIN_CODE_BITS).0, indices);
+    }
+}
+
+#[test]
+fn gif_frames_decode_to_what_was_encoded_and_only_cover_what_changed() {
+    let noisy = noise(usize::from(WIDTH_PIXELS) * usize::from(HEIGHT_PIXELS), 0x9e37_79b9);
+    let boxed = with_rectangle(&noisy, 10, 20, 30, 15, 3);
+    let two_boxes = with_rectangle(&with_rectangle(&boxed, 100, 5, 4, 4, 200), 2, 90, 6, 6, 201);
+    let frames = [&noisy, &boxed, &boxed, &two_boxes];
+
+    let mut encoder = GifEncoder::new(Vec::new(), WIDTH_PIXELS, HEIGHT_PIXELS, |colour| (colour.as_index(), 0, 0)).unwrap();
+    for (delay_centiseconds, indices) in frames.iter().enumerate() {
+        encoder.write_frame(&frame_of(indices), delay_centiseconds as u16 + 2).unwrap();
+    }
+
+    let gif = encoder.finish().unwrap();
+    assert_eq!(&gif[13..19], &[0, 0, 0, 1, 0, 0]);
+
+    let images = gif_decode(&gif);
+    let rectangles: Vec<_> = images.iter().map(|image| (image.left, image.top, image.width_pixels, image.height_pixels)).collect();
+    assert_eq!(rectangles, [(0, 0, WIDTH_PIXELS, HEIGHT_PIXELS), (10, 20, 30, 15), (0, 0, 1, 1), (2, 5, 102, 91)]);
+    assert_eq!(images.iter().map(|image| image.delay_centiseconds).collect::<Vec<_>>(), [2, 3, 4, 5]);
+
+    for (image, indices) in images.iter().zip(frames) {
+        assert!(image.screen == *indices);
+    }
+}
+
+#[test]
+fn gif_frames_of_the_wrong_size_are_refused() {
+    let mut encoder = GifEncoder::new(Vec::new(), WIDTH_PIXELS, HEIGHT_PIXELS, |colour| (colour.as_index(), 0, 0)).unwrap();
+    let frame = FrameBufferCanvas::new(WIDTH_PIXELS, HEIGHT_PIXELS - 1, vec![Colour::new(0); usize::from(WIDTH_PIXELS) * usize::from(HEIGHT_PIXELS - 1)]);
+
+    assert_eq!(encoder.write_frame(&frame, 2).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+fn primaries(colour: Colour) -> (u8, u8, u8) {
+    match colour.as_index() {
+        1 => (255, 0, 0),
+        2 => (255, 255, 255),
+        _ => (0, 0, 0)
+    }
+}
+
+#[test]
+fn y4m_streams_start_with_a_header_for_limited_range_420() {
+    let stream = Y4mRecorder::new(Vec::new(), 320, 200, primaries).unwrap().finish().unwrap();
+
+    assert_eq!(stream, b"YUV4MPEG2 W320 H200 F30:1 Ip A1:1 C420jpeg XCOLORRANGE=LIMITED\n");
+}
+
+#[test]
+fn y4m_frames_have_full_size_luma_and_chroma_rounded_up_to_whole_blocks() {
+    let (width_pixels, height_pixels) = (5, 3);
+    let mut indices = vec![Colour::new(0); 15];
+    (indices[0], indices[14], indices[1]) = (Colour::new(1), Colour::new(1), Colour::new(2));
+
+    let recorder = Y4mRecorder::new(Vec::new(), width_pixels, height_pixels, primaries).unwrap();
+    recorder.record(&FrameBufferCanvas::new(width_pixels, height_pixels, indices)).unwrap();
+    assert_eq!(recorder.frames(), 1);
+
+    let stream = recorder.finish().unwrap();
+    let header_bytes = stream.iter().position(|&byte| byte == b'\n').unwrap() + 1;
+    let (frame_header, planes) = stream[header_bytes..].split_at(6);
+    assert_eq!(frame_header, b"FRAME\n");
+    assert_eq!(planes.len(), 5 * 3 + 2 * (3 * 2));
+
+    let (luma, chroma) = planes.split_at(15);
+    assert_eq!(luma, [82, 235, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 82]);
+
+    // The first block averages red with white and black, which are both neutral; the last has only its corner pixel, which is red
+    let (blue_difference, red_difference) = chroma.split_at(6);
+    assert_eq!(blue_difference, [119, 128, 128, 128, 128, 90]);
+    assert_eq!(red_difference, [156, 128, 128, 128, 128, 240]);
+}
+
+#[test]
+fn y4m_frames_drawn_on_the_canvas_are_written_when_polled() {
+    let recorder = Y4mRecorder::new(Vec::new(), 4, 2, primaries).unwrap();
+    recorder.canvas().set_pixel(3, 1, Colour::new(2)).unwrap();
+    recorder.pollable().poll().unwrap();
+    recorder.pollable().poll().unwrap();
+    assert_eq!(recorder.frames(), 2);
+
+    let stream = recorder.finish().unwrap();
+    let (header_bytes, frame_bytes) = (stream.iter().position(|&byte| byte == b'\n').unwrap() + 1, 6 + 4 * 2 + 2 * 2);
+    assert_eq!(stream.len(), header_bytes + 2 * frame_bytes);
+    assert_eq!(&stream[stream.len() - frame_bytes..][..6 + 8], b"FRAME\n\x10\x10\x10\x10\x10\x10\x10\xeb");
+}
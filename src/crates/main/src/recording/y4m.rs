@@ -0,0 +1,156 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use notray_engine::{Canvas, Colour, FrameBufferCanvas, Pollable, Result as EngineResult};
+
+use crate::{Error, Result};
+
+/*
+    Appends every polled frame to a YUV4MPEG2 stream, for feeding into an encoder offline.  The colour indices
+    are converted through the palette to BT.601 limited-range YCbCr once, up front, and the chroma is averaged
+    over each 2x2 block of pixels to give the 4:2:0 sampling that encoders expect by default.
+*/
+pub struct Y4mRecorder<TWriter: Write> {
+    stream: RefCell<Y4mStream<TWriter>>
+}
+
+impl Y4mRecorder<Box<dyn Write>> {
+    // A path of '-' is standard output, so that the stream can be piped straight into an encoder
+    pub fn create(path: &Path, width_pixels: u16, height_pixels: u16, palette: fn(Colour) -> (u8, u8, u8)) -> Result<Self> {
+        let writer: Box<dyn Write> = if path.as_os_str() == "-" {
+            Box::new(BufWriter::new(io::stdout().lock()))
+        } else {
+            Box::new(BufWriter::new(File::create(path).map_err(|error| Error::String(format!("{}: {error}", path.display())))?))
+        };
+
+        Self::new(writer, width_pixels, height_pixels, palette)
+            .map_err(|error| Error::String(format!("{}: {error}", path.display())))
+    }
+}
+
+impl<TWriter: Write> Y4mRecorder<TWriter> {
+    // The game loop is not timed, so the frame rate is only nominal; encoders can be told to assume another
+    const FRAMES_PER_SECOND: u32 = 30;
+
+    pub fn new(mut writer: TWriter, width_pixels: u16, height_pixels: u16, palette: fn(Colour) -> (u8, u8, u8)) -> io::Result<Self> {
+        writeln!(
+            writer,
+            "YUV4MPEG2 W{width_pixels} H{height_pixels} F{}:1 Ip A1:1 C420jpeg XCOLORRANGE=LIMITED",
+            Self::FRAMES_PER_SECOND)?;
+
+        let pixels = usize::from(width_pixels) * usize::from(height_pixels);
+        Ok(Self {
+            stream: RefCell::new(Y4mStream {
+                frame: FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); pixels]),
+                encoder: Y4mEncoder {
+                    writer,
+                    width_pixels,
+                    height_pixels,
+                    palette: core::array::from_fn(|index| Y4mEncoder::<TWriter>::ycbcr_for(palette(Colour::new(index as u8)))),
+                    planes: Vec::with_capacity(pixels * 3 / 2),
+                    frames: 0
+                }
+            })
+        })
+    }
+
+    pub fn canvas(&self) -> impl Canvas + '_ {
+        SharedStream { stream: &self.stream }
+    }
+
+    pub fn pollable(&self) -> impl Pollable + '_ {
+        SharedStream { stream: &self.stream }
+    }
+
+    // For frames rendered elsewhere, such as by a FrameBufferCanvas, rather than through this recorder's canvas
+    pub fn record<TPixels: AsRef<[Colour]>>(&self, frame: &FrameBufferCanvas<TPixels>) -> io::Result<()> {
+        self.stream.borrow_mut().encoder.write_frame(frame)
+    }
+
+    pub fn frames(&self) -> u32 { self.stream.borrow().encoder.frames }
+
+    pub fn finish(self) -> io::Result<TWriter> {
+        let mut encoder = self.stream.into_inner().encoder;
+        encoder.writer.flush()?;
+        Ok(encoder.writer)
+    }
+}
+
+struct Y4mStream<TWriter: Write> {
+    frame: FrameBufferCanvas<Vec<Colour>>,
+    encoder: Y4mEncoder<TWriter>
+}
+
+struct Y4mEncoder<TWriter: Write> {
+    writer: TWriter,
+    width_pixels: u16,
+    height_pixels: u16,
+    palette: [[u8; 3]; 256],
+    planes: Vec<u8>,
+    frames: u32
+}
+
+impl<TWriter: Write> Y4mEncoder<TWriter> {
+    const LUMA: usize = 0;
+    const CHROMA: [usize; 2] = [1, 2];
+
+    fn ycbcr_for((red, green, blue): (u8, u8, u8)) -> [u8; 3] {
+        let (red, green, blue) = (i32::from(red), i32::from(green), i32::from(blue));
+        let luma = ((66 * red + 129 * green + 25 * blue + 128) >> 8) + 16;
+        let blue_difference = ((-38 * red - 74 * green + 112 * blue + 128) >> 8) + 128;
+        let red_difference = ((112 * red - 94 * green - 18 * blue + 128) >> 8) + 128;
+        [luma as u8, blue_difference as u8, red_difference as u8]
+    }
+
+    fn write_frame<TPixels: AsRef<[Colour]>>(&mut self, frame: &FrameBufferCanvas<TPixels>) -> io::Result<()> {
+        if (frame.width_pixels(), frame.height_pixels()) != (self.width_pixels, self.height_pixels) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame dimensions differ from the stream's"));
+        }
+
+        let (width_pixels, height_pixels) = (usize::from(self.width_pixels), usize::from(self.height_pixels));
+        let pixels = frame.pixels();
+        let ycbcr_at = |x: usize, y: usize| self.palette[usize::from(pixels[y * width_pixels + x].as_index())];
+
+        self.planes.clear();
+        self.planes.extend(pixels.iter().map(|colour| self.palette[usize::from(colour.as_index())][Self::LUMA]));
+        for chroma in Self::CHROMA {
+            for y in (0..height_pixels).step_by(2) {
+                for x in (0..width_pixels).step_by(2) {
+                    let (right, below) = ((x + 1).min(width_pixels - 1), (y + 1).min(height_pixels - 1));
+                    let total = [(x, y), (right, y), (x, below), (right, below)]
+                        .into_iter()
+                        .map(|(x, y)| u32::from(ycbcr_at(x, y)[chroma]))
+                        .sum::<u32>();
+
+                    self.planes.push(((total + 2) / 4) as u8);
+                }
+            }
+        }
+
+        self.writer.write_all(b"FRAME\n")?;
+        self.writer.write_all(&self.planes)?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+struct SharedStream<'s, TWriter: Write> {
+    stream: &'s RefCell<Y4mStream<TWriter>>
+}
+
+impl<'s, TWriter: Write> Pollable for SharedStream<'s, TWriter> {
+    fn poll(&mut self) -> EngineResult<()> {
+        let stream = &mut *self.stream.borrow_mut();
+        stream.encoder
+            .write_frame(&stream.frame)
+            .map_err(|_| notray_engine::Error::Str("Unable to write the Y4M recording"))
+    }
+}
+
+impl<'s, TWriter: Write> Canvas for SharedStream<'s, TWriter> {
+    fn set_pixel(&mut self, x: u16, y: u16, colour: Colour) -> EngineResult<()> {
+        self.stream.borrow_mut().frame.set_pixel(x, y, colour)
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use notray_engine::{Colour, FrameBufferCanvas, FrameExport, FrameRenderer};
+use notray_engine::raycasting::{Angle, HasCameraMut, ProjectionPlaneParameters, Scene, World, WorldCoordinates, WorldRendering};
+#[cfg(feature = "diagnostics")]
+use notray_engine::raycasting::{RaycastingDiagnostics, SaturationSite};
+use notray_engine::raycasting::worlds::assets;
+
+use crate::{Error, PoseOption, RaycastingParameters, Result, ResultCoalescing, WorldCommand, WorldCoordinate, Y4mRecorder};
+
+pub struct Render {
+    pose: Option<PoseOption>,
+    out: Option<PathBuf>,
+    record: Option<PathBuf>
+}
+
+impl Render {
+    pub fn new(pose: Option<PoseOption>, out: Option<PathBuf>, record: Option<PathBuf>) -> Self {
+        Self { pose, out, record }
+    }
+
+    fn coordinate_for(cells: f64) -> Result<WorldCoordinate> {
+        WorldCoordinate::checked_from_num(cells).ok_or_else(|| Error::String(format!("Pose coordinate {cells} is outside of the world")))
+    }
+
+    // Degrees are anticlockwise from East, the same as the engine's angles
+    fn angle_for(degrees: f64) -> Angle {
+        Angle::from_raw((degrees.rem_euclid(360.0) / 360.0 * 65536.0) as u32 as u16 as i16)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn report_diagnostics(diagnostics: &RaycastingDiagnostics<WorldCoordinate>) {
+        for site in SaturationSite::ALL {
+            let count = diagnostics.counts().count(site);
+            if count > 0 {
+                eprintln!("{site:?}: saturated {count} time(s)");
+            }
+        }
+
+        if let Some(first) = diagnostics.first() {
+            eprintln!(
+                "First saturation ({:?}) in column {} with the camera at {},{},{:.2}",
+                first.site(),
+                first.column(),
+                first.position().x(),
+                first.position().y(),
+                f64::from(first.direction().to_fixed_point().to_bits() as u16) / 65536.0 * 360.0);
+        }
+    }
+}
+
+impl WorldCommand for Render {
+    fn run<TWorld: World<WorldCoordinate> + WorldRendering>(self, world: TWorld, _size_cells: Option<(u16, u16)>) -> Result<()> {
+        let mut scene = Scene::<RaycastingParameters, _>::new(world);
+        if let Some(pose) = &self.pose {
+            let camera = scene.camera_mut();
+            camera.set_position(WorldCoordinates::new(Self::coordinate_for(pose.x_cells())?, Self::coordinate_for(pose.y_cells())?));
+            camera.set_direction(Self::angle_for(pose.angle_degrees()));
+        }
+
+        let (width_pixels, height_pixels) = (RaycastingParameters::CANVAS_WIDTH_PIXELS, RaycastingParameters::CANVAS_HEIGHT_PIXELS);
+        let mut canvas = FrameBufferCanvas::new(width_pixels, height_pixels, vec![Colour::new(0); usize::from(width_pixels) * usize::from(height_pixels)]);
+        scene.render_frame_onto(&mut canvas).coalesce_err()?;
+
+        #[cfg(feature = "diagnostics")]
+        Self::report_diagnostics(scene.diagnostics());
+
+        if let Some(out) = &self.out {
+            FrameExport::new(&canvas, assets::Palette::rgb_for)
+                .save(out)
+                .map_err(|error| Error::String(format!("{}: {error}", out.display())))?;
+        }
+
+        if let Some(record) = &self.record {
+            let recorder = Y4mRecorder::create(record, width_pixels, height_pixels, assets::Palette::rgb_for)?;
+            recorder
+                .record(&canvas)
+                .and_then(|_| recorder.finish())
+                .map_err(|error| Error::String(format!("{}: {error}", record.display())))?;
+        }
+
+        Ok(())
+    }
+}
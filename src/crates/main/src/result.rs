@@ -7,6 +7,16 @@ pub enum Error {
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Str(message) => f.write_str(message),
+            Self::String(message) => f.write_str(message),
+            Self::EngineError(error) => write!(f, "{error:?}")
+        }
+    }
+}
+
 type EngineResult<T> = notray_engine::Result<T>;
 type EngineError = notray_engine::Error;
 
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use notray_engine::raycasting::{Angle, CellTag, World, WorldAbsoluteCoordinate};
+use notray_engine::raycasting::worlds::GridWorld;
+use serde_json::{json, Value};
+
+use crate::{Error, Result};
+
+/*
+    The inverse of the importer, writing only what it reads back: a 'walls' layer (plus 'floors' and
+    'ceilings' when any cell has them), a tileset whose tiles carry their 'texture_id', and an object layer
+    with the spawn, the doors, and a 'cell' for each cell with a light level or flags.
+*/
+pub struct TiledExport {
+    map: Value
+}
+
+impl TiledExport {
+    const TILE_SIZE_PIXELS: u32 = 32;
+
+    pub fn from_world<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>) -> Self {
+        let cells = &world.cells()[..usize::from(world.width_cells()) * usize::from(world.height_cells())];
+        let walls: Vec<u32> = cells.iter().map(|cell| u32::from(cell.wall_id())).collect();
+        let floors: Vec<u32> = cells.iter().map(|cell| u32::from(cell.floor_texture_id())).collect();
+        let ceilings: Vec<u32> = cells.iter().map(|cell| u32::from(cell.ceiling_texture_id())).collect();
+        let tile_count = walls.iter().chain(&floors).chain(&ceilings).copied().max().unwrap_or(0);
+
+        let mut layers = vec![Self::tile_layer(world, 1, "walls", walls)];
+        for (name, texture_ids) in [("floors", floors), ("ceilings", ceilings)] {
+            if texture_ids.iter().any(|&texture_id| texture_id != 0) {
+                layers.push(Self::tile_layer(world, layers.len() + 1, name, texture_ids));
+            }
+        }
+
+        let objects = Self::objects(world);
+        let next_object_id = objects.len() + 1;
+        layers.push(json!({
+            "id": layers.len() + 1,
+            "name": "objects",
+            "type": "objectgroup",
+            "draworder": "topdown",
+            "opacity": 1,
+            "visible": true,
+            "x": 0,
+            "y": 0,
+            "objects": objects
+        }));
+
+        let tiles: Vec<Value> = (0..tile_count)
+            .map(|tile_id| json!({
+                "id": tile_id,
+                "properties": [{ "name": "texture_id", "type": "int", "value": tile_id + 1 }]
+            }))
+            .collect();
+
+        Self {
+            map: json!({
+                "type": "map",
+                "version": "1.10",
+                "orientation": "orthogonal",
+                "renderorder": "right-down",
+                "infinite": false,
+                "width": world.width_cells(),
+                "height": world.height_cells(),
+                "tilewidth": Self::TILE_SIZE_PIXELS,
+                "tileheight": Self::TILE_SIZE_PIXELS,
+                "nextlayerid": layers.len() + 1,
+                "nextobjectid": next_object_id,
+                "layers": layers,
+                "tilesets": [{
+                    "firstgid": 1,
+                    "name": "textures",
+                    "tilewidth": Self::TILE_SIZE_PIXELS,
+                    "tileheight": Self::TILE_SIZE_PIXELS,
+                    "tilecount": tile_count,
+                    "columns": 0,
+                    "margin": 0,
+                    "spacing": 0,
+                    "tiles": tiles
+                }]
+            })
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.map).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_json() + "\n")
+            .map_err(|error| Error::String(format!("Unable to write '{}': {error}", path.display())))
+    }
+
+    fn tile_layer<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>, id: usize, name: &str, data: Vec<u32>) -> Value {
+        json!({
+            "id": id,
+            "name": name,
+            "type": "tilelayer",
+            "width": world.width_cells(),
+            "height": world.height_cells(),
+            "opacity": 1,
+            "visible": true,
+            "x": 0,
+            "y": 0,
+            "data": data
+        })
+    }
+
+    fn objects<TCells: AsRef<[CellTag]>>(world: &GridWorld<TCells>) -> Vec<Value> {
+        let (spawn_x, spawn_y) = world.spawn_cell();
+        let spawn_angle = World::<WorldAbsoluteCoordinate>::spawn_angle(world);
+        let facing = [
+            (Angle::FACING_NORTH, "north"),
+            (Angle::FACING_EAST, "east"),
+            (Angle::FACING_WEST, "west")
+        ]
+            .into_iter()
+            .find(|(angle, _)| *angle == spawn_angle)
+            .map_or("south", |(_, facing)| facing);
+
+        let centre_of = |cell: u16| u32::from(cell) * Self::TILE_SIZE_PIXELS + Self::TILE_SIZE_PIXELS / 2;
+        let mut objects = vec![json!({
+            "id": 1,
+            "name": "spawn",
+            "type": "spawn",
+            "point": true,
+            "x": centre_of(spawn_x),
+            "y": centre_of(spawn_y),
+            "width": 0,
+            "height": 0,
+            "rotation": 0,
+            "visible": true,
+            "properties": [{ "name": "facing", "type": "string", "value": facing }]
+        })];
+
+        for cell_y in 0..world.height_cells() {
+            for cell_x in 0..world.width_cells() {
+                let Some(cell) = world.cell_at(cell_x, cell_y) else { continue; };
+                if let Some(door_id) = cell.door_id() {
+                    let id = objects.len() + 1;
+                    objects.push(Self::cell_object(id, "door", cell_x, cell_y, vec![json!({ "name": "door_id", "type": "int", "value": door_id })]));
+                }
+
+                let mut properties = Vec::new();
+                if cell.light_level() != 0 {
+                    properties.push(json!({ "name": "light_level", "type": "int", "value": cell.light_level() }));
+                }
+
+                for (name, is_set) in [("secret", cell.is_secret()), ("damaging", cell.is_damaging())] {
+                    if is_set {
+                        properties.push(json!({ "name": name, "type": "bool", "value": true }));
+                    }
+                }
+
+                if !properties.is_empty() {
+                    let id = objects.len() + 1;
+                    objects.push(Self::cell_object(id, "cell", cell_x, cell_y, properties));
+                }
+            }
+        }
+
+        objects
+    }
+
+    fn cell_object(id: usize, class: &str, cell_x: u16, cell_y: u16, properties: Vec<Value>) -> Value {
+        json!({
+            "id": id,
+            "name": "",
+            "type": class,
+            "x": u32::from(cell_x) * Self::TILE_SIZE_PIXELS,
+            "y": u32::from(cell_y) * Self::TILE_SIZE_PIXELS,
+            "width": Self::TILE_SIZE_PIXELS,
+            "height": Self::TILE_SIZE_PIXELS,
+            "rotation": 0,
+            "visible": true,
+            "properties": properties
+        })
+    }
+}
@@ -0,0 +1,112 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/*
+    Only the parts of Tiled's JSON map format (https://doc.mapeditor.org/en/stable/reference/json-map-format/)
+    that the importer understands or needs to reject; everything else is ignored by serde.
+*/
+#[derive(Deserialize)]
+pub struct TiledMap {
+    pub width: u32,
+    pub height: u32,
+    pub tilewidth: u32,
+    pub tileheight: u32,
+    #[serde(default)]
+    pub orientation: String,
+    #[serde(default)]
+    pub infinite: bool,
+    #[serde(default)]
+    pub layers: Vec<TiledLayer>,
+    #[serde(default)]
+    pub tilesets: Vec<TiledTileset>,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>
+}
+
+#[derive(Deserialize)]
+pub struct TiledLayer {
+    #[serde(rename = "type")]
+    pub layer_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub width: u32,
+    #[serde(default)]
+    pub height: u32,
+    pub data: Option<Value>,
+    pub encoding: Option<String>,
+    pub compression: Option<String>,
+    #[serde(default)]
+    pub objects: Vec<TiledObject>,
+    #[serde(default)]
+    pub layers: Vec<TiledLayer>,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>
+}
+
+#[derive(Deserialize)]
+pub struct TiledObject {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub object_type: String,
+    #[serde(default)]
+    pub class: String,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub width: f64,
+    #[serde(default)]
+    pub height: f64,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>
+}
+
+impl TiledObject {
+    // Tiled 1.9 renamed an object's 'type' to 'class', so maps from either side of that are accepted
+    pub fn class(&self) -> &str {
+        if self.class.is_empty() { &self.object_type } else { &self.class }
+    }
+
+    pub fn property(&self, name: &str) -> Option<&Value> { property(&self.properties, name) }
+}
+
+#[derive(Deserialize)]
+pub struct TiledTileset {
+    pub firstgid: u32,
+    #[serde(default)]
+    pub name: String,
+    pub source: Option<String>,
+    pub image: Option<String>,
+    #[serde(default)]
+    pub tilecount: u32,
+    #[serde(default)]
+    pub tiles: Vec<TiledTile>
+}
+
+#[derive(Deserialize)]
+pub struct TiledTile {
+    pub id: u32,
+    pub image: Option<String>,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>
+}
+
+impl TiledTile {
+    pub fn property(&self, name: &str) -> Option<&Value> { property(&self.properties, name) }
+}
+
+#[derive(Deserialize)]
+pub struct TiledProperty {
+    pub name: String,
+    pub value: Value
+}
+
+fn property<'p>(properties: &'p [TiledProperty], name: &str) -> Option<&'p Value> {
+    properties
+        .iter()
+        .find(|property| property.name == name)
+        .map(|property| &property.value)
+}
@@ -0,0 +1,361 @@
+use std::path::Path;
+
+use notray_engine::raycasting::{Angle, CellFlags, CellTag};
+use notray_engine::raycasting::worlds::GridWorld;
+use serde_json::Value;
+
+use crate::{Error, Result};
+use super::*;
+
+pub struct TiledImport {
+    world: GridWorld<Vec<CellTag>>,
+    warnings: Vec<String>
+}
+
+impl TiledImport {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|error| Error::String(format!("Unable to read '{}': {error}", path.display())))?;
+
+        Self::from_json(&json).map_err(|error| match error {
+            Error::String(message) => Error::String(format!("{}: {message}", path.display())),
+            error => error
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let map: TiledMap = serde_json::from_str(json)
+            .map_err(|error| Error::String(format!("Not a Tiled JSON map; {error}")))?;
+
+        Importer::new(&map)?.import()
+    }
+
+    pub fn into_world(self) -> GridWorld<Vec<CellTag>> { self.world }
+
+    pub fn warnings(&self) -> &[String] { &self.warnings }
+}
+
+enum TileLayerRole {
+    Walls,
+    Floors,
+    Ceilings
+}
+
+impl TileLayerRole {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "walls" | "wall" => Some(Self::Walls),
+            "floors" | "floor" => Some(Self::Floors),
+            "ceilings" | "ceiling" => Some(Self::Ceilings),
+            _ => None
+        }
+    }
+}
+
+/*
+    Tile layers are recognised by name - 'walls', 'floors' and 'ceilings' - and each tile becomes the texture
+    id in its 'texture_id' property, or its (one-based) position in its tileset.  Objects are recognised by
+    class: 'spawn' (with an optional 'facing' of north, east, south or west), 'door' (with an optional
+    'door_id') and 'cell' (with an optional 'light_level', 'secret' and 'damaging') are imported, whereas
+    'sprite' and 'trigger' are counted and reported until the engine has them.  Anything else that is not
+    imported is warned about, so that a warning-free import is one that can be exported again without loss.
+*/
+struct Importer<'m> {
+    map: &'m TiledMap,
+    width_cells: u16,
+    height_cells: u16,
+    wall_ids: Vec<u16>,
+    floor_texture_ids: Vec<u8>,
+    ceiling_texture_ids: Vec<u8>,
+    doors: Vec<(usize, u8)>,
+    lights_and_flags: Vec<(usize, u8, CellFlags)>,
+    spawn: Option<(u16, u16, Angle)>,
+    warnings: Vec<String>
+}
+
+impl<'m> Importer<'m> {
+    const FLIPPED_TILE_FLAGS: u32 = 0xf000_0000;
+
+    fn new(map: &'m TiledMap) -> Result<Self> {
+        if !map.orientation.is_empty() && map.orientation != "orthogonal" {
+            return Err(Error::String(format!("Only orthogonal maps are supported, not '{}'", map.orientation)));
+        }
+
+        if map.infinite {
+            return Err(Error::String("Infinite maps are not supported; resize the map to a fixed size".into()));
+        }
+
+        if let Some(tileset) = map.tilesets.iter().find(|tileset| tileset.source.is_some()) {
+            return Err(Error::String(format!("Tileset '{}' is external; embed it in the map", tileset.source.as_deref().unwrap_or_default())));
+        }
+
+        let (width_cells, height_cells) = match (u16::try_from(map.width), u16::try_from(map.height)) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 && map.tilewidth > 0 && map.tileheight > 0 => (width, height),
+            _ => return Err(Error::String(format!("A {}x{} map is not a size that can be imported", map.width, map.height)))
+        };
+
+        let cells = usize::from(width_cells) * usize::from(height_cells);
+        Ok(Self {
+            map,
+            width_cells,
+            height_cells,
+            wall_ids: vec![0; cells],
+            floor_texture_ids: vec![0; cells],
+            ceiling_texture_ids: vec![0; cells],
+            doors: Vec::new(),
+            lights_and_flags: Vec::new(),
+            spawn: None,
+            warnings: Vec::new()
+        })
+    }
+
+    fn import(mut self) -> Result<TiledImport> {
+        let map = self.map;
+        if !map.properties.is_empty() {
+            self.warnings.push(format!("Map has {} custom property(s), which are not imported", map.properties.len()));
+        }
+
+        for tileset in &map.tilesets {
+            let has_images = tileset.image.is_some() || tileset.tiles.iter().any(|tile| tile.image.is_some());
+            let has_other_properties = tileset.tiles.iter().flat_map(|tile| &tile.properties).any(|property| property.name != "texture_id");
+            if has_images || has_other_properties {
+                self.warnings.push(format!("Tileset '{}' has images or tile properties other than 'texture_id', which are not imported", tileset.name));
+            }
+        }
+
+        self.import_layers(&map.layers)?;
+
+        let mut cells: Vec<CellTag> = self.wall_ids
+            .iter()
+            .zip(&self.floor_texture_ids)
+            .zip(&self.ceiling_texture_ids)
+            .map(|((wall_id, floor_texture_id), ceiling_texture_id)| CellTag::from_wall_id(*wall_id)
+                .with_floor_texture_id(*floor_texture_id)
+                .with_ceiling_texture_id(*ceiling_texture_id))
+            .collect();
+
+        for &(index, door_id) in &self.doors {
+            cells[index] = CellTag::from_wall_id(0)
+                .with_floor_texture_id(self.floor_texture_ids[index])
+                .with_ceiling_texture_id(self.ceiling_texture_ids[index])
+                .with_door_id(door_id);
+        }
+
+        for &(index, light_level, flags) in &self.lights_and_flags {
+            cells[index] = cells[index].with_light_level(light_level).with_flags(flags);
+        }
+
+        let (spawn_x, spawn_y, spawn_angle) = match self.spawn {
+            Some(spawn) => spawn,
+            None => {
+                let index = cells
+                    .iter()
+                    .position(|cell| cell.wall_id() == 0)
+                    .ok_or(Error::Str("Map has no open cells to spawn in"))?;
+
+                let (spawn_x, spawn_y) = ((index % usize::from(self.width_cells)) as u16, (index / usize::from(self.width_cells)) as u16);
+                self.warnings.push(format!("No 'spawn' object, so spawning in the first open cell ({spawn_x}, {spawn_y})"));
+                (spawn_x, spawn_y, Angle::FACING_SOUTH)
+            }
+        };
+
+        let mut world = GridWorld::new(self.width_cells, self.height_cells, cells);
+        world.set_spawn(spawn_x, spawn_y, spawn_angle);
+        Ok(TiledImport { world, warnings: self.warnings })
+    }
+
+    fn import_layers(&mut self, layers: &'m [TiledLayer]) -> Result<()> {
+        for layer in layers {
+            if !layer.properties.is_empty() {
+                self.warnings.push(format!("Layer '{}' has {} custom property(s), which are not imported", layer.name, layer.properties.len()));
+            }
+
+            match layer.layer_type.as_str() {
+                "tilelayer" => self.import_tile_layer(layer)?,
+                "objectgroup" => self.import_objects(layer)?,
+                "group" => self.import_layers(&layer.layers)?,
+                layer_type => self.warnings.push(format!("Layer '{}' is an unsupported '{layer_type}' layer and was ignored", layer.name))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn import_tile_layer(&mut self, layer: &TiledLayer) -> Result<()> {
+        let Some(role) = TileLayerRole::from_name(&layer.name) else {
+            self.warnings.push(format!("Tile layer '{}' is not one of 'walls', 'floors' or 'ceilings' and was ignored", layer.name));
+            return Ok(());
+        };
+
+        if layer.encoding.as_deref().is_some_and(|encoding| encoding != "csv") || layer.compression.as_deref().is_some_and(|compression| !compression.is_empty()) {
+            return Err(Error::String(format!("Tile layer '{}' must use CSV encoding without compression", layer.name)));
+        }
+
+        if layer.width != self.map.width || layer.height != self.map.height {
+            return Err(Error::String(format!("Tile layer '{}' is not the same size as the map", layer.name)));
+        }
+
+        let gids = match &layer.data {
+            Some(Value::Array(gids)) if gids.len() == usize::from(self.width_cells) * usize::from(self.height_cells) => gids,
+            _ => return Err(Error::String(format!("Tile layer '{}' does not have a tile for every cell", layer.name)))
+        };
+
+        let mut flipped_tiles = 0;
+        for (index, gid) in gids.iter().enumerate() {
+            let gid = gid
+                .as_u64()
+                .and_then(|gid| u32::try_from(gid).ok())
+                .ok_or_else(|| Error::String(format!("Tile layer '{}' has an invalid tile '{gid}'", layer.name)))?;
+
+            if gid & Self::FLIPPED_TILE_FLAGS != 0 {
+                flipped_tiles += 1;
+            }
+
+            let gid = gid & !Self::FLIPPED_TILE_FLAGS;
+            if gid == 0 {
+                continue;
+            }
+
+            let texture_id = self.texture_id_for(gid)?;
+            let narrowed = |texture_id: u32| u8::try_from(texture_id)
+                .map_err(|_| Error::String(format!("Texture id {texture_id} in tile layer '{}' is too large for a floor or ceiling", layer.name)));
+
+            match role {
+                TileLayerRole::Walls => {
+                    self.wall_ids[index] = u16::try_from(texture_id)
+                        .map_err(|_| Error::String(format!("Texture id {texture_id} in tile layer '{}' is too large for a wall", layer.name)))?;
+                },
+
+                TileLayerRole::Floors => self.floor_texture_ids[index] = narrowed(texture_id)?,
+                TileLayerRole::Ceilings => self.ceiling_texture_ids[index] = narrowed(texture_id)?
+            }
+        }
+
+        if flipped_tiles > 0 {
+            self.warnings.push(format!("Tile layer '{}' has {flipped_tiles} flipped or rotated tile(s); they are imported unflipped", layer.name));
+        }
+
+        Ok(())
+    }
+
+    fn texture_id_for(&self, gid: u32) -> Result<u32> {
+        // Tiles map onto texture ids in tileset order unless a tile says otherwise with a 'texture_id' property
+        let tileset = self.map.tilesets
+            .iter()
+            .filter(|tileset| tileset.firstgid <= gid)
+            .max_by_key(|tileset| tileset.firstgid)
+            .ok_or_else(|| Error::String(format!("Tile {gid} does not belong to any tileset")))?;
+
+        let tile_id = gid - tileset.firstgid;
+        if tile_id >= tileset.tilecount {
+            return Err(Error::String(format!("Tile {gid} is beyond the {} tile(s) of tileset '{}'", tileset.tilecount, tileset.name)));
+        }
+
+        match tileset.tiles.iter().find(|tile| tile.id == tile_id).and_then(|tile| tile.property("texture_id")) {
+            Some(texture_id) => texture_id
+                .as_u64()
+                .and_then(|texture_id| u32::try_from(texture_id).ok())
+                .ok_or_else(|| Error::String(format!("Tile {tile_id} of tileset '{}' has an invalid 'texture_id'", tileset.name))),
+
+            None => Ok(tile_id + 1)
+        }
+    }
+
+    fn import_objects(&mut self, layer: &TiledLayer) -> Result<()> {
+        let (mut sprites, mut triggers) = (0, 0);
+        for object in &layer.objects {
+            let Some((cell_x, cell_y)) = self.cell_of(object) else {
+                self.warnings.push(format!("Object {} ('{}') is outside the map and was ignored", object.id, object.name));
+                continue;
+            };
+
+            match object.class().to_ascii_lowercase().as_str() {
+                "spawn" if self.spawn.is_some() => self.warnings.push(format!("Object {} is an extra 'spawn' and was ignored", object.id)),
+                "spawn" => self.spawn = Some((cell_x, cell_y, self.facing_of(object))),
+                "door" => {
+                    let door_id = match object.property("door_id") {
+                        Some(door_id) => door_id
+                            .as_u64()
+                            .and_then(|door_id| u8::try_from(door_id).ok())
+                            .ok_or_else(|| Error::String(format!("Door object {} has an invalid 'door_id'", object.id)))?,
+
+                        None => self.doors.len() as u8
+                    };
+
+                    self.doors.push((usize::from(cell_y) * usize::from(self.width_cells) + usize::from(cell_x), door_id));
+                },
+
+                "cell" => {
+                    let (light_level, flags) = Self::light_and_flags_of(object)?;
+                    self.lights_and_flags.push((usize::from(cell_y) * usize::from(self.width_cells) + usize::from(cell_x), light_level, flags));
+                },
+
+                "sprite" => sprites += 1,
+                "trigger" => triggers += 1,
+                class => self.warnings.push(format!("Object {} has an unsupported class '{class}' and was ignored", object.id))
+            }
+        }
+
+        if sprites > 0 {
+            self.warnings.push(format!("Object layer '{}' has {sprites} sprite(s), which the engine does not support yet", layer.name));
+        }
+
+        if triggers > 0 {
+            self.warnings.push(format!("Object layer '{}' has {triggers} trigger(s), which the engine does not support yet", layer.name));
+        }
+
+        Ok(())
+    }
+
+    fn light_and_flags_of(object: &TiledObject) -> Result<(u8, CellFlags)> {
+        let light_level = match object.property("light_level") {
+            Some(light_level) => light_level
+                .as_u64()
+                .and_then(|light_level| u8::try_from(light_level).ok())
+                .ok_or_else(|| Error::String(format!("Cell object {} has an invalid 'light_level'", object.id)))?,
+
+            None => 0
+        };
+
+        let mut flags = CellFlags::NONE;
+        for (name, flag) in [("secret", CellFlags::SECRET), ("damaging", CellFlags::DAMAGING)] {
+            let is_set = match object.property(name) {
+                Some(is_set) => is_set
+                    .as_bool()
+                    .ok_or_else(|| Error::String(format!("Cell object {} has an invalid '{name}'", object.id)))?,
+
+                None => false
+            };
+
+            if is_set {
+                flags = flags.union(flag);
+            }
+        }
+
+        Ok((light_level, flags))
+    }
+
+    fn cell_of(&self, object: &TiledObject) -> Option<(u16, u16)> {
+        // Points have no size, and anything larger belongs to the cell under its centre
+        let cell_x = ((object.x + object.width / 2.0) / f64::from(self.map.tilewidth)).floor();
+        let cell_y = ((object.y + object.height / 2.0) / f64::from(self.map.tileheight)).floor();
+        if cell_x >= 0.0 && cell_y >= 0.0 && cell_x < f64::from(self.width_cells) && cell_y < f64::from(self.height_cells) {
+            Some((cell_x as u16, cell_y as u16))
+        } else {
+            None
+        }
+    }
+
+    fn facing_of(&mut self, object: &TiledObject) -> Angle {
+        match object.property("facing").and_then(Value::as_str).map(str::to_ascii_lowercase).as_deref() {
+            Some("north") => Angle::FACING_NORTH,
+            Some("east") => Angle::FACING_EAST,
+            Some("west") => Angle::FACING_WEST,
+            Some("south") | None => Angle::FACING_SOUTH,
+            Some(facing) => {
+                self.warnings.push(format!("Spawn object {} faces '{facing}' rather than north, east, south or west", object.id));
+                Angle::FACING_SOUTH
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod exporter;
+pub use exporter::*;
+
+mod format;
+use format::*;
+
+mod importer;
+pub use importer::*;
+
+#[cfg(test)]
+mod tests;
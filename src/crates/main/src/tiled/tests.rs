@@ -0,0 +1,200 @@
+use notray_engine::raycasting::{Angle, CellFlags, CellTag, World, WorldAbsoluteCoordinate};
+use notray_engine::raycasting::worlds::GridWorld;
+use serde_json::{json, Value};
+
+use crate::Error;
+use super::*;
+
+fn designed_world() -> GridWorld<Vec<CellTag>> {
+    let (width_cells, height_cells) = (6, 5);
+    let mut world = GridWorld::new(width_cells, height_cells, vec![CellTag::from_wall_id(0); usize::from(width_cells) * usize::from(height_cells)]);
+    for cell_x in 0..width_cells {
+        world.set_cell(cell_x, 0, CellTag::from_wall_id(1));
+        world.set_cell(cell_x, height_cells - 1, CellTag::from_wall_id(2));
+    }
+
+    world.set_cell(3, 2, CellTag::from_wall_id(7));
+    world.set_cell(1, 1, CellTag::from_wall_id(0).with_floor_texture_id(3).with_ceiling_texture_id(4));
+    world.set_cell(2, 3, CellTag::from_wall_id(0).with_floor_texture_id(5));
+    world.set_cell(4, 1, CellTag::from_wall_id(0).with_ceiling_texture_id(2).with_door_id(9).with_light_level(40));
+    world.set_cell(2, 2, CellTag::from_wall_id(0).with_light_level(200).with_flags(CellFlags::SECRET));
+    world.set_cell(4, 3, CellTag::from_wall_id(0).with_flags(CellFlags::SECRET.union(CellFlags::DAMAGING)));
+    world.set_spawn(1, 2, Angle::FACING_NORTH);
+    world
+}
+
+fn round_tripped(world: &GridWorld<Vec<CellTag>>) -> TiledImport {
+    match TiledImport::from_json(&TiledExport::from_world(world).to_json()) {
+        Ok(import) => import,
+        Err(error) => panic!("exported map did not import: {error:?}")
+    }
+}
+
+fn minimal_map() -> Value {
+    json!({
+        "width": 2,
+        "height": 2,
+        "tilewidth": 32,
+        "tileheight": 32,
+        "orientation": "orthogonal",
+        "layers": [{ "type": "tilelayer", "name": "walls", "width": 2, "height": 2, "data": [1, 1, 0, 1] }],
+        "tilesets": [{ "firstgid": 1, "name": "textures", "tilecount": 1 }]
+    })
+}
+
+fn import_error(map: &Value) -> String {
+    match TiledImport::from_json(&map.to_string()) {
+        Ok(_) => panic!("map should have been rejected: {map}"),
+        Err(Error::String(message)) => message,
+        Err(error) => format!("{error:?}")
+    }
+}
+
+#[test]
+fn exported_worlds_import_unchanged() {
+    let world = designed_world();
+    let import = round_tripped(&world);
+    assert!(import.warnings().is_empty(), "unexpected warnings: {:?}", import.warnings());
+
+    let imported = import.into_world();
+    assert_eq!((imported.width_cells(), imported.height_cells()), (world.width_cells(), world.height_cells()));
+    assert_eq!(imported.cells(), world.cells());
+    assert_eq!(imported.spawn_cell(), world.spawn_cell());
+    assert!(World::<WorldAbsoluteCoordinate>::spawn_angle(&imported) == Angle::FACING_NORTH);
+}
+
+#[test]
+fn every_facing_survives_a_round_trip() {
+    for facing in [Angle::FACING_NORTH, Angle::FACING_EAST, Angle::FACING_SOUTH, Angle::FACING_WEST] {
+        let mut world = designed_world();
+        world.set_spawn(2, 2, facing);
+
+        let imported = round_tripped(&world).into_world();
+        assert!(World::<WorldAbsoluteCoordinate>::spawn_angle(&imported) == facing);
+    }
+}
+
+#[test]
+fn minimal_maps_import_with_a_warning_for_the_missing_spawn() {
+    let import = match TiledImport::from_json(&minimal_map().to_string()) {
+        Ok(import) => import,
+        Err(error) => panic!("minimal map did not import: {error:?}")
+    };
+
+    assert_eq!(import.warnings().len(), 1);
+    assert_eq!(import.into_world().spawn_cell(), (0, 1));
+}
+
+#[test]
+fn encoded_or_compressed_tile_layers_are_rejected() {
+    let mut base64 = minimal_map();
+    base64["layers"][0]["encoding"] = json!("base64");
+    base64["layers"][0]["data"] = json!("AQAAAAEAAAAAAAAAAQAAAA==");
+
+    let mut compressed = minimal_map();
+    compressed["layers"][0]["compression"] = json!("zlib");
+
+    for map in [base64, compressed] {
+        assert!(import_error(&map).contains("CSV encoding without compression"));
+    }
+}
+
+#[test]
+fn external_tilesets_are_rejected() {
+    let mut map = minimal_map();
+    map["tilesets"][0] = json!({ "firstgid": 1, "source": "textures.tsj" });
+
+    assert!(import_error(&map).contains("'textures.tsj' is external"));
+}
+
+#[test]
+fn infinite_and_non_orthogonal_maps_are_rejected() {
+    let mut infinite = minimal_map();
+    infinite["infinite"] = json!(true);
+    let mut isometric = minimal_map();
+    isometric["orientation"] = json!("isometric");
+
+    assert!(import_error(&infinite).contains("Infinite maps are not supported"));
+    assert!(import_error(&isometric).contains("Only orthogonal maps are supported"));
+}
+
+#[test]
+fn flipped_tiles_are_imported_unflipped_with_a_warning() {
+    let mut map = minimal_map();
+    map["layers"][0]["data"] = json!([1, 0x8000_0001_u32, 0, 0x4000_0001_u32]);
+
+    let import = match TiledImport::from_json(&map.to_string()) {
+        Ok(import) => import,
+        Err(error) => panic!("flipped tiles should import: {error:?}")
+    };
+
+    assert!(import.warnings().iter().any(|warning| warning.contains("2 flipped or rotated tile(s)")));
+    let walls: Vec<u16> = import.into_world().cells().iter().map(|cell| cell.wall_id()).collect();
+    assert_eq!(walls, [1, 1, 0, 1]);
+}
+
+#[test]
+fn tiles_outside_their_tileset_are_rejected() {
+    let mut map = minimal_map();
+    map["layers"][0]["data"] = json!([1, 2, 0, 1]);
+
+    assert!(import_error(&map).contains("beyond the 1 tile(s)"));
+}
+
+#[test]
+fn cell_objects_set_the_light_level_and_flags_of_their_cell() {
+    let mut map = minimal_map();
+    map["layers"].as_array_mut().unwrap().push(json!({
+        "type": "objectgroup",
+        "name": "objects",
+        "objects": [
+            { "id": 1, "type": "spawn", "x": 16, "y": 48 },
+            { "id": 2, "type": "cell", "x": 0, "y": 32, "width": 32, "height": 32, "properties": [
+                { "name": "light_level", "type": "int", "value": 96 },
+                { "name": "damaging", "type": "bool", "value": true },
+                { "name": "secret", "type": "bool", "value": false }
+            ]}
+        ]
+    }));
+
+    let import = match TiledImport::from_json(&map.to_string()) {
+        Ok(import) => import,
+        Err(error) => panic!("cell objects should import: {error:?}")
+    };
+
+    assert!(import.warnings().is_empty(), "unexpected warnings: {:?}", import.warnings());
+    let cell = import.into_world().cell_at(0, 1).unwrap();
+    assert_eq!((cell.wall_id(), cell.light_level(), cell.is_damaging(), cell.is_secret()), (0, 96, true, false));
+}
+
+#[test]
+fn cell_objects_with_invalid_properties_are_rejected() {
+    for (name, value) in [("light_level", json!(256)), ("light_level", json!("bright")), ("secret", json!(1))] {
+        let mut map = minimal_map();
+        map["layers"].as_array_mut().unwrap().push(json!({
+            "type": "objectgroup",
+            "name": "objects",
+            "objects": [{ "id": 7, "type": "cell", "x": 0, "y": 32, "properties": [{ "name": name, "value": value }] }]
+        }));
+
+        assert!(import_error(&map).contains(&format!("Cell object 7 has an invalid '{name}'")));
+    }
+}
+
+#[test]
+fn parts_of_the_map_that_are_not_imported_are_warned_about() {
+    let mut map = minimal_map();
+    map["properties"] = json!([{ "name": "author", "type": "string", "value": "someone" }]);
+    map["layers"][0]["properties"] = json!([{ "name": "music", "type": "string", "value": "dungeon.mod" }]);
+    map["tilesets"][0]["image"] = json!("textures.png");
+
+    let import = match TiledImport::from_json(&map.to_string()) {
+        Ok(import) => import,
+        Err(error) => panic!("map should import: {error:?}")
+    };
+
+    let warnings = import.warnings();
+    assert!(warnings.iter().any(|warning| warning.starts_with("Map has 1 custom property(s)")), "{warnings:?}");
+    assert!(warnings.iter().any(|warning| warning.starts_with("Layer 'walls' has 1 custom property(s)")), "{warnings:?}");
+    assert!(warnings.iter().any(|warning| warning.starts_with("Tileset 'textures' has images")), "{warnings:?}");
+}
@@ -3,6 +3,7 @@ use syn::{parse_macro_input, LitFloat};
 
 mod fixed_point_functions;
 mod raycasting_parameters;
+mod textures;
 
 #[proc_macro]
 pub fn angle_from_degrees(items: TokenStream) -> TokenStream {
@@ -15,3 +16,9 @@ pub fn _raycasting_parameters(items: TokenStream) -> TokenStream {
     let args = parse_macro_input!(items as raycasting_parameters::ArgumentTokens);
     raycasting_parameters::raycasting_parameters(args).into()
 }
+
+#[proc_macro]
+pub fn _include_texture(items: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(items as textures::ArgumentTokens);
+    textures::include_texture(args).into()
+}
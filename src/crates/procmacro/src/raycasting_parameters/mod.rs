@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, LitFloat, LitInt, Token};
+use syn::{Ident, LitFloat, LitInt, Token, Type};
 use syn::parse::{Parse, ParseStream};
 
 mod projection_plane_parameters;
@@ -21,6 +21,8 @@ pub struct ArgumentTokens {
     sine_lookup_msbs: LitInt,
     _delimiter_5: Token![,],
     sine_lookup_size_degrees: LitInt,
+    _delimiter_6: Token![,],
+    world_coordinate: Type,
     no_extra_tokens: bool
 }
 
@@ -38,6 +40,8 @@ impl Parse for ArgumentTokens {
             sine_lookup_msbs: input.parse()?,
             _delimiter_5: input.parse()?,
             sine_lookup_size_degrees: input.parse()?,
+            _delimiter_6: input.parse()?,
+            world_coordinate: input.parse()?,
             no_extra_tokens: input.is_empty()
         })
     }
@@ -51,11 +55,14 @@ pub fn raycasting_parameters(args: ArgumentTokens) -> TokenStream {
 
     let type_ident = &args.type_ident;
     let engine_parameters_ident = quote! { ::notray_engine::raycasting::EngineParameters };
+    let world_coordinate = &args.world_coordinate;
 
     quote! {
         pub struct #type_ident;
 
         impl #engine_parameters_ident for #type_ident {
+            type Coordinate = #world_coordinate;
+
             const MAX_RAY_CELL_PROBES: usize = 64;
         }
 
@@ -0,0 +1,58 @@
+pub fn generate_mip_levels(pixels: &[u8], palette: &[u8], width_pixels: usize, height_pixels: usize, mip_levels: usize, transparent_index: Option<u8>) -> Vec<u8> {
+    let mut mipmapped_pixels = Vec::from(pixels);
+    let mut level_offset = 0;
+    let (mut level_width, mut level_height) = (width_pixels, height_pixels);
+    for _ in 1..mip_levels {
+        let (next_width, next_height) = ((level_width / 2).max(1), (level_height / 2).max(1));
+        for y in 0..next_height {
+            for x in 0..next_width {
+                let texels = [
+                    (2 * x, 2 * y),
+                    ((2 * x + 1).min(level_width - 1), 2 * y),
+                    (2 * x, (2 * y + 1).min(level_height - 1)),
+                    ((2 * x + 1).min(level_width - 1), (2 * y + 1).min(level_height - 1))
+                ].map(|(u, v)| mipmapped_pixels[level_offset + v * level_width + u]);
+
+                mipmapped_pixels.push(nearest_palette_index_to_average_of(&texels, palette, transparent_index));
+            }
+        }
+
+        level_offset += level_width * level_height;
+        (level_width, level_height) = (next_width, next_height);
+    }
+
+    mipmapped_pixels
+}
+
+fn nearest_palette_index_to_average_of(texels: &[u8], palette: &[u8], transparent_index: Option<u8>) -> u8 {
+    let rgb_for = |index: u8| {
+        let index = index as usize * 3;
+        (palette[index] as i32, palette[index + 1] as i32, palette[index + 2] as i32)
+    };
+
+    /*
+        Transparent texels have no colour to average, so only the opaque ones are; a block that is mostly
+        transparent stays transparent, otherwise holes would fill in as the texture gets further away.
+    */
+    let is_opaque = |index: &u8| Some(*index) != transparent_index;
+    let count = texels.iter().filter(|texel| is_opaque(texel)).count() as i32;
+    if let Some(transparent_index) = transparent_index && count * 2 < texels.len() as i32 {
+        return transparent_index;
+    }
+
+    let (red, green, blue) = texels
+        .iter()
+        .filter(|texel| is_opaque(texel))
+        .map(|&texel| rgb_for(texel))
+        .fold((0, 0, 0), |sum, rgb| (sum.0 + rgb.0, sum.1 + rgb.1, sum.2 + rgb.2));
+
+    let average = (red / count, green / count, blue / count);
+    (0..=255_u8)
+        .filter(is_opaque)
+        .min_by_key(|&index| {
+            let rgb = rgb_for(index);
+            let error = (rgb.0 - average.0, rgb.1 - average.1, rgb.2 - average.2);
+            error.0 * error.0 + error.1 * error.1 + error.2 * error.2
+        })
+        .unwrap()
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, LitInt, LitStr, Token};
+use syn::parse::{Parse, ParseStream};
+
+mod mipmaps;
+use mipmaps::*;
+
+pub struct ArgumentTokens {
+    pixels_path: LitStr,
+    _delimiter_1: Token![,],
+    palette_path: LitStr,
+    _delimiter_2: Token![,],
+    width_pixels: LitInt,
+    _delimiter_3: Token![,],
+    height_pixels: LitInt,
+    _delimiter_4: Token![,],
+    wrapping: Ident,
+    _delimiter_5: Token![,],
+    mip_levels: LitInt,
+    transparent_colour: Option<LitInt>,
+    no_extra_tokens: bool
+}
+
+impl Parse for ArgumentTokens {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            pixels_path: input.parse()?,
+            _delimiter_1: input.parse()?,
+            palette_path: input.parse()?,
+            _delimiter_2: input.parse()?,
+            width_pixels: input.parse()?,
+            _delimiter_3: input.parse()?,
+            height_pixels: input.parse()?,
+            _delimiter_4: input.parse()?,
+            wrapping: input.parse()?,
+            _delimiter_5: input.parse()?,
+            mip_levels: input.parse()?,
+            transparent_colour: if input.parse::<Option<Token![,]>>()?.is_some() { Some(input.parse()?) } else { None },
+            no_extra_tokens: input.is_empty()
+        })
+    }
+}
+
+pub fn include_texture(args: ArgumentTokens) -> TokenStream {
+    assert!(args.no_extra_tokens, "Too many tokens passed to macro");
+
+    let width_pixels: u8 = args.width_pixels
+        .base10_parse()
+        .expect("Texture Width must be a literal number of pixels");
+
+    assert!(width_pixels != 0 && width_pixels.is_power_of_two(), "Texture Width must be a power of two");
+
+    let height_pixels: u8 = args.height_pixels
+        .base10_parse()
+        .expect("Texture Height must be a literal number of pixels");
+
+    assert!(height_pixels != 0 && height_pixels.is_power_of_two(), "Texture Height must be a power of two");
+
+    let wrapping = args.wrapping.to_string();
+    assert!(wrapping == "Stretched" || wrapping == "Repeated", "Texture Wrapping must be either Stretched or Repeated");
+
+    let mip_levels: u8 = args.mip_levels
+        .base10_parse()
+        .expect("Number of Mip Levels must be a literal number");
+
+    assert!(
+        mip_levels >= 1 && mip_levels as u32 <= width_pixels.min(height_pixels).ilog2() + 1,
+        "Number of Mip Levels must be at least one and cannot shrink the texture beyond a single pixel");
+
+    let transparent_index: Option<u8> = args.transparent_colour
+        .as_ref()
+        .map(|transparent_colour| transparent_colour.base10_parse().expect("Transparent Colour must be a literal palette index"));
+
+    let pixels_path = manifest_relative_path_for(&args.pixels_path);
+    let pixels = std::fs::read(&pixels_path).expect("Unable to read the texture's pixels");
+    assert!(
+        pixels.len() == width_pixels as usize * height_pixels as usize,
+        "Texture pixels must be a raw array of palette indices exactly Width x Height bytes in size");
+
+    let palette_path = manifest_relative_path_for(&args.palette_path);
+    let palette = std::fs::read(&palette_path).expect("Unable to read the palette");
+    assert!(palette.len() == 256 * 3, "Palette must be a raw array of 256 RGB triplets");
+
+    let mipmapped_pixels = generate_mip_levels(&pixels, &palette, width_pixels as usize, height_pixels as usize, mip_levels as usize, transparent_index);
+    let mipmapped_pixels_len = mipmapped_pixels.len();
+
+    let pixels_path = pixels_path.to_str().expect("Texture path must be valid UTF-8");
+    let palette_path = palette_path.to_str().expect("Palette path must be valid UTF-8");
+    let descriptor_ident = quote! { ::notray_engine::raycasting::TextureDescriptor };
+    let wrapping_ident = &args.wrapping;
+    let with_transparent_colour = transparent_index.map(|index| quote! { .with_transparent_colour(::notray_engine::Colour::new(#index)) });
+
+    quote! {
+        {
+            const _TRACK_PIXELS_CHANGES: &[u8] = include_bytes!(#pixels_path);
+            const _TRACK_PALETTE_CHANGES: &[u8] = include_bytes!(#palette_path);
+            static PIXELS: [u8; #mipmapped_pixels_len] = [#(#mipmapped_pixels),*];
+
+            #descriptor_ident::new(
+                #width_pixels,
+                #height_pixels,
+                ::notray_engine::raycasting::TextureWrapping::#wrapping_ident,
+                &PIXELS)
+                .with_mip_levels(#mip_levels)
+                #with_transparent_colour
+        }
+    }
+}
+
+fn manifest_relative_path_for(path: &LitStr) -> PathBuf {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+    PathBuf::from(manifest_dir).join(path.value())
+}